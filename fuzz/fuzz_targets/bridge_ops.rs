@@ -0,0 +1,20 @@
+#![no_main]
+// Arbitrary operation sequences against the bridge and validator programs; shares the
+// model and driver in tests/property/ with the proptest suite.
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../tests/property/model.rs"]
+mod model;
+#[path = "../../tests/property/vm.rs"]
+mod vm;
+
+// Each input boots a fresh bank, so long sequences only slow the fuzzer down
+const MAX_OPS: usize = 64;
+
+fuzz_target!(|input: (u8, Vec<model::Op>)| {
+    let (required_signatures, mut ops) = input;
+    ops.truncate(MAX_OPS);
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(vm::check_sequence(required_signatures % 4 + 1, &ops));
+});
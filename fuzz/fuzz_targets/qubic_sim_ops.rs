@@ -0,0 +1,11 @@
+#![no_main]
+// Arbitrary operation sequences against the HM25 simulator; shares the checks in
+// tests/property/qubic_model.rs with the proptest suite.
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../tests/property/qubic_model.rs"]
+mod qubic_model;
+
+fuzz_target!(|ops: Vec<qubic_model::QubicOp>| {
+    qubic_model::check_qubic_sequence(&ops);
+});
@@ -0,0 +1,328 @@
+// Off-chain helpers for building quantum_link instructions (enabled by the `client` feature).
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token;
+use validator::client::{validation_state_address, validation_vote_address, validator_config_address};
+use validator::state::Attestation;
+
+pub fn bridge_state_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"bridge_state"], &crate::ID).0
+}
+
+pub fn bridge_transaction_address(lock_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"bridge_tx", lock_id.to_le_bytes().as_ref()], &crate::ID).0
+}
+
+pub fn inbound_unlock_address(qubic_lock_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"inbound", qubic_lock_id.to_le_bytes().as_ref()], &crate::ID).0
+}
+
+pub fn mint_ledger_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"mint_ledger", mint.as_ref()], &crate::ID).0
+}
+
+/// The bridge's only vault for `mint`: the bridge state's associated token account.
+pub fn vault_address(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&bridge_state_address(), mint)
+}
+
+pub fn receipt_address(lock_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"receipt", lock_id.to_le_bytes().as_ref()], &crate::ID).0
+}
+
+/// Validators are registered in the validator program, not in quantum_link.
+pub fn validator_info_address(validator: &Pubkey) -> Pubkey {
+    validator::client::validator_info_address(validator)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn lock_assets(
+    user: &Pubkey,
+    next_lock_id: u64,
+    mint: &Pubkey,
+    user_token_account: &Pubkey,
+    bridge_token_account: &Pubkey,
+    amount: u64,
+    qubic_destination: [u8; 32],
+    memo: [u8; 64],
+) -> Instruction {
+    let accounts = crate::accounts::LockAssets {
+        bridge_state: bridge_state_address(),
+        bridge_transaction: bridge_transaction_address(next_lock_id),
+        user: *user,
+        user_token_account: *user_token_account,
+        bridge_token_account: *bridge_token_account,
+        mint_ledger: mint_ledger_address(mint),
+        token_program: token::ID,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::LockAssets { amount, qubic_destination, memo }.data(),
+    }
+}
+
+/// Release `attestation`, whose domain must be the one stored in the bridge state.
+/// `approvers` are the validators whose votes are recounted; the program only counts those
+/// still active and staked.
+pub fn unlock_assets(
+    validator: &Pubkey,
+    attestation: &Attestation,
+    recipient_token_account: &Pubkey,
+    bridge_token_account: &Pubkey,
+    approvers: &[Pubkey],
+) -> Instruction {
+    let digest = attestation.digest();
+    let mut accounts = crate::accounts::UnlockAssets {
+        bridge_state: bridge_state_address(),
+        inbound_unlock: inbound_unlock_address(attestation.lock_id),
+        validator_info: validator_info_address(validator),
+        validator_config: validator_config_address(),
+        validation_state: validation_state_address(&digest),
+        validator: *validator,
+        recipient_token_account: *recipient_token_account,
+        bridge_token_account: *bridge_token_account,
+        mint_ledger: mint_ledger_address(&attestation.mint),
+        token_program: token::ID,
+        validator_program: validator::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    for approver in approvers {
+        accounts.push(AccountMeta::new_readonly(validation_vote_address(&digest, approver), false));
+        accounts.push(AccountMeta::new_readonly(validator_info_address(approver), false));
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: crate::instruction::UnlockAssets {
+            lock_id: attestation.lock_id,
+            mint: attestation.mint,
+            recipient: attestation.recipient,
+            amount: attestation.amount,
+        }
+        .data(),
+    }
+}
+
+/// Record `qubic_tx_hash` as the delivery of the outbound lock `attestation` names. The
+/// program only accepts it once the attestation is approved.
+pub fn validate_bridge(validator: &Pubkey, attestation: &Attestation, qubic_tx_hash: [u8; 32]) -> Instruction {
+    let lock_id = attestation.lock_id;
+    let accounts = crate::accounts::ValidateBridge {
+        bridge_state: bridge_state_address(),
+        bridge_transaction: bridge_transaction_address(lock_id),
+        validation_state: validation_state_address(&attestation.digest()),
+        validator_info: validator_info_address(validator),
+        validator_config: validator_config_address(),
+        validator: *validator,
+        validator_program: validator::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::ValidateBridge { bridge_id: lock_id, qubic_tx_hash }.data(),
+    }
+}
+
+pub fn update_bridge_state(validator: &Pubkey, lock_id: u64, status: u8, confirmations: u64) -> Instruction {
+    let accounts = crate::accounts::UpdateBridgeState {
+        bridge_state: bridge_state_address(),
+        bridge_transaction: bridge_transaction_address(lock_id),
+        validator_info: validator_info_address(validator),
+        validator: *validator,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::UpdateBridgeState { bridge_id: lock_id, status, confirmations }.data(),
+    }
+}
+
+pub fn close_lock(payer: &Pubkey, user: &Pubkey, lock_id: u64) -> Instruction {
+    let accounts = crate::accounts::CloseLock {
+        bridge_state: bridge_state_address(),
+        bridge_transaction: bridge_transaction_address(lock_id),
+        receipt: receipt_address(lock_id),
+        user: *user,
+        payer: *payer,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::CloseLock { lock_id }.data(),
+    }
+}
+
+pub fn get_bridge_info() -> Instruction {
+    let accounts = crate::accounts::GetBridgeInfo {
+        bridge_state: bridge_state_address(),
+        validator_config: validator_config_address(),
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::GetBridgeInfo {}.data(),
+    }
+}
+
+pub fn migrate_bridge_state(admin: &Pubkey, genesis_hash: [u8; 32]) -> Instruction {
+    let accounts = crate::accounts::MigrateBridgeState {
+        bridge_state: bridge_state_address(),
+        admin: *admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateBridgeState { genesis_hash }.data(),
+    }
+}
+
+pub fn migrate_bridge_transaction(admin: &Pubkey, lock_id: u64) -> Instruction {
+    let accounts = crate::accounts::MigrateBridgeTransaction {
+        bridge_state: bridge_state_address(),
+        bridge_transaction: bridge_transaction_address(lock_id),
+        admin: *admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateBridgeTransaction { lock_id }.data(),
+    }
+}
+
+pub fn migrate_mint_ledger(admin: &Pubkey, mint: Pubkey) -> Instruction {
+    let accounts = crate::accounts::MigrateMintLedger {
+        bridge_state: bridge_state_address(),
+        mint_ledger: mint_ledger_address(&mint),
+        admin: *admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateMintLedger { mint }.data(),
+    }
+}
+
+/// Signed by the admin or the guardian.
+pub fn emergency_pause(authority: &Pubkey) -> Instruction {
+    let accounts = crate::accounts::EmergencyPause {
+        bridge_state: bridge_state_address(),
+        authority: *authority,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::EmergencyPause {}.data(),
+    }
+}
+
+/// Signed by the admin or the guardian.
+pub fn pause_operations(authority: &Pubkey, flags: u8) -> Instruction {
+    let accounts = crate::accounts::PauseOperations {
+        bridge_state: bridge_state_address(),
+        authority: *authority,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::PauseOperations { flags }.data(),
+    }
+}
+
+pub fn unpause_operations(admin: &Pubkey, flags: u8) -> Instruction {
+    let accounts = crate::accounts::UnpauseOperations {
+        bridge_state: bridge_state_address(),
+        admin: *admin,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::UnpauseOperations { flags }.data(),
+    }
+}
+
+/// Pausing may be signed by the admin or the guardian, resuming only by the admin.
+pub fn set_mint_paused(authority: &Pubkey, mint: Pubkey, is_paused: bool) -> Instruction {
+    let accounts = crate::accounts::SetMintPaused {
+        bridge_state: bridge_state_address(),
+        mint_ledger: mint_ledger_address(&mint),
+        authority: *authority,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::SetMintPaused { mint, is_paused }.data(),
+    }
+}
+
+pub fn set_guardian(admin: &Pubkey, guardian: Pubkey) -> Instruction {
+    let accounts = crate::accounts::SetGuardian {
+        bridge_state: bridge_state_address(),
+        admin: *admin,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::SetGuardian { guardian }.data(),
+    }
+}
+
+pub fn configure_circuit_breaker(
+    admin: &Pubkey,
+    max_unlock_bps: u16,
+    max_unlocks_per_window: u16,
+    unlock_window_slots: u64,
+) -> Instruction {
+    let accounts = crate::accounts::ConfigureCircuitBreaker {
+        bridge_state: bridge_state_address(),
+        admin: *admin,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::ConfigureCircuitBreaker {
+            max_unlock_bps,
+            max_unlocks_per_window,
+            unlock_window_slots,
+        }
+        .data(),
+    }
+}
+
+pub fn report_phantom_lock(reporter: &Pubkey, attestation: Attestation) -> Instruction {
+    let accounts = crate::accounts::ReportPhantomLock {
+        bridge_state: bridge_state_address(),
+        validation_state: validation_state_address(&attestation.digest()),
+        reporter: *reporter,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::ReportPhantomLock { attestation }.data(),
+    }
+}
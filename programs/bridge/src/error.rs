@@ -100,4 +100,55 @@ pub enum BridgeError {
     
     #[msg("Bridge maintenance mode")]
     BridgeMaintenanceMode,
+    
+    #[msg("Arithmetic overflow or underflow")]
+    ArithmeticOverflow,
+    
+    #[msg("Vault balance does not match recorded liabilities")]
+    InvariantViolation,
+    
+    #[msg("Bridge transaction is not finalized")]
+    TransactionNotFinalized,
+    
+    #[msg("Retention period has not elapsed")]
+    RetentionPeriodActive,
+    
+    #[msg("Invalid deployment identity")]
+    InvalidDomain,
+    
+    #[msg("Account data matches no known layout")]
+    UnknownAccountLayout,
+    
+    #[msg("This operation is paused")]
+    OperationPaused,
+    
+    #[msg("Bridging is paused for this mint")]
+    MintPaused,
+    
+    #[msg("Only the admin or guardian can pause")]
+    UnauthorizedPauser,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Invalid circuit breaker limits")]
+    InvalidCircuitBreakerConfig,
+    
+    #[msg("The attested lock exists")]
+    LockExists,
+    
+    #[msg("Lock was already delivered to Qubic")]
+    LockAlreadyDelivered,
+    
+    #[msg("Refund timeout has not elapsed")]
+    RefundTimeoutActive,
+    
+    #[msg("Invalid bridge transaction status transition")]
+    InvalidStatusTransition,
+    
+    #[msg("Validator vote account does not match the attestation")]
+    InvalidValidatorVote,
+    
+    #[msg("Attestation is for the other bridge direction")]
+    WrongDirection,
 }
\ No newline at end of file
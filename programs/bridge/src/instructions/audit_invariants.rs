@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{BridgeState, MintLedger, PAUSE_REASON_VAULT_SHORTFALL};
+
+pub fn handler(ctx: Context<AuditInvariants>) -> Result<()> {
+    let bridge_state = &mut ctx.accounts.bridge_state;
+    let mint_ledger = &mut ctx.accounts.mint_ledger;
+    let vault_balance = ctx.accounts.bridge_token_account.amount;
+    let clock = Clock::get()?;
+
+    // Vault must cover everything locked and not yet unlocked, plus retained fees.
+    // Only a shortfall is a breach; a surplus (e.g. a direct transfer) is just logged.
+    let expected_balance = mint_ledger.expected_vault_balance()?;
+    let shortfall = mint_ledger.shortfall(vault_balance)?;
+    mint_ledger.last_audit_at = clock.unix_timestamp;
+
+    if shortfall > 0 {
+        // Do not fail the instruction: the pause has to be persisted
+        bridge_state.trip(PAUSE_REASON_VAULT_SHORTFALL, clock.unix_timestamp);
+
+        msg!(
+            "Invariant violation for mint {}: vault={}, expected={} (locked={}, unlocked={}, fees={}). Bridge paused",
+            mint_ledger.mint,
+            vault_balance,
+            expected_balance,
+            mint_ledger.total_locked,
+            mint_ledger.total_unlocked,
+            mint_ledger.total_fees
+        );
+        return Ok(());
+    }
+
+    msg!(
+        "Invariants hold for mint {}: vault={}, expected={}, surplus={}",
+        mint_ledger.mint,
+        vault_balance,
+        expected_balance,
+        vault_balance - expected_balance
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AuditInvariants<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        has_one = admin
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint_ledger", mint_ledger.mint.as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+    
+    #[account(
+        associated_token::mint = mint_ledger.mint,
+        associated_token::authority = bridge_state
+    )]
+    pub bridge_token_account: Account<'info, TokenAccount>,
+    
+    pub admin: Signer<'info>,
+}
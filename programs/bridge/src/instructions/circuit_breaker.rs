@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use validator::state::{Attestation, ValidationState};
+use crate::state::*;
+use crate::error::BridgeError;
+
+/// Set the unlock tripwires. A zero limit turns its tripwire off; the vault shortfall and
+/// unknown-lock tripwires are always armed.
+pub fn configure_circuit_breaker_handler(
+    ctx: Context<ConfigureCircuitBreaker>,
+    max_unlock_bps: u16,
+    max_unlocks_per_window: u16,
+    unlock_window_slots: u64,
+) -> Result<()> {
+    require!(max_unlock_bps <= 10000, BridgeError::InvalidCircuitBreakerConfig);
+    require!(
+        max_unlocks_per_window == 0 || unlock_window_slots > 0,
+        BridgeError::InvalidCircuitBreakerConfig
+    );
+
+    let bridge_state = &mut ctx.accounts.bridge_state;
+    bridge_state.max_unlock_bps = max_unlock_bps;
+    bridge_state.max_unlocks_per_window = max_unlocks_per_window;
+    bridge_state.unlock_window_slots = unlock_window_slots;
+    bridge_state.window_start_slot = Clock::get()?.slot;
+    bridge_state.window_unlocks = 0;
+
+    msg!(
+        "Circuit breaker: max unlock {} bps, {} unlocks per {} slots",
+        max_unlock_bps,
+        max_unlocks_per_window,
+        unlock_window_slots
+    );
+
+    Ok(())
+}
+
+/// Anyone may show that validators voted for a lock this bridge never made, which trips the
+/// breaker. Lock ids start at 1 and are never reused, so an id of 0 or at or past
+/// `next_lock_id` cannot have been locked. Only outbound attestations name Solana locks;
+/// inbound ones carry HM25's lock ids, which this program has no counter for.
+pub fn report_phantom_lock_handler(ctx: Context<ReportPhantomLock>, attestation: Attestation) -> Result<()> {
+    let bridge_state = &mut ctx.accounts.bridge_state;
+    require!(
+        attestation.lock_id == 0 || attestation.lock_id >= bridge_state.next_lock_id,
+        BridgeError::LockExists
+    );
+
+    bridge_state.trip(PAUSE_REASON_PHANTOM_LOCK, Clock::get()?.unix_timestamp);
+
+    msg!(
+        "Circuit breaker tripped (unknown lock): {} approvals for Lock ID {}, next is {}. Bridge paused",
+        ctx.accounts.validation_state.approvals,
+        attestation.lock_id,
+        bridge_state.next_lock_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureCircuitBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        has_one = admin @ BridgeError::UnauthorizedAdmin
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestation: Attestation)]
+pub struct ReportPhantomLock<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        constraint = attestation.domain == bridge_state.attestation_domain() @ BridgeError::InvalidDomain,
+        constraint = attestation.direction == BRIDGE_DIRECTION_SOLANA_TO_QUBIC @ BridgeError::WrongDirection
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    #[account(
+        seeds = [b"validation", attestation.digest().as_ref()],
+        bump = validation_state.bump,
+        seeds::program = validator::ID,
+        constraint = validation_state.approvals > 0 @ BridgeError::InvalidValidatorSignature
+    )]
+    pub validation_state: Account<'info, ValidationState>,
+    
+    pub reporter: Signer<'info>,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::{BridgeState, BridgeTransaction, ProcessedReceipt, LOCK_RETENTION_PERIOD};
+use crate::error::BridgeError;
+
+pub fn handler(ctx: Context<CloseLock>, lock_id: u64) -> Result<()> {
+    let bridge_transaction = &ctx.accounts.bridge_transaction;
+    let receipt = &mut ctx.accounts.receipt;
+    let clock = Clock::get()?;
+
+    require!(bridge_transaction.id == lock_id, BridgeError::InvalidBridgeTransaction);
+    require!(bridge_transaction.is_finalized(), BridgeError::TransactionNotFinalized);
+    require!(
+        bridge_transaction.is_processed && bridge_transaction.completed_at != 0,
+        BridgeError::TransactionNotFinalized
+    );
+
+    let closable_at = bridge_transaction.completed_at
+        .checked_add(LOCK_RETENTION_PERIOD)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    require!(clock.unix_timestamp >= closable_at, BridgeError::RetentionPeriodActive);
+
+    // Leave a compact receipt behind; the transaction account itself is closed
+    // by Anchor and its rent returned to the user who paid for it
+    receipt.lock_id = lock_id;
+    receipt.user = bridge_transaction.user;
+    receipt.token_mint = bridge_transaction.token_mint;
+    receipt.actual_amount = bridge_transaction.actual_amount;
+    receipt.status = bridge_transaction.status;
+    receipt.completed_at = bridge_transaction.completed_at;
+    receipt.qubic_tx_hash = bridge_transaction.qubic_tx_hash;
+    receipt.domain_hash = ctx.accounts.bridge_state.attestation_domain().hash();
+    receipt.bump = ctx.bumps.receipt;
+
+    msg!("Lock {} closed, rent returned to {}", lock_id, bridge_transaction.user);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(lock_id: u64)]
+pub struct CloseLock<'info> {
+    #[account(
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    #[account(
+        mut,
+        seeds = [b"bridge_tx", lock_id.to_le_bytes().as_ref()],
+        bump = bridge_transaction.bump,
+        close = user
+    )]
+    pub bridge_transaction: Account<'info, BridgeTransaction>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProcessedReceipt::INIT_SPACE,
+        seeds = [b"receipt", lock_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, ProcessedReceipt>,
+    
+    /// CHECK: original payer of the lock account, only receives the reclaimed rent
+    #[account(mut, address = bridge_transaction.user @ BridgeError::InvalidSolanaAddress)]
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
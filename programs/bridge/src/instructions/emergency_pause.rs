@@ -1,25 +1,26 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::error::BridgeError;
 
 pub fn handler(ctx: Context<EmergencyPause>) -> Result<()> {
     let bridge_state = &mut ctx.accounts.bridge_state;
     
-    bridge_state.is_paused = true;
-    bridge_state.emergency_pause_timestamp = Clock::get()?.unix_timestamp;
+    bridge_state.trip(PAUSE_REASON_MANUAL, Clock::get()?.unix_timestamp);
     
     Ok(())
 }
 
+/// The admin or the guardian may pause; only the admin may unpause.
 #[derive(Accounts)]
 pub struct EmergencyPause<'info> {
     #[account(
         mut,
         seeds = [b"bridge_state"],
         bump = bridge_state.bump,
-        has_one = admin
+        constraint = bridge_state.can_pause(&authority.key()) @ BridgeError::UnauthorizedPauser
     )]
     pub bridge_state: Account<'info, BridgeState>,
     
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
 }
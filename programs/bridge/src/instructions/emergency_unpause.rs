@@ -5,6 +5,7 @@ pub fn handler(ctx: Context<EmergencyUnpause>) -> Result<()> {
     let bridge_state = &mut ctx.accounts.bridge_state;
     
     bridge_state.is_paused = false;
+    bridge_state.pause_reason = PAUSE_REASON_NONE;
     bridge_state.emergency_pause_timestamp = 0;
     
     Ok(())
@@ -1,19 +1,29 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::error::BridgeError;
 
+/// `genesis_hash`, `qubic_chain_id` and `bridge_id` identify this deployment. Every
+/// attestation and receipt is bound to them, so they are fixed from here on.
 pub fn handler(
     ctx: Context<InitializeBridge>,
     admin: Pubkey,
     validator_threshold: u8,
+    genesis_hash: [u8; 32],
+    qubic_chain_id: u64,
+    bridge_id: u64,
 ) -> Result<()> {
+    require!(genesis_hash != [0; 32], BridgeError::InvalidDomain);
+    require!(bridge_id > 0, BridgeError::InvalidDomain);
+
     let bridge_state = &mut ctx.accounts.bridge_state;
     
     // Initialize admin and pause state
+    bridge_state.version = BridgeState::VERSION;
     bridge_state.admin = admin;
     bridge_state.is_paused = false;
     
     // Initialize bridge configuration (matching Qubic contract defaults)
-    bridge_state.bridge_id = 1; // Solana-Qubic Bridge ID
+    bridge_state.bridge_id = bridge_id;
     bridge_state.min_lock_amount = 1000000;      // 1M tokens minimum (adjust for token decimals)
     bridge_state.max_lock_amount = 1000000000000; // 1T tokens maximum
     bridge_state.bridge_fee = 100;               // 1% fee (100 basis points)
@@ -21,10 +31,10 @@ pub fn handler(
     
     // Initialize validator management
     bridge_state.validator_threshold = validator_threshold;
-    bridge_state.active_validators = 0;
     
     // Initialize chain configuration
-    bridge_state.qubic_chain_id = 1; // Qubic mainnet
+    bridge_state.genesis_hash = genesis_hash;
+    bridge_state.qubic_chain_id = qubic_chain_id;
     
     // Initialize statistics
     bridge_state.total_locked_tokens = 0;
@@ -36,10 +46,20 @@ pub fn handler(
     bridge_state.last_qubic_block = 0;
     bridge_state.emergency_pause_timestamp = 0;
     bridge_state.bump = ctx.bumps.bridge_state;
+    bridge_state.guardian = Pubkey::default();
+    bridge_state.pause_flags = 0;
+    bridge_state.pause_reason = PAUSE_REASON_NONE;
+    bridge_state.max_unlock_bps = 0;
+    bridge_state.max_unlocks_per_window = 0;
+    bridge_state.unlock_window_slots = 0;
+    bridge_state.window_start_slot = 0;
+    bridge_state.window_unlocks = 0;
     
     msg!(
-        "Bridge initialized: admin={}, threshold={}, min_amount={}, max_amount={}, fee={}",
+        "Bridge initialized: admin={}, bridge_id={}, qubic_chain_id={}, threshold={}, min_amount={}, max_amount={}, fee={}",
         admin,
+        bridge_id,
+        qubic_chain_id,
         validator_threshold,
         bridge_state.min_lock_amount,
         bridge_state.max_lock_amount,
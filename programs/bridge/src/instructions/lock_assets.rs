@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer, Token, TokenAccount};
-use crate::state::{BridgeState, BridgeTransaction, BRIDGE_STATUS_PENDING};
+use crate::state::{BridgeState, BridgeTransaction, MintLedger, BRIDGE_STATUS_PENDING, PAUSE_LOCK};
 use crate::error::BridgeError;
 
 pub fn handler(
@@ -11,11 +11,14 @@ pub fn handler(
 ) -> Result<()> {
     let bridge_state = &mut ctx.accounts.bridge_state;
     let bridge_transaction = &mut ctx.accounts.bridge_transaction;
+    let mint_ledger = &mut ctx.accounts.mint_ledger;
     let clock = Clock::get()?;
 
     // Check if bridge is active and not paused (matching Qubic contract validation)
     require!(bridge_state.is_active, BridgeError::BridgeNotActive);
-    require!(!bridge_state.is_paused, BridgeError::BridgePaused);
+    bridge_state.require_not_paused(PAUSE_LOCK)?;
+    mint_ledger.init_if_new(ctx.accounts.user_token_account.mint, ctx.bumps.mint_ledger);
+    require!(!mint_ledger.is_paused, BridgeError::MintPaused);
     
     // Validate amount against min/max limits (matching Qubic contract)
     require!(
@@ -36,7 +39,7 @@ pub fn handler(
     );
 
     // Calculate fee (matching Qubic contract fee calculation)
-    let (actual_amount, fee) = bridge_state.calculate_fee(amount);
+    let (actual_amount, fee) = bridge_state.calculate_fee(amount)?;
 
     // Transfer tokens from user to bridge
     let transfer_instruction = Transfer {
@@ -54,9 +57,7 @@ pub fn handler(
 
     // Initialize bridge transaction (matching Qubic LockAssets output)
     let lock_id = bridge_state.next_lock_id;
-    bridge_transaction.id = lock_id;
-    // Initialize bridge transaction (matching Qubic LockAssets output)
-    let lock_id = bridge_state.next_lock_id;
+    bridge_transaction.version = BridgeTransaction::VERSION;
     bridge_transaction.id = lock_id;
     bridge_transaction.user = ctx.accounts.user.key();
     bridge_transaction.token_mint = ctx.accounts.user_token_account.mint;
@@ -75,10 +76,25 @@ pub fn handler(
     bridge_transaction.is_processed = false;
     bridge_transaction.bump = ctx.bumps.bridge_transaction;
 
-    // Update bridge state (matching Qubic contract statistics)
-    bridge_state.total_locked_tokens += actual_amount;  // Use actual_amount like Qubic
-    bridge_state.total_bridge_transactions += 1;       // Same as totalTransfers in Qubic
-    bridge_state.next_lock_id += 1;                     // Increment for next lock
+    // Update bridge state (matching Qubic contract statistics): total_locked_tokens
+    // uses actual_amount like Qubic, total_bridge_transactions is totalTransfers
+    bridge_state.total_locked_tokens = bridge_state.total_locked_tokens
+        .checked_add(actual_amount)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    bridge_state.total_bridge_transactions = bridge_state.total_bridge_transactions
+        .checked_add(1)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    bridge_state.next_lock_id = bridge_state.next_lock_id
+        .checked_add(1)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+
+    // Update per-mint accounting used by audit_invariants
+    mint_ledger.total_locked = mint_ledger.total_locked
+        .checked_add(actual_amount)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    mint_ledger.total_fees = mint_ledger.total_fees
+        .checked_add(fee)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
 
     msg!(
         "Assets locked: {} tokens (fee: {}, net: {}) to Qubic address {:?}, Lock ID: {}",
@@ -121,12 +137,23 @@ pub struct LockAssets<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
     
+    // The bridge state's associated token account, so each mint has exactly one vault
     #[account(
         mut,
-        constraint = bridge_token_account.mint == user_token_account.mint
+        associated_token::mint = user_token_account.mint,
+        associated_token::authority = bridge_state
     )]
     pub bridge_token_account: Account<'info, TokenAccount>,
     
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + MintLedger::INIT_SPACE,
+        seeds = [b"mint_ledger", user_token_account.mint.as_ref()],
+        bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::error::BridgeError;
+
+/// Upgrade the bridge state from any earlier layout in place. `genesis_hash` is only read
+/// when the stored layout predates the deployment identity.
+pub fn migrate_bridge_state_handler(ctx: Context<MigrateBridgeState>, genesis_hash: [u8; 32]) -> Result<()> {
+    let account = ctx.accounts.bridge_state.to_account_info();
+    let migrated = {
+        let data = account.try_borrow_data()?;
+        let mut body = account_body(&data, &BridgeState::DISCRIMINATOR)?;
+        match body.len() {
+            BridgeStateV0::LEN => BridgeStateV0::deserialize(&mut body)?.upgrade().upgrade(genesis_hash)?.upgrade(),
+            BridgeStateV1::LEN => BridgeStateV1::deserialize(&mut body)?.upgrade(genesis_hash)?.upgrade(),
+            BridgeStateV2::LEN => BridgeStateV2::deserialize(&mut body)?.upgrade(),
+            BridgeState::INIT_SPACE if body[0] == BridgeState::VERSION => {
+                msg!("Bridge state already at layout {}", BridgeState::VERSION);
+                return Ok(());
+            }
+            _ => return err!(BridgeError::UnknownAccountLayout),
+        }
+    };
+
+    require_keys_eq!(migrated.admin, ctx.accounts.admin.key(), BridgeError::UnauthorizedAdmin);
+
+    rewrite(&account, &ctx.accounts.admin, &ctx.accounts.system_program, &migrated, BridgeState::INIT_SPACE)?;
+
+    msg!("Bridge state migrated to layout {}", BridgeState::VERSION);
+
+    Ok(())
+}
+
+/// Upgrade one lock record from any earlier layout in place. The bridge state has to be
+/// migrated first.
+pub fn migrate_bridge_transaction_handler(ctx: Context<MigrateBridgeTransaction>, lock_id: u64) -> Result<()> {
+    let account = ctx.accounts.bridge_transaction.to_account_info();
+    let migrated = {
+        let data = account.try_borrow_data()?;
+        let mut body = account_body(&data, &BridgeTransaction::DISCRIMINATOR)?;
+        match body.len() {
+            BridgeTransactionV0::LEN => BridgeTransactionV0::deserialize(&mut body)?.upgrade(),
+            BridgeTransaction::INIT_SPACE if body[0] == BridgeTransaction::VERSION => {
+                msg!("Lock {} already at layout {}", lock_id, BridgeTransaction::VERSION);
+                return Ok(());
+            }
+            _ => return err!(BridgeError::UnknownAccountLayout),
+        }
+    };
+
+    rewrite(&account, &ctx.accounts.admin, &ctx.accounts.system_program, &migrated, BridgeTransaction::INIT_SPACE)?;
+
+    msg!("Lock {} migrated to layout {}", lock_id, BridgeTransaction::VERSION);
+
+    Ok(())
+}
+
+/// Upgrade one mint's ledger from any earlier layout in place.
+pub fn migrate_mint_ledger_handler(ctx: Context<MigrateMintLedger>, mint: Pubkey) -> Result<()> {
+    let account = ctx.accounts.mint_ledger.to_account_info();
+    let migrated = {
+        let data = account.try_borrow_data()?;
+        let mut body = account_body(&data, &MintLedger::DISCRIMINATOR)?;
+        match body.len() {
+            MintLedgerV0::LEN => MintLedgerV0::deserialize(&mut body)?.upgrade(),
+            MintLedger::INIT_SPACE if body[0] == MintLedger::VERSION => {
+                msg!("Ledger of mint {} already at layout {}", mint, MintLedger::VERSION);
+                return Ok(());
+            }
+            _ => return err!(BridgeError::UnknownAccountLayout),
+        }
+    };
+
+    rewrite(&account, &ctx.accounts.admin, &ctx.accounts.system_program, &migrated, MintLedger::INIT_SPACE)?;
+
+    msg!("Ledger of mint {} migrated to layout {}", mint, MintLedger::VERSION);
+
+    Ok(())
+}
+
+/// Account data after the discriminator, which must be `discriminator`.
+fn account_body<'a>(data: &'a [u8], discriminator: &[u8; 8]) -> Result<&'a [u8]> {
+    require!(data.len() > 8, BridgeError::UnknownAccountLayout);
+    require!(data[..8] == discriminator[..], ErrorCode::AccountDiscriminatorMismatch);
+    Ok(&data[8..])
+}
+
+/// Resize `account` to hold `space` bytes after the discriminator, topping up rent from
+/// `payer`, and write `value` over it.
+fn rewrite<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    value: &T,
+    space: usize,
+) -> Result<()> {
+    let len = 8 + space;
+    let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    account.realloc(len, true)?;
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateBridgeState<'info> {
+    /// CHECK: may hold any earlier layout, so it is decoded by hand in the handler
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub bridge_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(lock_id: u64)]
+pub struct MigrateBridgeTransaction<'info> {
+    #[account(
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        has_one = admin
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+
+    /// CHECK: may hold any earlier layout, so it is decoded by hand in the handler
+    #[account(
+        mut,
+        seeds = [b"bridge_tx", lock_id.to_le_bytes().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub bridge_transaction: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct MigrateMintLedger<'info> {
+    #[account(
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        has_one = admin
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+
+    /// CHECK: may hold any earlier layout, so it is decoded by hand in the handler
+    #[account(
+        mut,
+        seeds = [b"mint_ledger", mint.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub mint_ledger: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,26 +1,35 @@
 // src/solana/programs/bridge/src/instructions/mod.rs
-use anchor_lang::prelude::*;
+// Every instruction module exposes its own `handler`; lib.rs always calls them by path.
+#![allow(ambiguous_glob_reexports)]
 
 pub mod initialize_bridge;
-pub mod add_validator;
-pub mod remove_validator;
 pub mod update_bridge_state;
 pub mod emergency_pause;
 pub mod emergency_unpause;
+pub mod pause_controls;
+pub mod circuit_breaker;
 pub mod lock_assets;
 pub mod unlock_assets;
 pub mod validate_bridge;
 pub mod update_config;
 pub mod query;
+pub mod audit_invariants;
+pub mod refund_lock;
+pub mod close_lock;
+pub mod migrate;
 
 pub use initialize_bridge::*;
-pub use add_validator::*;
-pub use remove_validator::*;
 pub use update_bridge_state::*;
 pub use emergency_pause::*;
 pub use emergency_unpause::*;
+pub use pause_controls::*;
+pub use circuit_breaker::*;
 pub use lock_assets::*;
 pub use unlock_assets::*;
 pub use validate_bridge::*;
 pub use update_config::*;
-pub use query::*;
\ No newline at end of file
+pub use query::*;
+pub use audit_invariants::*;
+pub use refund_lock::*;
+pub use close_lock::*;
+pub use migrate::*;
\ No newline at end of file
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::BridgeError;
+
+/// Pause the operations named by `flags` (`PAUSE_*` bits), on top of those already paused.
+pub fn pause_operations_handler(ctx: Context<PauseOperations>, flags: u8) -> Result<()> {
+    require!(flags != 0 && flags & !PAUSE_FLAGS_ALL == 0, BridgeError::InvalidPauseFlags);
+
+    let bridge_state = &mut ctx.accounts.bridge_state;
+    bridge_state.pause_flags |= flags;
+
+    msg!("Paused {:#06b} by {}, now {:#06b}", flags, ctx.accounts.authority.key(), bridge_state.pause_flags);
+
+    Ok(())
+}
+
+/// Resume the operations named by `flags`; the others stay paused.
+pub fn unpause_operations_handler(ctx: Context<UnpauseOperations>, flags: u8) -> Result<()> {
+    require!(flags != 0 && flags & !PAUSE_FLAGS_ALL == 0, BridgeError::InvalidPauseFlags);
+
+    let bridge_state = &mut ctx.accounts.bridge_state;
+    bridge_state.pause_flags &= !flags;
+
+    msg!("Unpaused {:#06b}, now {:#06b}", flags, bridge_state.pause_flags);
+
+    Ok(())
+}
+
+/// Pause or resume locks and unlocks of one mint. The guardian may only pause.
+pub fn set_mint_paused_handler(ctx: Context<SetMintPaused>, mint: Pubkey, is_paused: bool) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    if is_paused {
+        require!(ctx.accounts.bridge_state.can_pause(&authority), BridgeError::UnauthorizedPauser);
+    } else {
+        require_keys_eq!(authority, ctx.accounts.bridge_state.admin, BridgeError::UnauthorizedAdmin);
+    }
+
+    let mint_ledger = &mut ctx.accounts.mint_ledger;
+    mint_ledger.init_if_new(mint, ctx.bumps.mint_ledger);
+    mint_ledger.is_paused = is_paused;
+
+    msg!("Mint {} {}", mint, if is_paused { "paused" } else { "resumed" });
+
+    Ok(())
+}
+
+/// Appoint the guardian; `Pubkey::default()` removes it.
+pub fn set_guardian_handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    ctx.accounts.bridge_state.guardian = guardian;
+
+    msg!("Guardian set to {}", guardian);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PauseOperations<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        constraint = bridge_state.can_pause(&authority.key()) @ BridgeError::UnauthorizedPauser
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnpauseOperations<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        has_one = admin @ BridgeError::UnauthorizedAdmin
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct SetMintPaused<'info> {
+    #[account(
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintLedger::INIT_SPACE,
+        seeds = [b"mint_ledger", mint.as_ref()],
+        bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        has_one = admin @ BridgeError::UnauthorizedAdmin
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    pub admin: Signer<'info>,
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::state::{BridgeState, BridgeTransaction};
+use validator::state::ValidatorConfig;
+use crate::state::{BridgeState, BridgeTransaction, ProcessedReceipt};
 
 pub fn handler(ctx: Context<GetBridgeInfo>) -> Result<BridgeInfoResponse> {
     let bridge_state = &ctx.accounts.bridge_state;
+    let validator_config = &ctx.accounts.validator_config;
 
     Ok(BridgeInfoResponse {
         bridge_id: bridge_state.bridge_id,
@@ -10,7 +12,7 @@ pub fn handler(ctx: Context<GetBridgeInfo>) -> Result<BridgeInfoResponse> {
         max_lock_amount: bridge_state.max_lock_amount,
         bridge_fee: bridge_state.bridge_fee,
         is_active: bridge_state.is_active,
-        validator_count: bridge_state.active_validators as u64,
+        validator_count: validator_config.active_validators as u64,
         required_signatures: bridge_state.validator_threshold as u64,
         total_locked: bridge_state.total_locked_tokens,
         total_unlocked: bridge_state.total_unlocked_tokens,
@@ -35,6 +37,21 @@ pub fn get_lock_info_handler(ctx: Context<GetLockInfo>) -> Result<LockInfoRespon
     })
 }
 
+pub fn get_receipt_handler(ctx: Context<GetReceipt>) -> Result<ReceiptResponse> {
+    let receipt = &ctx.accounts.receipt;
+
+    Ok(ReceiptResponse {
+        lock_id: receipt.lock_id,
+        locker: receipt.user,
+        token_mint: receipt.token_mint,
+        actual_amount: receipt.actual_amount,
+        status: receipt.status,
+        completed_at: receipt.completed_at,
+        qubic_tx_hash: receipt.qubic_tx_hash,
+        domain_hash: receipt.domain_hash,
+    })
+}
+
 #[derive(Accounts)]
 pub struct GetBridgeInfo<'info> {
     #[account(
@@ -42,6 +59,13 @@ pub struct GetBridgeInfo<'info> {
         bump = bridge_state.bump
     )]
     pub bridge_state: Account<'info, BridgeState>,
+    
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        seeds::program = validator::ID
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
 }
 
 #[derive(Accounts)]
@@ -54,6 +78,16 @@ pub struct GetLockInfo<'info> {
     pub bridge_transaction: Account<'info, BridgeTransaction>,
 }
 
+#[derive(Accounts)]
+#[instruction(lock_id: u64)]
+pub struct GetReceipt<'info> {
+    #[account(
+        seeds = [b"receipt", lock_id.to_le_bytes().as_ref()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, ProcessedReceipt>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct BridgeInfoResponse {
     pub bridge_id: u64,
@@ -81,3 +115,15 @@ pub struct LockInfoResponse {
     pub is_processed: bool,
     pub status: u8,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReceiptResponse {
+    pub lock_id: u64,
+    pub locker: Pubkey,
+    pub token_mint: Pubkey,
+    pub actual_amount: u64,
+    pub status: u8,
+    pub completed_at: i64,
+    pub qubic_tx_hash: [u8; 32],
+    pub domain_hash: [u8; 32],
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+use crate::state::{BridgeState, BridgeTransaction, MintLedger, BRIDGE_STATUS_REFUNDED, LOCK_REFUND_TIMEOUT};
+use crate::error::BridgeError;
+
+pub fn handler(ctx: Context<RefundLock>, lock_id: u64) -> Result<()> {
+    // Store account info and bump before mutable borrow
+    let bridge_account_info = ctx.accounts.bridge_state.to_account_info();
+    let bridge_bump = ctx.accounts.bridge_state.bump;
+
+    let bridge_state = &mut ctx.accounts.bridge_state;
+    let bridge_transaction = &mut ctx.accounts.bridge_transaction;
+    let mint_ledger = &mut ctx.accounts.mint_ledger;
+    let clock = Clock::get()?;
+
+    // Maintenance mode and the granular pauses leave refunds open; only the emergency pause stops them
+    require!(!bridge_state.is_paused, BridgeError::BridgePaused);
+
+    // Only locks that were never relayed can be refunded
+    require!(!bridge_transaction.is_processed, BridgeError::TransactionAlreadyCompleted);
+    require!(bridge_transaction.id == lock_id, BridgeError::InvalidBridgeTransaction);
+    require!(!bridge_transaction.is_delivered(), BridgeError::LockAlreadyDelivered);
+
+    // Give the relayer time to deliver before the tokens can come back
+    let refundable_at = bridge_transaction.created_at
+        .checked_add(LOCK_REFUND_TIMEOUT)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    require!(clock.unix_timestamp >= refundable_at, BridgeError::RefundTimeoutActive);
+
+    // Inbound unlocks draw on the same vault; never release more than the ledger still owes
+    let new_total_unlocked = mint_ledger.total_unlocked
+        .checked_add(bridge_transaction.actual_amount)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    require!(
+        new_total_unlocked <= mint_ledger.total_locked,
+        BridgeError::InsufficientBalance
+    );
+
+    // The full amount, fee included, goes back to the user
+    let refund_amount = bridge_transaction.amount;
+
+    let bridge_seeds = &[b"bridge_state".as_ref(), &[bridge_bump]];
+    let bridge_signer = &[&bridge_seeds[..]];
+
+    let transfer_instruction = Transfer {
+        from: ctx.accounts.bridge_token_account.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: bridge_account_info,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_instruction,
+        bridge_signer,
+    );
+
+    token::transfer(cpi_ctx, refund_amount)?;
+
+    bridge_transaction.status = BRIDGE_STATUS_REFUNDED;
+    bridge_transaction.updated_at = clock.unix_timestamp;
+    bridge_transaction.completed_at = clock.unix_timestamp;
+    bridge_transaction.is_processed = true;
+
+    // The net amount leaves the vault like an unlock, the fee is returned as well
+    bridge_state.total_unlocked_tokens = bridge_state.total_unlocked_tokens
+        .checked_add(bridge_transaction.actual_amount)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    mint_ledger.total_unlocked = new_total_unlocked;
+    mint_ledger.total_fees = mint_ledger.total_fees
+        .checked_sub(bridge_transaction.fee)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+
+    msg!(
+        "Lock {} refunded: {} tokens returned to {}",
+        lock_id,
+        refund_amount,
+        bridge_transaction.user
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(lock_id: u64)]
+pub struct RefundLock<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_state"],
+        bump = bridge_state.bump,
+        has_one = admin
+    )]
+    pub bridge_state: Account<'info, BridgeState>,
+    
+    #[account(
+        mut,
+        seeds = [b"bridge_tx", lock_id.to_le_bytes().as_ref()],
+        bump = bridge_transaction.bump
+    )]
+    pub bridge_transaction: Account<'info, BridgeTransaction>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == bridge_transaction.user @ BridgeError::InvalidTokenAccount,
+        constraint = user_token_account.mint == bridge_transaction.token_mint @ BridgeError::InvalidTokenAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = bridge_transaction.token_mint,
+        associated_token::authority = bridge_state
+    )]
+    pub bridge_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint_ledger", bridge_transaction.token_mint.as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+    
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
@@ -1,45 +1,81 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer, Token, TokenAccount};
-use crate::state::{BridgeState, BridgeTransaction, ValidatorInfo, BRIDGE_STATUS_COMPLETED};
+use validator::program::Validator;
+use validator::state::{Attestation, ValidationState, ValidationVote, ValidatorConfig, ValidatorInfo};
+use crate::state::{pause_reason_name, BridgeState, InboundUnlock, MintLedger, BRIDGE_DIRECTION_QUBIC_TO_SOLANA, PAUSE_UNLOCK};
 use crate::error::BridgeError;
 
-pub fn handler(
-    ctx: Context<UnlockAssets>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UnlockAssets<'info>>,
     lock_id: u64,
+    mint: Pubkey,
     recipient: Pubkey,
     amount: u64,
-    qubic_signatures: Vec<[u8; 64]>,
 ) -> Result<()> {
     // Store account info and bump before mutable borrow
     let bridge_account_info = ctx.accounts.bridge_state.to_account_info();
     let bridge_bump = ctx.accounts.bridge_state.bump;
     
     let bridge_state = &mut ctx.accounts.bridge_state;
-    let bridge_transaction = &mut ctx.accounts.bridge_transaction;
+    let inbound_unlock = &mut ctx.accounts.inbound_unlock;
     let validator_info = &ctx.accounts.validator_info;
+    let mint_ledger = &mut ctx.accounts.mint_ledger;
 
     // Check if bridge is active and not paused (matching Qubic contract validation)
     require!(bridge_state.is_active, BridgeError::BridgeNotActive);
-    require!(!bridge_state.is_paused, BridgeError::BridgePaused);
+    bridge_state.require_not_paused(PAUSE_UNLOCK)?;
+    require!(!mint_ledger.is_paused, BridgeError::MintPaused);
     
     // Validate validator
     require!(validator_info.is_active, BridgeError::UnauthorizedValidator);
     
-    // Validate lock exists and hasn't been processed (matching Qubic contract)
-    require!(!bridge_transaction.is_processed, BridgeError::TransactionAlreadyCompleted);
-    require!(bridge_transaction.id == lock_id, BridgeError::InvalidBridgeTransaction);
+    // Each Qubic lock is released at most once. The guard is created on first use rather than
+    // with `init` so an unlock held back by the circuit breaker can be retried after unpausing
+    require!(!inbound_unlock.is_processed, BridgeError::TransactionAlreadyCompleted);
     
     // Validate amount
     require!(amount > 0, BridgeError::InvalidAmount);
     
-    // Validate signature count (matching Qubic contract requiredSignatures check)
+    // Never release more than the vault owes for this mint
+    let new_total_unlocked = mint_ledger.total_unlocked
+        .checked_add(amount)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
     require!(
-        qubic_signatures.len() >= bridge_state.validator_threshold as usize,
-        BridgeError::InsufficientValidatorSignatures
+        new_total_unlocked <= mint_ledger.total_locked,
+        BridgeError::InsufficientBalance
+    );
+    
+    // The validator program must have approved exactly this (lock_id, mint, recipient, amount).
+    // These recounted votes are the only signatures the program checks (they stand in for the
+    // Qubic contract's requiredSignatures). `is_approved` never goes back, so recount: only votes
+    // by validators that are still active and staked count towards the threshold
+    let approvals = active_approvals(
+        ctx.remaining_accounts,
+        &ctx.accounts.validation_state.transaction_hash,
+        &ctx.accounts.validator_config,
+    )?;
+    require!(
+        approvals >= ctx.accounts.validation_state.threshold as usize,
+        BridgeError::ValidatorThresholdNotMet
     );
 
-    // TODO: In production, validate actual signatures against registered validators
-    // For now, we trust the validator calling this function has verified the signatures
+    // Circuit breaker: an anomalous unlock pauses the bridge instead of moving funds. Do not
+    // fail the instruction: the pause has to be persisted
+    let clock = Clock::get()?;
+    let vault_balance = ctx.accounts.bridge_token_account.amount;
+    let liabilities = mint_ledger.expected_vault_balance()?;
+    if let Some(reason) = bridge_state.unlock_tripwire(amount, vault_balance, liabilities, clock.slot) {
+        bridge_state.trip(reason, clock.unix_timestamp);
+        msg!(
+            "Circuit breaker tripped ({}) by unlock of {} for Lock ID {}: vault={}, liabilities={}. Bridge paused",
+            pause_reason_name(reason),
+            amount,
+            lock_id,
+            vault_balance,
+            liabilities
+        );
+        return Ok(());
+    }
 
     // Transfer tokens from bridge to recipient
     let bridge_seeds = &[b"bridge_state".as_ref(), &[bridge_bump]];
@@ -48,7 +84,7 @@ pub fn handler(
     let transfer_instruction = Transfer {
         from: ctx.accounts.bridge_token_account.to_account_info(),
         to: ctx.accounts.recipient_token_account.to_account_info(),
-        authority: bridge_account_info,
+        authority: bridge_account_info.clone(),
     };
 
     let cpi_ctx = CpiContext::new_with_signer(
@@ -59,16 +95,35 @@ pub fn handler(
 
     token::transfer(cpi_ctx, amount)?;
 
-    // Update bridge transaction (matching Qubic contract behavior)
-    bridge_transaction.status = BRIDGE_STATUS_COMPLETED;
-    bridge_transaction.updated_at = Clock::get()?.unix_timestamp;
-    bridge_transaction.completed_at = Clock::get()?.unix_timestamp;
-    bridge_transaction.validator_signatures = qubic_signatures;
-    bridge_transaction.is_processed = true;
+    // Record the release so the same Qubic lock can never be unlocked again
+    inbound_unlock.qubic_lock_id = lock_id;
+    inbound_unlock.mint = mint;
+    inbound_unlock.recipient = recipient;
+    inbound_unlock.amount = amount;
+    inbound_unlock.unlocked_at = clock.unix_timestamp;
+    inbound_unlock.validator_signatures = Vec::new();
+    inbound_unlock.is_processed = true;
+    inbound_unlock.bump = ctx.bumps.inbound_unlock;
 
     // Update bridge state statistics (matching Qubic contract)
-    bridge_state.total_unlocked_tokens += amount;
-    bridge_state.total_validator_actions += 1;
+    bridge_state.total_unlocked_tokens = bridge_state.total_unlocked_tokens
+        .checked_add(amount)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    bridge_state.total_validator_actions = bridge_state.total_validator_actions
+        .checked_add(1)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+    mint_ledger.total_unlocked = new_total_unlocked;
+
+    // Credit the submitting validator in the registry's performance counters
+    validator::cpi::record_activity(CpiContext::new_with_signer(
+        ctx.accounts.validator_program.to_account_info(),
+        validator::cpi::accounts::RecordActivity {
+            bridge_authority: bridge_account_info,
+            validator_config: ctx.accounts.validator_config.to_account_info(),
+            validator_info: ctx.accounts.validator_info.to_account_info(),
+        },
+        bridge_signer,
+    ))?;
 
     msg!(
         "Assets unlocked: {} tokens from Lock ID: {} to recipient: {}",
@@ -80,8 +135,51 @@ pub fn handler(
     Ok(())
 }
 
+/// Distinct approvals of `digest` by validators that are still active and meet the minimum
+/// stake. Remaining accounts come in (ValidationVote, ValidatorInfo) pairs; a vote account that
+/// was never created counts for nothing.
+fn active_approvals<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    digest: &[u8; 32],
+    validator_config: &ValidatorConfig,
+) -> Result<usize> {
+    let pairs = remaining_accounts.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), BridgeError::InvalidValidatorVote);
+
+    let mut approvers: Vec<Pubkey> = Vec::with_capacity(remaining_accounts.len() / 2);
+    let mut approvals = 0;
+    for pair in pairs {
+        if pair[0].data_is_empty() {
+            continue;
+        }
+        let vote = Account::<ValidationVote>::try_from(&pair[0])?;
+        let validator_info = Account::<ValidatorInfo>::try_from(&pair[1])?;
+
+        let vote_address = Pubkey::create_program_address(
+            &[b"vote", digest.as_ref(), vote.validator.as_ref(), &[vote.bump]],
+            &validator::ID,
+        )
+        .map_err(|_| error!(BridgeError::InvalidValidatorVote))?;
+        let info_address = Pubkey::create_program_address(
+            &[b"validator", vote.validator.as_ref(), &[validator_info.bump]],
+            &validator::ID,
+        )
+        .map_err(|_| error!(BridgeError::InvalidValidatorVote))?;
+        require_keys_eq!(vote.key(), vote_address, BridgeError::InvalidValidatorVote);
+        require_keys_eq!(validator_info.key(), info_address, BridgeError::InvalidValidatorVote);
+        require!(!approvers.contains(&vote.validator), BridgeError::InvalidValidatorVote);
+        approvers.push(vote.validator);
+
+        if vote.is_valid && validator_info.is_active && validator_info.stake >= validator_config.min_stake {
+            approvals += 1;
+        }
+    }
+
+    Ok(approvals)
+}
+
 #[derive(Accounts)]
-#[instruction(lock_id: u64, recipient: Pubkey)]
+#[instruction(lock_id: u64, mint: Pubkey, recipient: Pubkey, amount: u64)]
 pub struct UnlockAssets<'info> {
     #[account(
         mut,
@@ -91,33 +189,74 @@ pub struct UnlockAssets<'info> {
     pub bridge_state: Account<'info, BridgeState>,
     
     #[account(
-        mut,
-        seeds = [b"bridge_tx", lock_id.to_le_bytes().as_ref()],
-        bump = bridge_transaction.bump
+        init_if_needed,
+        payer = validator,
+        space = 8 + InboundUnlock::INIT_SPACE,
+        seeds = [b"inbound", lock_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub bridge_transaction: Account<'info, BridgeTransaction>,
+    pub inbound_unlock: Account<'info, InboundUnlock>,
     
     #[account(
+        mut,
         seeds = [b"validator", validator.key().as_ref()],
-        bump = validator_info.bump
+        bump = validator_info.bump,
+        seeds::program = validator::ID
     )]
     pub validator_info: Account<'info, ValidatorInfo>,
     
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        seeds::program = validator::ID
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    
+    #[account(
+        seeds = [
+            b"validation",
+            Attestation {
+                domain: bridge_state.attestation_domain(),
+                direction: BRIDGE_DIRECTION_QUBIC_TO_SOLANA,
+                lock_id,
+                mint,
+                recipient,
+                amount,
+            }
+            .digest()
+            .as_ref()
+        ],
+        bump = validation_state.bump,
+        seeds::program = validator::ID,
+        constraint = validation_state.is_approved @ BridgeError::ValidatorThresholdNotMet
+    )]
+    pub validation_state: Account<'info, ValidationState>,
+    
     #[account(mut)]
     pub validator: Signer<'info>,
     
     #[account(
         mut,
-        constraint = recipient_token_account.owner == recipient
+        constraint = recipient_token_account.owner == recipient,
+        constraint = recipient_token_account.mint == mint @ BridgeError::InvalidTokenAccount
     )]
     pub recipient_token_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = bridge_token_account.mint == recipient_token_account.mint
+        associated_token::mint = mint,
+        associated_token::authority = bridge_state
     )]
     pub bridge_token_account: Account<'info, TokenAccount>,
     
+    #[account(
+        mut,
+        seeds = [b"mint_ledger", mint.as_ref()],
+        bump = mint_ledger.bump
+    )]
+    pub mint_ledger: Account<'info, MintLedger>,
+    
     pub token_program: Program<'info, Token>,
+    pub validator_program: Program<'info, Validator>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
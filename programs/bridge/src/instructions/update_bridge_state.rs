@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
+use validator::state::ValidatorInfo;
 use crate::state::*;
+use crate::error::BridgeError;
 
 pub fn handler(
     ctx: Context<UpdateBridgeState>,
@@ -10,12 +12,23 @@ pub fn handler(
     let bridge_transaction = &mut ctx.accounts.bridge_transaction;
     let validator_info = &ctx.accounts.validator_info;
     
+    ctx.accounts.bridge_state.require_not_paused(PAUSE_VALIDATOR_OPS)?;
+    
     // Only allow active validators to update bridge state
-    require!(validator_info.is_active, crate::error::BridgeError::UnauthorizedValidator);
+    require!(validator_info.is_active, BridgeError::UnauthorizedValidator);
+    require!(bridge_transaction.can_transition_to(status), BridgeError::InvalidStatusTransition);
     
+    let now = Clock::get()?.unix_timestamp;
     bridge_transaction.status = status;
-    bridge_transaction.confirmations = confirmations;
-    bridge_transaction.updated_at = Clock::get()?.unix_timestamp;
+    // Confirmations only ever grow; a lower count is ignored rather than rolled back to
+    bridge_transaction.confirmations = bridge_transaction.confirmations.max(confirmations);
+    bridge_transaction.updated_at = now;
+    
+    // A completed lock was delivered and can never be refunded; it becomes closable
+    if status == BRIDGE_STATUS_COMPLETED {
+        bridge_transaction.completed_at = now;
+        bridge_transaction.is_processed = true;
+    }
     
     Ok(())
 }
@@ -39,7 +52,8 @@ pub struct UpdateBridgeState<'info> {
     
     #[account(
         seeds = [b"validator", validator.key().as_ref()],
-        bump = validator_info.bump
+        bump = validator_info.bump,
+        seeds::program = validator::ID
     )]
     pub validator_info: Account<'info, ValidatorInfo>,
     
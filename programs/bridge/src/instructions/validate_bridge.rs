@@ -1,6 +1,7 @@
-// filepath: /solana-qubic-bridge/solana-qubic-bridge/src/solana/programs/bridge/src/instructions/validate_bridge.rs
 use anchor_lang::prelude::*;
-use crate::state::{BridgeState, BridgeTransaction, ValidatorInfo};
+use validator::program::Validator;
+use validator::state::{Attestation, ValidationState, ValidatorConfig, ValidatorInfo};
+use crate::state::{BridgeState, BridgeTransaction, BRIDGE_DIRECTION_SOLANA_TO_QUBIC, PAUSE_VALIDATOR_OPS};
 use crate::error::BridgeError;
 
 pub fn handler(
@@ -8,12 +9,16 @@ pub fn handler(
     bridge_id: u64,
     qubic_tx_hash: [u8; 32],
 ) -> Result<()> {
+    // Store account info and bump before mutable borrow
+    let bridge_account_info = ctx.accounts.bridge_state.to_account_info();
+    let bridge_bump = ctx.accounts.bridge_state.bump;
+
     let bridge_state = &mut ctx.accounts.bridge_state;
     let bridge_transaction = &mut ctx.accounts.bridge_transaction;
     let validator_info = &ctx.accounts.validator_info;
 
     // Check if bridge is paused
-    require!(!bridge_state.is_paused, BridgeError::BridgePaused);
+    bridge_state.require_not_paused(PAUSE_VALIDATOR_OPS)?;
     
     // Validate validator
     require!(validator_info.is_active, BridgeError::UnauthorizedValidator);
@@ -24,22 +29,39 @@ pub fn handler(
         BridgeError::InvalidBridgeTransaction
     );
 
+    // A recorded delivery blocks the refund for good, so it is written once, and only for a
+    // lock the validator quorum approved releasing on Qubic (checked on `validation_state`)
+    require!(!bridge_transaction.is_delivered(), BridgeError::LockAlreadyDelivered);
+    require!(qubic_tx_hash != [0; 32], BridgeError::InvalidBridgeTransaction);
+
     // Update the bridge transaction with Qubic tx hash
     bridge_transaction.qubic_tx_hash = qubic_tx_hash;
-    bridge_transaction.confirmations += 1;
+    bridge_transaction.confirmations = bridge_transaction.confirmations
+        .checked_add(1)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
     bridge_transaction.updated_at = Clock::get()?.unix_timestamp;
 
-    // Update validator's last activity
-    let validator_info = &mut ctx.accounts.validator_info;
-    validator_info.last_activity = Clock::get()?.unix_timestamp;
-
     // Update bridge state
-    bridge_state.last_qubic_block += 1;
+    bridge_state.last_qubic_block = bridge_state.last_qubic_block
+        .checked_add(1)
+        .ok_or(BridgeError::ArithmeticOverflow)?;
+
+    // Credit the confirming validator in the registry's performance counters
+    let bridge_seeds = &[b"bridge_state".as_ref(), &[bridge_bump]];
+    validator::cpi::record_activity(CpiContext::new_with_signer(
+        ctx.accounts.validator_program.to_account_info(),
+        validator::cpi::accounts::RecordActivity {
+            bridge_authority: bridge_account_info,
+            validator_config: ctx.accounts.validator_config.to_account_info(),
+            validator_info: ctx.accounts.validator_info.to_account_info(),
+        },
+        &[&bridge_seeds[..]],
+    ))?;
 
     msg!(
         "Bridge transaction {} validated by validator {}",
         bridge_id,
-        validator_info.pubkey
+        validator_info.validator_pubkey
     );
 
     Ok(())
@@ -62,12 +84,41 @@ pub struct ValidateBridge<'info> {
     )]
     pub bridge_transaction: Account<'info, BridgeTransaction>,
     
+    #[account(
+        seeds = [
+            b"validation",
+            Attestation {
+                domain: bridge_state.attestation_domain(),
+                direction: BRIDGE_DIRECTION_SOLANA_TO_QUBIC,
+                lock_id: bridge_id,
+                mint: bridge_transaction.token_mint,
+                recipient: Pubkey::new_from_array(bridge_transaction.qubic_destination),
+                amount: bridge_transaction.actual_amount,
+            }
+            .digest()
+            .as_ref()
+        ],
+        bump = validation_state.bump,
+        seeds::program = validator::ID,
+        constraint = validation_state.is_approved @ BridgeError::ValidatorThresholdNotMet
+    )]
+    pub validation_state: Account<'info, ValidationState>,
+    
     #[account(
         mut,
         seeds = [b"validator", validator.key().as_ref()],
-        bump = validator_info.bump
+        bump = validator_info.bump,
+        seeds::program = validator::ID
     )]
     pub validator_info: Account<'info, ValidatorInfo>,
     
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        seeds::program = validator::ID
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    
     pub validator: Signer<'info>,
+    pub validator_program: Program<'info, Validator>,
 }
\ No newline at end of file
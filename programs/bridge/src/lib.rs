@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
 
-mod instructions;
-mod state;
-mod error;
+pub mod instructions;
+pub mod state;
+pub mod error;
+#[cfg(feature = "client")]
+pub mod client;
 
 use crate::instructions::*;
+use validator::state::Attestation;
 
-declare_id!("4LXpG5445Kmv879vWzCFx5bTc7miCJAY96iQDMJB1zaP");
+declare_id!("CyJrx48JtLX9QdgmmMxARVpRuY4ixuML33SMzGHtV9Zu");
 
 // QuantumLink - Solana-Qubic Cross-Chain Bridge
 // Connecting the power of Solana with Qubic's quantum computing capabilities
@@ -16,13 +19,15 @@ pub mod quantum_link {
     use super::*;
 
     /// Initialize the QuantumLink bridge with admin and validator settings
-
     pub fn initialize_bridge(
         ctx: Context<InitializeBridge>,
         admin: Pubkey,
         validator_threshold: u8,
+        genesis_hash: [u8; 32],
+        qubic_chain_id: u64,
+        bridge_id: u64,
     ) -> Result<()> {
-        instructions::initialize_bridge::handler(ctx, admin, validator_threshold)
+        instructions::initialize_bridge::handler(ctx, admin, validator_threshold, genesis_hash, qubic_chain_id, bridge_id)
     }
 
     pub fn lock_assets(
@@ -34,29 +39,14 @@ pub mod quantum_link {
         instructions::lock_assets::handler(ctx, amount, qubic_destination, memo)
     }
 
-    pub fn unlock_assets(
-        ctx: Context<UnlockAssets>,
+    pub fn unlock_assets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnlockAssets<'info>>,
         lock_id: u64,
+        mint: Pubkey,
         recipient: Pubkey,
         amount: u64,
-        qubic_signatures: Vec<[u8; 64]>,
-    ) -> Result<()> {
-        instructions::unlock_assets::handler(ctx, lock_id, recipient, amount, qubic_signatures)
-    }
-
-    pub fn add_validator(
-        ctx: Context<AddValidator>,
-        validator_pubkey: Pubkey,
-        qubic_validator_id: [u8; 32],
     ) -> Result<()> {
-        instructions::add_validator::handler(ctx, validator_pubkey, qubic_validator_id)
-    }
-
-    pub fn remove_validator(
-        ctx: Context<RemoveValidator>,
-        validator_pubkey: Pubkey,
-    ) -> Result<()> {
-        instructions::remove_validator::handler(ctx, validator_pubkey)
+        instructions::unlock_assets::handler(ctx, lock_id, mint, recipient, amount)
     }
 
     pub fn update_bridge_state(
@@ -68,6 +58,14 @@ pub mod quantum_link {
         instructions::update_bridge_state::handler(ctx, bridge_id, status, confirmations)
     }
 
+    pub fn validate_bridge(
+        ctx: Context<ValidateBridge>,
+        bridge_id: u64,
+        qubic_tx_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::validate_bridge::handler(ctx, bridge_id, qubic_tx_hash)
+    }
+
     pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
         instructions::emergency_pause::handler(ctx)
     }
@@ -76,6 +74,44 @@ pub mod quantum_link {
         instructions::emergency_unpause::handler(ctx)
     }
 
+    /// Pause lock, unlock or validator operations, or enter maintenance mode (admin or guardian)
+    pub fn pause_operations(ctx: Context<PauseOperations>, flags: u8) -> Result<()> {
+        instructions::pause_controls::pause_operations_handler(ctx, flags)
+    }
+
+    pub fn unpause_operations(ctx: Context<UnpauseOperations>, flags: u8) -> Result<()> {
+        instructions::pause_controls::unpause_operations_handler(ctx, flags)
+    }
+
+    /// Pause or resume bridging of one mint (the guardian may only pause)
+    pub fn set_mint_paused(ctx: Context<SetMintPaused>, mint: Pubkey, is_paused: bool) -> Result<()> {
+        instructions::pause_controls::set_mint_paused_handler(ctx, mint, is_paused)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::pause_controls::set_guardian_handler(ctx, guardian)
+    }
+
+    /// Set the unlock size and rate limits that trip the circuit breaker (zero turns one off)
+    pub fn configure_circuit_breaker(
+        ctx: Context<ConfigureCircuitBreaker>,
+        max_unlock_bps: u16,
+        max_unlocks_per_window: u16,
+        unlock_window_slots: u64,
+    ) -> Result<()> {
+        instructions::circuit_breaker::configure_circuit_breaker_handler(
+            ctx,
+            max_unlock_bps,
+            max_unlocks_per_window,
+            unlock_window_slots,
+        )
+    }
+
+    /// Trip the circuit breaker with proof that validators voted for a lock that was never made
+    pub fn report_phantom_lock(ctx: Context<ReportPhantomLock>, attestation: Attestation) -> Result<()> {
+        instructions::circuit_breaker::report_phantom_lock_handler(ctx, attestation)
+    }
+
     pub fn update_config(
         ctx: Context<UpdateConfig>,
         new_min_lock_amount: u64,
@@ -101,4 +137,38 @@ pub mod quantum_link {
     pub fn get_lock_info(ctx: Context<GetLockInfo>, _lock_id: u64) -> Result<LockInfoResponse> {
         instructions::query::get_lock_info_handler(ctx)
     }
+
+    pub fn get_receipt(ctx: Context<GetReceipt>, _lock_id: u64) -> Result<ReceiptResponse> {
+        instructions::query::get_receipt_handler(ctx)
+    }
+
+    /// Return a never-relayed lock to its owner
+    pub fn refund_lock(ctx: Context<RefundLock>, lock_id: u64) -> Result<()> {
+        instructions::refund_lock::handler(ctx, lock_id)
+    }
+
+    /// Close a finalized lock after the retention period, leaving a ProcessedReceipt
+    pub fn close_lock(ctx: Context<CloseLock>, lock_id: u64) -> Result<()> {
+        instructions::close_lock::handler(ctx, lock_id)
+    }
+
+    /// Compare a mint's vault balance with its recorded liabilities, pausing on mismatch
+    pub fn audit_invariants(ctx: Context<AuditInvariants>) -> Result<()> {
+        instructions::audit_invariants::handler(ctx)
+    }
+
+    /// Upgrade the bridge state from an earlier account layout in place
+    pub fn migrate_bridge_state(ctx: Context<MigrateBridgeState>, genesis_hash: [u8; 32]) -> Result<()> {
+        instructions::migrate::migrate_bridge_state_handler(ctx, genesis_hash)
+    }
+
+    /// Upgrade a lock record from an earlier account layout in place
+    pub fn migrate_bridge_transaction(ctx: Context<MigrateBridgeTransaction>, lock_id: u64) -> Result<()> {
+        instructions::migrate::migrate_bridge_transaction_handler(ctx, lock_id)
+    }
+
+    /// Upgrade a mint ledger from an earlier account layout in place
+    pub fn migrate_mint_ledger(ctx: Context<MigrateMintLedger>, mint: Pubkey) -> Result<()> {
+        instructions::migrate::migrate_mint_ledger_handler(ctx, mint)
+    }
 }
\ No newline at end of file
@@ -1,12 +1,46 @@
 use anchor_lang::prelude::*;
+use validator::state::AttestationDomain;
+use crate::error::BridgeError;
 
 pub const BRIDGE_STATUS_PENDING: u8 = 0;
 pub const BRIDGE_STATUS_CONFIRMED: u8 = 1;
 pub const BRIDGE_STATUS_COMPLETED: u8 = 2;
 pub const BRIDGE_STATUS_FAILED: u8 = 3;
+pub const BRIDGE_STATUS_REFUNDED: u8 = 4;
+
+/// How long a lock must stay undelivered before the admin may refund it.
+pub const LOCK_REFUND_TIMEOUT: i64 = 24 * 60 * 60; // 1 day
+
+// Bits of `BridgeState::pause_flags`
+pub const PAUSE_LOCK: u8 = 1 << 0;          // Outbound: lock_assets
+pub const PAUSE_UNLOCK: u8 = 1 << 1;        // Inbound: unlock_assets
+pub const PAUSE_VALIDATOR_OPS: u8 = 1 << 2; // validate_bridge and update_bridge_state
+pub const PAUSE_MAINTENANCE: u8 = 1 << 3;   // Everything above, while refunds still go through
+pub const PAUSE_FLAGS_ALL: u8 = PAUSE_LOCK | PAUSE_UNLOCK | PAUSE_VALIDATOR_OPS | PAUSE_MAINTENANCE;
+
+// Values of `BridgeState::pause_reason`: who or which tripwire set the emergency pause
+pub const PAUSE_REASON_NONE: u8 = 0;
+pub const PAUSE_REASON_MANUAL: u8 = 1;          // emergency_pause
+pub const PAUSE_REASON_LARGE_UNLOCK: u8 = 2;    // One unlock above max_unlock_bps of the vault
+pub const PAUSE_REASON_UNLOCK_RATE: u8 = 3;     // More than max_unlocks_per_window unlocks in a window
+pub const PAUSE_REASON_VAULT_SHORTFALL: u8 = 4; // Vault holds less than the ledger owes
+pub const PAUSE_REASON_PHANTOM_LOCK: u8 = 5;    // Validators approved a lock that was never made
+
+pub fn pause_reason_name(reason: u8) -> &'static str {
+    match reason {
+        PAUSE_REASON_NONE => "none",
+        PAUSE_REASON_MANUAL => "manual",
+        PAUSE_REASON_LARGE_UNLOCK => "large unlock",
+        PAUSE_REASON_UNLOCK_RATE => "unlock rate",
+        PAUSE_REASON_VAULT_SHORTFALL => "vault shortfall",
+        PAUSE_REASON_PHANTOM_LOCK => "unknown lock",
+        _ => "unknown",
+    }
+}
 
 #[account]
 pub struct BridgeState {
+    pub version: u8,                 // Layout version, always right after the discriminator
     pub admin: Pubkey,
     pub is_paused: bool,
     
@@ -17,11 +51,11 @@ pub struct BridgeState {
     pub bridge_fee: u64,              // Fee in basis points (100 = 1%)
     pub is_active: bool,
     
-    // Validator management
+    // Validator management (the validator registry itself lives in the validator program)
     pub validator_threshold: u8,      // Required signatures (same as requiredSignatures in Qubic)
-    pub active_validators: u8,
     
     // Chain configuration
+    pub genesis_hash: [u8; 32],      // Genesis hash of the Solana cluster this copy runs on
     pub qubic_chain_id: u64,         // Qubic chain identifier
     
     // Statistics matching Qubic contract
@@ -34,28 +68,113 @@ pub struct BridgeState {
     pub last_qubic_block: u64,
     pub emergency_pause_timestamp: i64,
     pub bump: u8,
+    
+    // Granular pauses, carved out of `reserved`: zeroes mean no guardian and nothing paused
+    pub guardian: Pubkey,            // May pause, but never unpause or reconfigure
+    pub pause_flags: u8,             // PAUSE_* bits
+    
+    // Circuit breaker, carved out of `reserved`: zero limits mean the tripwire is off
+    pub pause_reason: u8,            // PAUSE_REASON_* behind the current emergency pause
+    pub max_unlock_bps: u16,         // Largest single unlock, in basis points of the vault
+    pub max_unlocks_per_window: u16,
+    pub unlock_window_slots: u64,
+    pub window_start_slot: u64,
+    pub window_unlocks: u16,         // Unlocks since window_start_slot
+    pub reserved: [u8; 72],          // Room for new fields without a realloc
 }
 
 impl BridgeState {
-    pub const INIT_SPACE: usize = 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+    /// Layouts 0 to 2 were unversioned; see `state::legacy`.
+    pub const VERSION: u8 = 3;
+    /// Length of `reserved`.
+    pub const RESERVED: usize = 72;
+    pub const INIT_SPACE: usize = 1 + 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1
+        + 32 + 1 + 1 + 2 + 2 + 8 + 8 + 2 + Self::RESERVED;
     
+    /// The deployment attestations for this bridge must name.
+    pub fn attestation_domain(&self) -> AttestationDomain {
+        AttestationDomain {
+            genesis_hash: self.genesis_hash,
+            bridge_program: crate::ID,
+            qubic_network_id: self.qubic_chain_id,
+            bridge_id: self.bridge_id,
+        }
+    }
+
     pub fn is_emergency_paused(&self) -> bool {
         self.is_paused
     }
     
+    /// The guardian can only pause; everything else stays with the admin.
+    pub fn can_pause(&self, authority: &Pubkey) -> bool {
+        *authority == self.admin || (self.guardian != Pubkey::default() && *authority == self.guardian)
+    }
+    
+    /// Fails unless `operation` (a `PAUSE_*` bit) may run. The emergency pause and maintenance
+    /// mode stop every operation; the operation's own bit stops just that one.
+    pub fn require_not_paused(&self, operation: u8) -> Result<()> {
+        require!(!self.is_paused, BridgeError::BridgePaused);
+        require!(self.pause_flags & PAUSE_MAINTENANCE == 0, BridgeError::BridgeMaintenanceMode);
+        require!(self.pause_flags & operation == 0, BridgeError::OperationPaused);
+        Ok(())
+    }
+    
+    /// Emergency pause the bridge, recording why and when. Only the admin can undo it.
+    pub fn trip(&mut self, reason: u8, now: i64) {
+        self.is_paused = true;
+        self.pause_reason = reason;
+        self.emergency_pause_timestamp = now;
+    }
+    
+    /// The tripwire an unlock of `amount` at `slot` sets off, if any. `vault_balance` is read
+    /// before the transfer and `liabilities` is what the mint's ledger says the vault owes.
+    /// Counts the unlock towards the rate limit.
+    pub fn unlock_tripwire(&mut self, amount: u64, vault_balance: u64, liabilities: u64, slot: u64) -> Option<u8> {
+        if vault_balance < liabilities {
+            return Some(PAUSE_REASON_VAULT_SHORTFALL);
+        }
+        if self.max_unlock_bps > 0
+            && (amount as u128) * 10000 > (vault_balance as u128) * (self.max_unlock_bps as u128)
+        {
+            return Some(PAUSE_REASON_LARGE_UNLOCK);
+        }
+        if self.max_unlocks_per_window > 0 {
+            if slot >= self.window_start_slot.saturating_add(self.unlock_window_slots) {
+                self.window_start_slot = slot;
+                self.window_unlocks = 0;
+            }
+            self.window_unlocks = self.window_unlocks.saturating_add(1);
+            if self.window_unlocks > self.max_unlocks_per_window {
+                return Some(PAUSE_REASON_UNLOCK_RATE);
+            }
+        }
+        None
+    }
+    
     pub fn can_bridge(&self, amount: u64) -> bool {
-        self.is_active && !self.is_paused && amount >= self.min_lock_amount && amount <= self.max_lock_amount
+        self.is_active
+            && self.require_not_paused(PAUSE_LOCK).is_ok()
+            && amount >= self.min_lock_amount
+            && amount <= self.max_lock_amount
     }
     
-    pub fn calculate_fee(&self, amount: u64) -> (u64, u64) {
-        let fee = (amount * self.bridge_fee) / 10000; // Fee in basis points
-        let net_amount = amount - fee;
-        (net_amount, fee)
+    pub fn calculate_fee(&self, amount: u64) -> Result<(u64, u64)> {
+        // Fee in basis points, computed in u128 so large amounts cannot overflow
+        let fee = (amount as u128)
+            .checked_mul(self.bridge_fee as u128)
+            .and_then(|v| v.checked_div(10000))
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(BridgeError::ArithmeticOverflow)?;
+        let net_amount = amount
+            .checked_sub(fee)
+            .ok_or(BridgeError::ArithmeticOverflow)?;
+        Ok((net_amount, fee))
     }
 }
 
 #[account]
 pub struct BridgeTransaction {
+    pub version: u8,                 // Layout version, always right after the discriminator
     pub id: u64,                     // Same as lockId in Qubic
     pub user: Pubkey,
     pub token_mint: Pubkey,
@@ -64,7 +183,7 @@ pub struct BridgeTransaction {
     pub fee: u64,                    // Fee deducted
     pub qubic_destination: [u8; 32], // Qubic recipient address
     pub memo: [u8; 64],              // Optional memo (same as Qubic)
-    pub status: u8,                  // 0: Pending, 1: Confirmed, 2: Completed, 3: Failed
+    pub status: u8,                  // 0: Pending, 1: Confirmed, 2: Completed, 3: Failed, 4: Refunded
     pub confirmations: u64,
     pub created_at: i64,
     pub updated_at: i64,
@@ -73,13 +192,20 @@ pub struct BridgeTransaction {
     pub validator_signatures: Vec<[u8; 64]>,
     pub is_processed: bool,          // Same as lockProcessed in Qubic
     pub bump: u8,
+    pub reserved: [u8; 64],          // Room for new fields without a realloc
 }
 
 impl BridgeTransaction {
-    // Updated space calculation: id(8) + user(32) + token_mint(32) + amount(8) + actual_amount(8) + fee(8) + 
-    // qubic_destination(32) + memo(64) + status(1) + confirmations(8) + created_at(8) + updated_at(8) + 
-    // completed_at(8) + qubic_tx_hash(32) + validator_signatures(4 + 64*10) + is_processed(1) + bump(1)
-    pub const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 32 + 64 + 1 + 8 + 8 + 8 + 8 + 32 + 4 + (64 * 10) + 1 + 1;
+    /// Layout 0 was unversioned; see `state::legacy`.
+    pub const VERSION: u8 = 1;
+    /// Length of `reserved`.
+    pub const RESERVED: usize = 64;
+    // version(1) + id(8) + user(32) + token_mint(32) + amount(8) + actual_amount(8) + fee(8) +
+    // qubic_destination(32) + memo(64) + status(1) + confirmations(8) + created_at(8) + updated_at(8) +
+    // completed_at(8) + qubic_tx_hash(32) + validator_signatures(4 + 64*10) + is_processed(1) + bump(1) +
+    // reserved
+    pub const INIT_SPACE: usize =
+        1 + 8 + 32 + 32 + 8 + 8 + 8 + 32 + 64 + 1 + 8 + 8 + 8 + 8 + 32 + 4 + (64 * 10) + 1 + 1 + Self::RESERVED;
     
     pub fn is_completed(&self) -> bool {
         self.status == BRIDGE_STATUS_COMPLETED
@@ -88,31 +214,111 @@ impl BridgeTransaction {
     pub fn is_pending(&self) -> bool {
         self.status == BRIDGE_STATUS_PENDING
     }
+    
+    pub fn is_refunded(&self) -> bool {
+        self.status == BRIDGE_STATUS_REFUNDED
+    }
+    
+    /// Completed or refunded locks can no longer change and may eventually be closed
+    pub fn is_finalized(&self) -> bool {
+        self.is_completed() || self.is_refunded()
+    }
+    
+    /// Whether a delivery of this lock to Qubic was recorded. Only `validate_bridge` sets the
+    /// Qubic transaction, and only for a lock the validator quorum approved
+    pub fn is_delivered(&self) -> bool {
+        self.qubic_tx_hash != [0; 32]
+    }
+    
+    /// Statuses `update_bridge_state` may move a lock to. Refunds have their own instruction,
+    /// and completing requires a delivery recorded by `validate_bridge` first.
+    pub fn can_transition_to(&self, status: u8) -> bool {
+        match (self.status, status) {
+            (BRIDGE_STATUS_PENDING, BRIDGE_STATUS_CONFIRMED) => true,
+            (BRIDGE_STATUS_PENDING | BRIDGE_STATUS_CONFIRMED, BRIDGE_STATUS_COMPLETED) => self.qubic_tx_hash != [0; 32],
+            (BRIDGE_STATUS_PENDING | BRIDGE_STATUS_CONFIRMED, BRIDGE_STATUS_FAILED) => true,
+            _ => false,
+        }
+    }
 }
 
-#[account]
-pub struct ValidatorInfo {
-    pub pubkey: Pubkey,
-    pub qubic_validator_id: String,
-    pub is_active: bool,
-    pub stake: u64,
-    pub last_activity: i64,
-    pub bump: u8,
-}
+// Bridge direction constants, as attestations carry them
+pub const BRIDGE_DIRECTION_SOLANA_TO_QUBIC: u8 = validator::state::DIRECTION_SOLANA_TO_QUBIC;
+pub const BRIDGE_DIRECTION_QUBIC_TO_SOLANA: u8 = validator::state::DIRECTION_QUBIC_TO_SOLANA;
 
-impl ValidatorInfo {
-    pub const INIT_SPACE: usize = 32 + 4 + 32 + 1 + 8 + 8 + 1; // Pubkey + String (max 32) + bool + u64 + i64 + u8
-    
-    pub fn update_reputation(&mut self, success: bool) {
-        if success {
-            self.stake += 1;
-        } else if self.stake > 0 {
-            self.stake -= 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_fee(bridge_fee: u64) -> BridgeState {
+        let mut state = BridgeState::deserialize(&mut &[0u8; BridgeState::INIT_SPACE][..]).unwrap();
+        state.bridge_fee = bridge_fee;
+        state
+    }
+
+    #[test]
+    fn fee_is_taken_in_basis_points() {
+        assert_eq!(state_with_fee(100).calculate_fee(1_000_000).unwrap(), (990_000, 10_000));
+        assert_eq!(state_with_fee(0).calculate_fee(1_000_000).unwrap(), (1_000_000, 0));
+    }
+
+    #[test]
+    fn fee_on_max_amount_does_not_overflow() {
+        let (net, fee) = state_with_fee(10_000).calculate_fee(u64::MAX).unwrap();
+        assert_eq!((net, fee), (0, u64::MAX));
+    }
+
+    #[test]
+    fn fee_above_amount_is_an_error() {
+        let err = state_with_fee(10_001).calculate_fee(10_000).unwrap_err();
+        assert_eq!(err, error!(BridgeError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn fee_beyond_u64_is_an_error() {
+        let err = state_with_fee(u64::MAX).calculate_fee(u64::MAX).unwrap_err();
+        assert_eq!(err, error!(BridgeError::ArithmeticOverflow));
+    }
+
+    fn lock(status: u8, qubic_tx_hash: [u8; 32]) -> BridgeTransaction {
+        let mut lock = BridgeTransaction::deserialize(&mut &[0u8; BridgeTransaction::INIT_SPACE][..]).unwrap();
+        lock.status = status;
+        lock.qubic_tx_hash = qubic_tx_hash;
+        lock
+    }
+
+    #[test]
+    fn completing_requires_a_recorded_delivery() {
+        assert!(!lock(BRIDGE_STATUS_PENDING, [0; 32]).can_transition_to(BRIDGE_STATUS_COMPLETED));
+        assert!(lock(BRIDGE_STATUS_PENDING, [7; 32]).can_transition_to(BRIDGE_STATUS_COMPLETED));
+        assert!(lock(BRIDGE_STATUS_CONFIRMED, [7; 32]).can_transition_to(BRIDGE_STATUS_COMPLETED));
+    }
+
+    #[test]
+    fn final_statuses_do_not_change() {
+        for status in [BRIDGE_STATUS_COMPLETED, BRIDGE_STATUS_REFUNDED, BRIDGE_STATUS_FAILED] {
+            for next in BRIDGE_STATUS_PENDING..=BRIDGE_STATUS_REFUNDED {
+                assert!(!lock(status, [7; 32]).can_transition_to(next), "{} -> {}", status, next);
+            }
         }
-        self.last_activity = Clock::get().unwrap().unix_timestamp;
     }
-}
 
-// Bridge direction constants
-pub const BRIDGE_DIRECTION_SOLANA_TO_QUBIC: u8 = 0;
-pub const BRIDGE_DIRECTION_QUBIC_TO_SOLANA: u8 = 1;
\ No newline at end of file
+    #[test]
+    fn refunds_and_regressions_are_not_transitions() {
+        let confirmed = lock(BRIDGE_STATUS_CONFIRMED, [7; 32]);
+        assert!(!confirmed.can_transition_to(BRIDGE_STATUS_PENDING));
+        assert!(!confirmed.can_transition_to(BRIDGE_STATUS_REFUNDED));
+        assert!(!lock(BRIDGE_STATUS_PENDING, [0; 32]).can_transition_to(BRIDGE_STATUS_REFUNDED));
+    }
+
+    #[test]
+    fn only_a_recorded_qubic_transaction_counts_as_delivered() {
+        assert!(!lock(BRIDGE_STATUS_PENDING, [0; 32]).is_delivered());
+        assert!(lock(BRIDGE_STATUS_PENDING, [7; 32]).is_delivered());
+
+        // Confirmations can be reported by any one validator, so they prove nothing
+        let mut confirmed = lock(BRIDGE_STATUS_PENDING, [0; 32]);
+        confirmed.confirmations = 1;
+        assert!(!confirmed.is_delivered());
+    }
+}
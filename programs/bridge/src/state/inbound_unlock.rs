@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Replay guard for a Qubic -> Solana transfer, keyed by the Qubic lock ID. Inbound lock IDs
+/// come from HM25's counter and share nothing with the outbound `BridgeTransaction` IDs.
+#[account]
+pub struct InboundUnlock {
+    pub qubic_lock_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub unlocked_at: i64,
+    pub validator_signatures: Vec<[u8; 64]>, // Always empty: approval is the recounted on-chain votes
+    pub is_processed: bool,          // Set once the tokens have been released
+    pub bump: u8,
+}
+
+impl InboundUnlock {
+    // qubic_lock_id(8) + mint(32) + recipient(32) + amount(8) + unlocked_at(8) +
+    // validator_signatures(4 + 64*10) + is_processed(1) + bump(1)
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 8 + (4 + 64 * 10) + 1 + 1;
+}
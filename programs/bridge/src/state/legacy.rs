@@ -0,0 +1,250 @@
+// Account layouts deployed before accounts carried a version byte, kept so the
+// `migrate_*` instructions can read them. None of them has a version field, so they are
+// told apart by data length; each `upgrade` moves one layout forward by one step.
+use anchor_lang::prelude::*;
+
+use super::bridge_state::{BridgeState, BridgeTransaction, PAUSE_REASON_MANUAL, PAUSE_REASON_NONE};
+use super::mint_ledger::MintLedger;
+use crate::error::BridgeError;
+
+/// `BridgeState` while the validator registry still lived in the bridge (layout 0).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BridgeStateV0 {
+    pub admin: Pubkey,
+    pub is_paused: bool,
+    pub bridge_id: u64,
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub is_active: bool,
+    pub validator_threshold: u8,
+    pub active_validators: u8,
+    pub qubic_chain_id: u64,
+    pub total_locked_tokens: u64,
+    pub total_unlocked_tokens: u64,
+    pub total_bridge_transactions: u64,
+    pub total_validator_actions: u64,
+    pub next_lock_id: u64,
+    pub last_qubic_block: u64,
+    pub emergency_pause_timestamp: i64,
+    pub bump: u8,
+}
+
+impl BridgeStateV0 {
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// The validator count moved to the validator program's config.
+    pub fn upgrade(self) -> BridgeStateV1 {
+        BridgeStateV1 {
+            admin: self.admin,
+            is_paused: self.is_paused,
+            bridge_id: self.bridge_id,
+            min_lock_amount: self.min_lock_amount,
+            max_lock_amount: self.max_lock_amount,
+            bridge_fee: self.bridge_fee,
+            is_active: self.is_active,
+            validator_threshold: self.validator_threshold,
+            qubic_chain_id: self.qubic_chain_id,
+            total_locked_tokens: self.total_locked_tokens,
+            total_unlocked_tokens: self.total_unlocked_tokens,
+            total_bridge_transactions: self.total_bridge_transactions,
+            total_validator_actions: self.total_validator_actions,
+            next_lock_id: self.next_lock_id,
+            last_qubic_block: self.last_qubic_block,
+            emergency_pause_timestamp: self.emergency_pause_timestamp,
+            bump: self.bump,
+        }
+    }
+}
+
+/// `BridgeState` once the validator program became the registry (layout 1).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BridgeStateV1 {
+    pub admin: Pubkey,
+    pub is_paused: bool,
+    pub bridge_id: u64,
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub is_active: bool,
+    pub validator_threshold: u8,
+    pub qubic_chain_id: u64,
+    pub total_locked_tokens: u64,
+    pub total_unlocked_tokens: u64,
+    pub total_bridge_transactions: u64,
+    pub total_validator_actions: u64,
+    pub next_lock_id: u64,
+    pub last_qubic_block: u64,
+    pub emergency_pause_timestamp: i64,
+    pub bump: u8,
+}
+
+impl BridgeStateV1 {
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// These layouts predate the deployment identity; the admin supplies the genesis hash
+    /// of the cluster the bridge was deployed on.
+    pub fn upgrade(self, genesis_hash: [u8; 32]) -> Result<BridgeStateV2> {
+        require!(genesis_hash != [0; 32], BridgeError::InvalidDomain);
+        Ok(BridgeStateV2 {
+            admin: self.admin,
+            is_paused: self.is_paused,
+            bridge_id: self.bridge_id,
+            min_lock_amount: self.min_lock_amount,
+            max_lock_amount: self.max_lock_amount,
+            bridge_fee: self.bridge_fee,
+            is_active: self.is_active,
+            validator_threshold: self.validator_threshold,
+            genesis_hash,
+            qubic_chain_id: self.qubic_chain_id,
+            total_locked_tokens: self.total_locked_tokens,
+            total_unlocked_tokens: self.total_unlocked_tokens,
+            total_bridge_transactions: self.total_bridge_transactions,
+            total_validator_actions: self.total_validator_actions,
+            next_lock_id: self.next_lock_id,
+            last_qubic_block: self.last_qubic_block,
+            emergency_pause_timestamp: self.emergency_pause_timestamp,
+            bump: self.bump,
+        })
+    }
+}
+
+/// `BridgeState` with the deployment identity, before versioning (layout 2).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BridgeStateV2 {
+    pub admin: Pubkey,
+    pub is_paused: bool,
+    pub bridge_id: u64,
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub is_active: bool,
+    pub validator_threshold: u8,
+    pub genesis_hash: [u8; 32],
+    pub qubic_chain_id: u64,
+    pub total_locked_tokens: u64,
+    pub total_unlocked_tokens: u64,
+    pub total_bridge_transactions: u64,
+    pub total_validator_actions: u64,
+    pub next_lock_id: u64,
+    pub last_qubic_block: u64,
+    pub emergency_pause_timestamp: i64,
+    pub bump: u8,
+}
+
+impl BridgeStateV2 {
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 8 + 1 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn upgrade(self) -> BridgeState {
+        BridgeState {
+            version: BridgeState::VERSION,
+            admin: self.admin,
+            is_paused: self.is_paused,
+            bridge_id: self.bridge_id,
+            min_lock_amount: self.min_lock_amount,
+            max_lock_amount: self.max_lock_amount,
+            bridge_fee: self.bridge_fee,
+            is_active: self.is_active,
+            validator_threshold: self.validator_threshold,
+            genesis_hash: self.genesis_hash,
+            qubic_chain_id: self.qubic_chain_id,
+            total_locked_tokens: self.total_locked_tokens,
+            total_unlocked_tokens: self.total_unlocked_tokens,
+            total_bridge_transactions: self.total_bridge_transactions,
+            total_validator_actions: self.total_validator_actions,
+            next_lock_id: self.next_lock_id,
+            last_qubic_block: self.last_qubic_block,
+            emergency_pause_timestamp: self.emergency_pause_timestamp,
+            bump: self.bump,
+            guardian: Pubkey::default(),
+            pause_flags: 0,
+            pause_reason: if self.is_paused { PAUSE_REASON_MANUAL } else { PAUSE_REASON_NONE },
+            max_unlock_bps: 0,
+            max_unlocks_per_window: 0,
+            unlock_window_slots: 0,
+            window_start_slot: 0,
+            window_unlocks: 0,
+            reserved: [0; BridgeState::RESERVED],
+        }
+    }
+}
+
+/// `BridgeTransaction` before versioning (layout 0). Accounts were allocated for the
+/// maximum number of stored signatures, so the length is fixed despite the `Vec`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BridgeTransactionV0 {
+    pub id: u64,
+    pub user: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub actual_amount: u64,
+    pub fee: u64,
+    pub qubic_destination: [u8; 32],
+    pub memo: [u8; 64],
+    pub status: u8,
+    pub confirmations: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub completed_at: i64,
+    pub qubic_tx_hash: [u8; 32],
+    pub validator_signatures: Vec<[u8; 64]>,
+    pub is_processed: bool,
+    pub bump: u8,
+}
+
+impl BridgeTransactionV0 {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 32 + 64 + 1 + 8 + 8 + 8 + 8 + 32 + 4 + (64 * 10) + 1 + 1;
+
+    pub fn upgrade(self) -> BridgeTransaction {
+        BridgeTransaction {
+            version: BridgeTransaction::VERSION,
+            id: self.id,
+            user: self.user,
+            token_mint: self.token_mint,
+            amount: self.amount,
+            actual_amount: self.actual_amount,
+            fee: self.fee,
+            qubic_destination: self.qubic_destination,
+            memo: self.memo,
+            status: self.status,
+            confirmations: self.confirmations,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            completed_at: self.completed_at,
+            qubic_tx_hash: self.qubic_tx_hash,
+            validator_signatures: self.validator_signatures,
+            is_processed: self.is_processed,
+            bump: self.bump,
+            reserved: [0; BridgeTransaction::RESERVED],
+        }
+    }
+}
+
+/// `MintLedger` before versioning and per-mint pauses (layout 0).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MintLedgerV0 {
+    pub mint: Pubkey,
+    pub total_locked: u64,
+    pub total_unlocked: u64,
+    pub total_fees: u64,
+    pub last_audit_at: i64,
+    pub bump: u8,
+}
+
+impl MintLedgerV0 {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn upgrade(self) -> MintLedger {
+        MintLedger {
+            version: MintLedger::VERSION,
+            mint: self.mint,
+            total_locked: self.total_locked,
+            total_unlocked: self.total_unlocked,
+            total_fees: self.total_fees,
+            last_audit_at: self.last_audit_at,
+            is_paused: false,
+            bump: self.bump,
+            reserved: [0; MintLedger::RESERVED],
+        }
+    }
+}
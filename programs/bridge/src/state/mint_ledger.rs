@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::error::BridgeError;
+
+/// Per-mint accounting, so vault balances can be audited mint by mint.
+#[account]
+pub struct MintLedger {
+    pub version: u8,          // Layout version, always right after the discriminator
+    pub mint: Pubkey,
+    pub total_locked: u64,    // Net amounts locked (after fees)
+    pub total_unlocked: u64,  // Amounts released from the vault
+    pub total_fees: u64,      // Fees retained in the vault
+    pub last_audit_at: i64,
+    pub is_paused: bool,      // Stops locks and unlocks of this mint; refunds still go through
+    pub bump: u8,
+    pub reserved: [u8; 64],   // Room for new fields without a realloc
+}
+
+impl MintLedger {
+    /// Layout 0 was unversioned; see `state::legacy`.
+    pub const VERSION: u8 = 1;
+    /// Length of `reserved`.
+    pub const RESERVED: usize = 64;
+    // version(1) + mint(32) + total_locked(8) + total_unlocked(8) + total_fees(8) + last_audit_at(8) +
+    // is_paused(1) + bump(1) + reserved
+    pub const INIT_SPACE: usize = 1 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + Self::RESERVED;
+
+    /// Set up a ledger created by `init_if_needed`; a no-op once it exists.
+    pub fn init_if_new(&mut self, mint: Pubkey, bump: u8) {
+        if self.mint == Pubkey::default() {
+            self.version = Self::VERSION;
+            self.mint = mint;
+            self.bump = bump;
+        }
+    }
+
+    /// Tokens the vault owes to users: locked - unlocked.
+    pub fn liabilities(&self) -> Result<u64> {
+        self.total_locked
+            .checked_sub(self.total_unlocked)
+            .ok_or_else(|| error!(BridgeError::ArithmeticOverflow))
+    }
+
+    /// Balance the vault must hold at minimum: liabilities plus retained fees.
+    pub fn expected_vault_balance(&self) -> Result<u64> {
+        self.liabilities()?
+            .checked_add(self.total_fees)
+            .ok_or_else(|| error!(BridgeError::ArithmeticOverflow))
+    }
+
+    /// How far `vault_balance` falls short of the expected balance. A surplus is not a breach:
+    /// anyone can transfer tokens into the vault, and nobody is owed them.
+    pub fn shortfall(&self, vault_balance: u64) -> Result<u64> {
+        Ok(self.expected_vault_balance()?.saturating_sub(vault_balance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(total_locked: u64, total_unlocked: u64, total_fees: u64) -> MintLedger {
+        MintLedger {
+            version: MintLedger::VERSION,
+            mint: Pubkey::new_unique(),
+            total_locked,
+            total_unlocked,
+            total_fees,
+            last_audit_at: 0,
+            is_paused: false,
+            bump: 255,
+            reserved: [0; MintLedger::RESERVED],
+        }
+    }
+
+    #[test]
+    fn expected_balance_is_liabilities_plus_fees() {
+        let ledger = ledger(1_000, 400, 10);
+        assert_eq!(ledger.liabilities().unwrap(), 600);
+        assert_eq!(ledger.expected_vault_balance().unwrap(), 610);
+    }
+
+    #[test]
+    fn unlocking_more_than_locked_is_an_error() {
+        let err = ledger(100, 101, 0).liabilities().unwrap_err();
+        assert_eq!(err, error!(BridgeError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn expected_balance_overflow_is_an_error() {
+        let err = ledger(u64::MAX, 0, 1).expected_vault_balance().unwrap_err();
+        assert_eq!(err, error!(BridgeError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn shortfall_only_counts_missing_tokens() {
+        let ledger = ledger(1_000, 400, 10);
+        assert_eq!(ledger.shortfall(500).unwrap(), 110);
+        assert_eq!(ledger.shortfall(610).unwrap(), 0);
+        // Donations to the vault are tolerated
+        assert_eq!(ledger.shortfall(10_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn shortfall_propagates_ledger_corruption() {
+        assert!(ledger(1, 2, 0).shortfall(0).is_err());
+    }
+}
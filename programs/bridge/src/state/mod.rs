@@ -1,7 +1,13 @@
 // This file defines the state management for the bridge program.
 
 pub mod bridge_state;
-pub mod validator_set;
+pub mod inbound_unlock;
+pub mod legacy;
+pub mod mint_ledger;
+pub mod processed_receipt;
 
 pub use bridge_state::*;
-pub use validator_set::*;
\ No newline at end of file
+pub use inbound_unlock::*;
+pub use legacy::*;
+pub use mint_ledger::*;
+pub use processed_receipt::*;
\ No newline at end of file
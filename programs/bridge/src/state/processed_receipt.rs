@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// How long a finalized lock must stay open before `close_lock` may reclaim its rent.
+pub const LOCK_RETENTION_PERIOD: i64 = 7 * 24 * 60 * 60; // 7 days
+
+/// Compact record left behind when a finalized `BridgeTransaction` is closed.
+/// Keeps lock-ID lookups and replay protection working without the full account.
+#[account]
+pub struct ProcessedReceipt {
+    pub lock_id: u64,
+    pub user: Pubkey,
+    pub token_mint: Pubkey,
+    pub actual_amount: u64,
+    pub status: u8,                  // Final status of the lock (Completed or Refunded)
+    pub completed_at: i64,
+    pub qubic_tx_hash: [u8; 32],
+    pub domain_hash: [u8; 32],       // `AttestationDomain::hash` of the deployment that closed it
+    pub bump: u8,
+}
+
+impl ProcessedReceipt {
+    // lock_id(8) + user(32) + token_mint(32) + actual_amount(8) + status(1) + completed_at(8) +
+    // qubic_tx_hash(32) + domain_hash(32) + bump(1)
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1 + 8 + 32 + 32 + 1;
+}
@@ -0,0 +1,58 @@
+// Off-chain helpers for the validator program (enabled by the `client` feature).
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use crate::state::Attestation;
+
+pub fn validator_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"validator_config"], &crate::ID).0
+}
+
+pub fn validator_info_address(validator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"validator", validator.as_ref()], &crate::ID).0
+}
+
+pub fn validation_state_address(transaction_hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"validation", transaction_hash.as_ref()], &crate::ID).0
+}
+
+pub fn validation_vote_address(transaction_hash: &[u8; 32], validator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vote", transaction_hash.as_ref(), validator.as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn validate_transaction(validator: &Pubkey, attestation: Attestation, is_valid: bool) -> Instruction {
+    let transaction_hash = attestation.digest();
+    let accounts = crate::accounts::ValidateTransaction {
+        validator: *validator,
+        validator_config: validator_config_address(),
+        validator_info: validator_info_address(validator),
+        validation_vote: validation_vote_address(&transaction_hash, validator),
+        validation_state: validation_state_address(&transaction_hash),
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::ValidateTransaction { attestation, is_valid }.data(),
+    }
+}
+
+pub fn migrate_validator_info(admin: &Pubkey, validator_pubkey: Pubkey) -> Instruction {
+    let accounts = crate::accounts::MigrateValidatorInfo {
+        admin: *admin,
+        validator_config: validator_config_address(),
+        validator_info: validator_info_address(&validator_pubkey),
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateValidatorInfo { validator_pubkey }.data(),
+    }
+}
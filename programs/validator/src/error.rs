@@ -18,4 +18,32 @@ pub enum ValidatorError {
     ValidatorAlreadyExists,
     #[msg("Invalid validator state")]
     InvalidValidatorState,
+    #[msg("Validator is not active")]
+    ValidatorInactive,
+    #[msg("Invalid validation threshold")]
+    InvalidThreshold,
+    #[msg("Arithmetic overflow or underflow")]
+    ArithmeticOverflow,
+    #[msg("Bonded stake is below the required minimum")]
+    InsufficientStake,
+    #[msg("Staking is not configured")]
+    StakingNotConfigured,
+    #[msg("Invalid stake token account")]
+    InvalidStakeAccount,
+    #[msg("Unbonding period has not elapsed")]
+    UnbondingPeriodActive,
+    #[msg("Invalid equivocation proof")]
+    InvalidEquivocationProof,
+    #[msg("Attestation deadline has not passed")]
+    DeadlineNotReached,
+    #[msg("Validator already voted on this attestation")]
+    AlreadyVoted,
+    #[msg("Attestation is for a different bridge deployment")]
+    WrongDomain,
+    #[msg("Account data matches no known layout")]
+    UnknownAccountLayout,
+    #[msg("Attestation never reached the approval threshold")]
+    AttestationNotApproved,
+    #[msg("Only the bridge program can record relay activity")]
+    UnauthorizedBridge,
 }
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct BondStake<'info> {
+    pub validator: Signer<'info>,
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        constraint = validator_config.staking_enabled() @ ValidatorError::StakingNotConfigured
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+    #[account(
+        mut,
+        constraint = validator_token_account.owner == validator.key() @ ValidatorError::InvalidStakeAccount,
+        constraint = validator_token_account.mint == validator_config.stake_mint @ ValidatorError::InvalidStakeAccount
+    )]
+    pub validator_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<BondStake>, amount: u64) -> Result<()> {
+    require!(amount > 0, ValidatorError::InvalidArgument);
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.validator_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.validator.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let validator_info = &mut ctx.accounts.validator_info;
+    validator_info.stake = validator_info.stake
+        .checked_add(amount)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+    
+    Ok(())
+}
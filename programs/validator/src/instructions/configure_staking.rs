@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct ConfigureStaking<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        has_one = admin @ ValidatorError::Unauthorized
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    pub stake_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = validator_config,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(constraint = slash_treasury.mint == stake_mint.key() @ ValidatorError::InvalidStakeAccount)]
+    pub slash_treasury: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ConfigureStaking>,
+    min_stake: u64,
+    unbonding_period: i64,
+    slash_bps: u16,
+) -> Result<()> {
+    require!(unbonding_period > 0, ValidatorError::InvalidArgument);
+    require!(slash_bps > 0 && slash_bps <= 10000, ValidatorError::InvalidArgument);
+
+    let validator_config = &mut ctx.accounts.validator_config;
+    validator_config.stake_mint = ctx.accounts.stake_mint.key();
+    validator_config.slash_treasury = ctx.accounts.slash_treasury.key();
+    validator_config.min_stake = min_stake;
+    validator_config.unbonding_period = unbonding_period;
+    validator_config.slash_bps = slash_bps;
+    
+    Ok(())
+}
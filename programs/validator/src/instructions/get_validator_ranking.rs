@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct GetValidatorRanking<'info> {
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+}
+
+/// Rank the ValidatorInfo accounts passed as remaining accounts, best first:
+/// by SLA score, then attestations signed. Return data is capped at 1KB, so
+/// callers should pass at most 14 validators per call.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetValidatorRanking<'info>>,
+) -> Result<Vec<ValidatorRanking>> {
+    let mut ranking = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| {
+            let info = Account::<ValidatorInfo>::try_from(account)?;
+            Ok(ValidatorRanking {
+                validator: info.validator_pubkey,
+                sla_bps: info.sla_bps(),
+                attestations_signed: info.attestations_signed,
+                missed_deadlines: info.missed_deadlines,
+                late_signatures: info.late_signatures,
+                last_seen_slot: info.last_seen_slot,
+                is_active: info.is_active,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    ranking.sort_by(|a, b| {
+        b.sla_bps
+            .cmp(&a.sla_bps)
+            .then(b.attestations_signed.cmp(&a.attestations_signed))
+    });
+
+    Ok(ranking)
+}
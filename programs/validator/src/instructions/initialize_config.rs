@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ValidatorConfig::INIT_SPACE,
+        seeds = [b"validator_config"],
+        bump
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `domain` names the bridge deployment this registry attests for and cannot change later.
+pub fn handler(ctx: Context<InitializeConfig>, threshold: u8, domain: AttestationDomain) -> Result<()> {
+    require!(threshold > 0, ValidatorError::InvalidThreshold);
+    require!(
+        domain.genesis_hash != [0; 32] && domain.bridge_program != Pubkey::default(),
+        ValidatorError::InvalidArgument
+    );
+
+    let validator_config = &mut ctx.accounts.validator_config;
+    validator_config.admin = ctx.accounts.admin.key();
+    validator_config.threshold = threshold;
+    validator_config.active_validators = 0;
+    validator_config.attestation_window_slots = DEFAULT_ATTESTATION_WINDOW_SLOTS;
+    validator_config.min_sla_bps = 0;
+    validator_config.sla_min_samples = 0;
+    validator_config.domain = domain;
+    validator_config.bump = ctx.bumps.validator_config;
+    
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+#[instruction(validator_pubkey: Pubkey)]
+pub struct MigrateValidatorInfo<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        has_one = admin @ ValidatorError::Unauthorized
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    /// CHECK: may hold any earlier layout, so it is decoded by hand in the handler
+    #[account(
+        mut,
+        seeds = [b"validator", validator_pubkey.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub validator_info: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade a registry entry from any earlier layout in place, topping up rent from the admin.
+pub fn handler(ctx: Context<MigrateValidatorInfo>, validator_pubkey: Pubkey) -> Result<()> {
+    let account = ctx.accounts.validator_info.to_account_info();
+    let migrated = {
+        let data = account.try_borrow_data()?;
+        require!(data.len() > 8, ValidatorError::UnknownAccountLayout);
+        require!(data[..8] == ValidatorInfo::DISCRIMINATOR[..], ErrorCode::AccountDiscriminatorMismatch);
+        let mut body = &data[8..];
+        match body.len() {
+            ValidatorInfoV0::LEN => ValidatorInfoV0::deserialize(&mut body)?.upgrade().upgrade().upgrade(),
+            ValidatorInfoV1::LEN => ValidatorInfoV1::deserialize(&mut body)?.upgrade().upgrade(),
+            ValidatorInfoV2::LEN => ValidatorInfoV2::deserialize(&mut body)?.upgrade(),
+            ValidatorInfo::INIT_SPACE if body[0] == ValidatorInfo::VERSION => {
+                msg!("Validator {} already at layout {}", validator_pubkey, ValidatorInfo::VERSION);
+                return Ok(());
+            }
+            _ => return err!(ValidatorError::UnknownAccountLayout),
+        }
+    };
+
+    let len = 8 + ValidatorInfo::INIT_SPACE;
+    let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    account.realloc(len, true)?;
+    let mut data = account.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    msg!("Validator {} migrated to layout {}", validator_pubkey, ValidatorInfo::VERSION);
+
+    Ok(())
+}
@@ -1,7 +1,36 @@
+// Every instruction module exposes its own `handler`; lib.rs always calls them by path.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod initialize_config;
+pub mod update_threshold;
 pub mod register_validator;
 pub mod update_validator_status;
 pub mod validate_transaction;
+pub mod configure_staking;
+pub mod bond_stake;
+pub mod request_unbond;
+pub mod withdraw_unbonded;
+pub mod submit_equivocation_proof;
+pub mod submit_conflicting_votes;
+pub mod report_missed_attestation;
+pub mod update_sla;
+pub mod get_validator_ranking;
+pub mod migrate_validator_info;
+pub mod record_activity;
 
+pub use initialize_config::*;
+pub use update_threshold::*;
 pub use register_validator::*;
 pub use update_validator_status::*;
-pub use validate_transaction::*;
\ No newline at end of file
+pub use validate_transaction::*;
+pub use configure_staking::*;
+pub use bond_stake::*;
+pub use request_unbond::*;
+pub use withdraw_unbonded::*;
+pub use submit_equivocation_proof::*;
+pub use submit_conflicting_votes::*;
+pub use report_missed_attestation::*;
+pub use update_sla::*;
+pub use get_validator_ranking::*;
+pub use migrate_validator_info::*;
+pub use record_activity::*;
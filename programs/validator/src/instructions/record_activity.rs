@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct RecordActivity<'info> {
+    /// quantum_link's `bridge_state` PDA; only that program can sign for it
+    pub bridge_authority: Signer<'info>,
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator_info.validator_pubkey.as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+}
+
+/// Credit a validator for an unlock or delivery confirmation it submitted, once the
+/// bridge program has accepted it.
+pub fn handler(ctx: Context<RecordActivity>) -> Result<()> {
+    let (bridge_authority, _) = Pubkey::find_program_address(
+        &[b"bridge_state"],
+        &ctx.accounts.validator_config.domain.bridge_program,
+    );
+    require_keys_eq!(
+        ctx.accounts.bridge_authority.key(),
+        bridge_authority,
+        ValidatorError::UnauthorizedBridge
+    );
+
+    let validator_info = &mut ctx.accounts.validator_info;
+    validator_info.relays_landed = validator_info.relays_landed
+        .checked_add(1)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+    validator_info.last_seen_slot = Clock::get()?.slot;
+
+    Ok(())
+}
@@ -1,19 +1,27 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::error::ValidatorError;
 
 #[derive(Accounts)]
 #[instruction(validator_pubkey: Pubkey)]
 pub struct RegisterValidator<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        has_one = admin @ ValidatorError::Unauthorized
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
     #[account(
         init,
-        payer = authority,
-        space = 8 + 32 + 32 + 1,
+        payer = admin,
+        space = 8 + ValidatorInfo::INIT_SPACE,
         seeds = [b"validator", validator_pubkey.as_ref()],
         bump
     )]
-    pub validator_state: Account<'info, ValidatorState>,
+    pub validator_info: Account<'info, ValidatorInfo>,
     pub system_program: Program<'info, System>,
 }
 
@@ -22,10 +30,27 @@ pub fn handler(
     validator_pubkey: Pubkey,
     qubic_validator_id: [u8; 32],
 ) -> Result<()> {
-    let validator_state = &mut ctx.accounts.validator_state;
-    validator_state.validator_pubkey = validator_pubkey;
-    validator_state.qubic_validator_id = qubic_validator_id;
-    validator_state.is_active = true;
+    let validator_info = &mut ctx.accounts.validator_info;
+    validator_info.version = ValidatorInfo::VERSION;
+    validator_info.validator_pubkey = validator_pubkey;
+    validator_info.qubic_validator_id = qubic_validator_id;
+    validator_info.is_active = true;
+    validator_info.registered_at = Clock::get()?.unix_timestamp;
+    validator_info.stake = 0;
+    validator_info.unbonding_amount = 0;
+    validator_info.unbonding_available_at = 0;
+    validator_info.slashed_amount = 0;
+    validator_info.attestations_signed = 0;
+    validator_info.missed_deadlines = 0;
+    validator_info.late_signatures = 0;
+    validator_info.last_seen_slot = 0;
+    validator_info.bump = ctx.bumps.validator_info;
+    validator_info.relays_landed = 0;
+
+    let validator_config = &mut ctx.accounts.validator_config;
+    validator_config.active_validators = validator_config.active_validators
+        .checked_add(1)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
     
     Ok(())
 }
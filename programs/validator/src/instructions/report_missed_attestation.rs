@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+#[instruction(validator_pubkey: Pubkey, transaction_hash: [u8; 32])]
+pub struct ReportMissedAttestation<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator_pubkey.as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+    #[account(
+        seeds = [b"validation", transaction_hash.as_ref()],
+        bump = validation_state.bump
+    )]
+    pub validation_state: Account<'info, ValidationState>,
+    /// CHECK: the validator's vote PDA for this attestation; must not exist
+    #[account(
+        seeds = [b"vote", transaction_hash.as_ref(), validator_pubkey.as_ref()],
+        bump
+    )]
+    pub validation_vote: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + MissRecord::INIT_SPACE,
+        seeds = [b"miss", transaction_hash.as_ref(), validator_pubkey.as_ref()],
+        bump
+    )]
+    pub miss_record: Account<'info, MissRecord>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless crank: count a missed deadline against a validator that was
+/// active when an approved attestation opened and never voted on it.
+pub fn handler(
+    ctx: Context<ReportMissedAttestation>,
+    validator_pubkey: Pubkey,
+    transaction_hash: [u8; 32],
+) -> Result<()> {
+    let validation_state = &ctx.accounts.validation_state;
+    let validator_info = &mut ctx.accounts.validator_info;
+
+    require!(
+        Clock::get()?.slot > validation_state.deadline_slot,
+        ValidatorError::DeadlineNotReached
+    );
+    require!(ctx.accounts.validation_vote.data_is_empty(), ValidatorError::AlreadyVoted);
+    // Anyone can open an attestation for a lock that never happened; only the ones the
+    // validator set actually approved were owed a vote
+    require!(validation_state.is_approved, ValidatorError::AttestationNotApproved);
+    // Validators registered after the attestation opened, or inactive ones, owed nothing
+    require!(
+        validator_info.is_active && validator_info.registered_at <= validation_state.created_at,
+        ValidatorError::InvalidValidatorState
+    );
+
+    validator_info.missed_deadlines = validator_info.missed_deadlines
+        .checked_add(1)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+    if validator_info.enforce_sla(&mut ctx.accounts.validator_config) {
+        msg!("Validator {} deactivated: below SLA", validator_pubkey);
+    }
+
+    let miss_record = &mut ctx.accounts.miss_record;
+    miss_record.validator = validator_pubkey;
+    miss_record.transaction_hash = transaction_hash;
+    miss_record.bump = ctx.bumps.miss_record;
+    
+    Ok(())
+}
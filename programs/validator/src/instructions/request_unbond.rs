@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    pub validator: Signer<'info>,
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+}
+
+pub fn handler(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+    require!(amount > 0, ValidatorError::InvalidArgument);
+
+    let validator_info = &mut ctx.accounts.validator_info;
+    validator_info.stake = validator_info.stake
+        .checked_sub(amount)
+        .ok_or(ValidatorError::InsufficientStake)?;
+    validator_info.unbonding_amount = validator_info.unbonding_amount
+        .checked_add(amount)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+
+    // Every new request restarts the delay for the whole unbonding amount, so
+    // stake can never leave before it has been slashable for a full period
+    validator_info.unbonding_available_at = Clock::get()?.unix_timestamp
+        .checked_add(ctx.accounts.validator_config.unbonding_period)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+    
+    Ok(())
+}
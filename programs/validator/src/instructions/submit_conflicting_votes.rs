@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::error::ValidatorError;
+use super::submit_equivocation_proof::slash;
+
+#[derive(Accounts)]
+#[instruction(validator_pubkey: Pubkey, attestation_a: Attestation, attestation_b: Attestation)]
+pub struct SubmitConflictingVotes<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        constraint = validator_config.staking_enabled() @ ValidatorError::StakingNotConfigured
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator_pubkey.as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+    #[account(
+        seeds = [b"vote", attestation_a.digest().as_ref(), validator_pubkey.as_ref()],
+        bump = vote_a.bump
+    )]
+    pub vote_a: Account<'info, ValidationVote>,
+    #[account(
+        seeds = [b"vote", attestation_b.digest().as_ref(), validator_pubkey.as_ref()],
+        bump = vote_b.bump
+    )]
+    pub vote_b: Account<'info, ValidationVote>,
+    // Same record as submit_equivocation_proof: a lock is punished once, whichever proof lands
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + SlashRecord::INIT_SPACE,
+        seeds = [
+            b"slash",
+            validator_pubkey.as_ref(),
+            &[attestation_a.direction],
+            attestation_a.lock_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = validator_config.slash_treasury @ ValidatorError::InvalidStakeAccount)]
+    pub slash_treasury: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Slash a validator that approved two different attestations for the same lock on-chain.
+/// The on-chain counterpart of `submit_equivocation_proof`: the two ValidationVote accounts
+/// are the proof, since a vote account can only be created by its validator's signature.
+/// Permissionless.
+pub fn handler(
+    ctx: Context<SubmitConflictingVotes>,
+    validator_pubkey: Pubkey,
+    attestation_a: Attestation,
+    attestation_b: Attestation,
+) -> Result<()> {
+    let domain = ctx.accounts.validator_config.domain;
+    require!(
+        attestation_a.domain == domain && attestation_b.domain == domain,
+        ValidatorError::WrongDomain
+    );
+    require!(attestation_a.conflicts_with(&attestation_b), ValidatorError::InvalidEquivocationProof);
+
+    // Rejecting a bogus statement while approving the real one is what an honest validator does
+    require!(
+        ctx.accounts.vote_a.is_valid && ctx.accounts.vote_b.is_valid,
+        ValidatorError::InvalidEquivocationProof
+    );
+
+    let amount = slash(
+        &mut ctx.accounts.validator_config,
+        &mut ctx.accounts.validator_info,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.slash_treasury,
+        &ctx.accounts.token_program,
+    )?;
+
+    let slash_record = &mut ctx.accounts.slash_record;
+    slash_record.validator = validator_pubkey;
+    slash_record.direction = attestation_a.direction;
+    slash_record.lock_id = attestation_a.lock_id;
+    slash_record.amount = amount;
+    slash_record.reporter = ctx.accounts.reporter.key();
+    slash_record.slashed_at = Clock::get()?.unix_timestamp;
+    slash_record.bump = ctx.bumps.slash_record;
+
+    msg!(
+        "Validator {} slashed {} for conflicting votes on lock {} (direction {})",
+        validator_pubkey,
+        amount,
+        attestation_a.lock_id,
+        attestation_a.direction
+    );
+
+    Ok(())
+}
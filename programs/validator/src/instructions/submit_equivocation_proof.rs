@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{self as instructions_sysvar, load_instruction_at_checked};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+#[instruction(validator_pubkey: Pubkey, attestation_a: Attestation)]
+pub struct SubmitEquivocationProof<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        constraint = validator_config.staking_enabled() @ ValidatorError::StakingNotConfigured
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator_pubkey.as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + SlashRecord::INIT_SPACE,
+        seeds = [
+            b"slash",
+            validator_pubkey.as_ref(),
+            &[attestation_a.direction],
+            attestation_a.lock_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = validator_config.slash_treasury @ ValidatorError::InvalidStakeAccount)]
+    pub slash_treasury: Account<'info, TokenAccount>,
+    /// CHECK: the instructions sysvar, used to find the ed25519 verifications of both attestations
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Slash a validator that signed two different attestations for the same lock, that is the
+/// same lock id in the same direction.
+/// Permissionless: the transaction must contain ed25519 program instructions
+/// verifying both signatures, which this instruction finds through the sysvar.
+pub fn handler(
+    ctx: Context<SubmitEquivocationProof>,
+    validator_pubkey: Pubkey,
+    attestation_a: Attestation,
+    attestation_b: Attestation,
+) -> Result<()> {
+    // Signing the same lock id on another deployment is not equivocation
+    let domain = ctx.accounts.validator_config.domain;
+    require!(
+        attestation_a.domain == domain && attestation_b.domain == domain,
+        ValidatorError::WrongDomain
+    );
+    require!(attestation_a.conflicts_with(&attestation_b), ValidatorError::InvalidEquivocationProof);
+
+    let instructions = ctx.accounts.instructions_sysvar.to_account_info();
+    require!(
+        has_ed25519_verification(&instructions, &validator_pubkey, &attestation_a.message()),
+        ValidatorError::InvalidEquivocationProof
+    );
+    require!(
+        has_ed25519_verification(&instructions, &validator_pubkey, &attestation_b.message()),
+        ValidatorError::InvalidEquivocationProof
+    );
+
+    let amount = slash(
+        &mut ctx.accounts.validator_config,
+        &mut ctx.accounts.validator_info,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.slash_treasury,
+        &ctx.accounts.token_program,
+    )?;
+
+    let slash_record = &mut ctx.accounts.slash_record;
+    slash_record.validator = validator_pubkey;
+    slash_record.direction = attestation_a.direction;
+    slash_record.lock_id = attestation_a.lock_id;
+    slash_record.amount = amount;
+    slash_record.reporter = ctx.accounts.reporter.key();
+    slash_record.slashed_at = Clock::get()?.unix_timestamp;
+    slash_record.bump = ctx.bumps.slash_record;
+
+    msg!(
+        "Validator {} slashed {} for equivocating on lock {} (direction {})",
+        validator_pubkey,
+        amount,
+        attestation_a.lock_id,
+        attestation_a.direction
+    );
+    
+    Ok(())
+}
+
+/// Take `slash_bps` of the validator's stake into the slash treasury and deactivate it.
+/// Returns the amount slashed.
+pub(crate) fn slash<'info>(
+    validator_config: &mut Account<'info, ValidatorConfig>,
+    validator_info: &mut Account<'info, ValidatorInfo>,
+    stake_vault: &Account<'info, TokenAccount>,
+    slash_treasury: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<u64> {
+    // Unbonding stake is still at risk, that is what the unbonding delay is for
+    let slashable = validator_info.stake
+        .checked_add(validator_info.unbonding_amount)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+    let amount = u64::try_from(
+        (slashable as u128) * (validator_config.slash_bps as u128) / 10000,
+    )
+    .map_err(|_| ValidatorError::ArithmeticOverflow)?;
+
+    let from_stake = amount.min(validator_info.stake);
+    validator_info.stake -= from_stake;
+    validator_info.unbonding_amount -= amount - from_stake;
+    validator_info.slashed_amount = validator_info.slashed_amount
+        .checked_add(amount)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+
+    if validator_info.is_active {
+        validator_info.is_active = false;
+        validator_config.active_validators = validator_config.active_validators
+            .checked_sub(1)
+            .ok_or(ValidatorError::ArithmeticOverflow)?;
+    }
+
+    if amount > 0 {
+        let config_seeds = &[b"validator_config".as_ref(), &[validator_config.bump]];
+        let config_signer = &[&config_seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: stake_vault.to_account_info(),
+                to: slash_treasury.to_account_info(),
+                authority: validator_config.to_account_info(),
+            },
+            config_signer,
+        );
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    Ok(amount)
+}
+
+// Layout of the ed25519 program instruction data: a 2-byte header followed by
+// one 14-byte offsets record per signature.
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_SIGNATURE_LEN: usize = 64;
+const ED25519_PUBKEY_LEN: usize = 32;
+
+/// Whether any ed25519 program instruction in this transaction verifies a
+/// signature by `pubkey` over exactly `message`.
+fn has_ed25519_verification(instructions: &AccountInfo, pubkey: &Pubkey, message: &[u8]) -> bool {
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        if ix.program_id == ed25519_program::ID && verifies(&ix.data, pubkey, message) {
+            return true;
+        }
+        index += 1;
+    }
+    false
+}
+
+fn verifies(data: &[u8], pubkey: &Pubkey, message: &[u8]) -> bool {
+    let read_u16 = |at: usize| -> Option<usize> {
+        data.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+    };
+    let count = match data.first() {
+        Some(count) => *count as usize,
+        None => return false,
+    };
+
+    (0..count).any(|i| {
+        let base = ED25519_HEADER_LEN + i * ED25519_OFFSETS_LEN;
+        let fields: Option<[usize; 7]> = (|| {
+            Some([
+                read_u16(base)?,      // signature offset
+                read_u16(base + 2)?,  // signature instruction index
+                read_u16(base + 4)?,  // public key offset
+                read_u16(base + 6)?,  // public key instruction index
+                read_u16(base + 8)?,  // message offset
+                read_u16(base + 10)?, // message size
+                read_u16(base + 12)?, // message instruction index
+            ])
+        })();
+        let Some([sig_offset, sig_ix, key_offset, key_ix, msg_offset, msg_size, msg_ix]) = fields else {
+            return false;
+        };
+
+        // Everything must live inside the ed25519 instruction itself, otherwise
+        // the verified bytes could differ from what we compare here
+        let this_instruction = u16::MAX as usize;
+        if sig_ix != this_instruction || key_ix != this_instruction || msg_ix != this_instruction {
+            return false;
+        }
+
+        data.get(sig_offset..sig_offset + ED25519_SIGNATURE_LEN).is_some()
+            && data.get(key_offset..key_offset + ED25519_PUBKEY_LEN) == Some(pubkey.as_ref())
+            && data.get(msg_offset..msg_offset + msg_size) == Some(message)
+    })
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct UpdateSla<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        has_one = admin @ ValidatorError::Unauthorized
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateSla>,
+    attestation_window_slots: u64,
+    min_sla_bps: u16,
+    sla_min_samples: u64,
+) -> Result<()> {
+    require!(attestation_window_slots > 0, ValidatorError::InvalidArgument);
+    require!(min_sla_bps <= 10000, ValidatorError::InvalidArgument);
+
+    let validator_config = &mut ctx.accounts.validator_config;
+    validator_config.attestation_window_slots = attestation_window_slots;
+    validator_config.min_sla_bps = min_sla_bps;
+    validator_config.sla_min_samples = sla_min_samples;
+    
+    Ok(())
+}
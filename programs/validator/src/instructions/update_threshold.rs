@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct UpdateThreshold<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        has_one = admin @ ValidatorError::Unauthorized
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+}
+
+pub fn handler(ctx: Context<UpdateThreshold>, threshold: u8) -> Result<()> {
+    require!(threshold > 0, ValidatorError::InvalidThreshold);
+
+    // Applies to attestations whose first vote lands after this update
+    ctx.accounts.validator_config.threshold = threshold;
+    
+    Ok(())
+}
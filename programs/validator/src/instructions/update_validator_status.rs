@@ -1,17 +1,24 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::error::ValidatorError;
 
 #[derive(Accounts)]
-#[instruction(_validator_pubkey: Pubkey)]
+#[instruction(validator_pubkey: Pubkey)]
 pub struct UpdateValidatorStatus<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    pub admin: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"validator", _validator_pubkey.as_ref()],
-        bump
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        has_one = admin @ ValidatorError::Unauthorized
     )]
-    pub validator_state: Account<'info, ValidatorState>,
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator_pubkey.as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
 }
 
 pub fn handler(
@@ -19,8 +26,19 @@ pub fn handler(
     _validator_pubkey: Pubkey,
     is_active: bool,
 ) -> Result<()> {
-    let validator_state = &mut ctx.accounts.validator_state;
-    validator_state.is_active = is_active;
+    let validator_info = &mut ctx.accounts.validator_info;
+    let validator_config = &mut ctx.accounts.validator_config;
+
+    // Keep the active count exact: only count real transitions
+    if validator_info.is_active != is_active {
+        validator_config.active_validators = if is_active {
+            validator_config.active_validators.checked_add(1)
+        } else {
+            validator_config.active_validators.checked_sub(1)
+        }
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+    }
+    validator_info.is_active = is_active;
     
     Ok(())
 }
@@ -1,21 +1,40 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::error::ValidatorError;
 
 #[derive(Accounts)]
-#[instruction(transaction_hash: [u8; 32])]
+#[instruction(attestation: Attestation)]
 pub struct ValidateTransaction<'info> {
     #[account(mut)]
     pub validator: Signer<'info>,
     #[account(
+        mut,
+        seeds = [b"validator_config"],
+        bump = validator_config.bump,
+        constraint = attestation.domain == validator_config.domain @ ValidatorError::WrongDomain
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
         seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_info.bump,
+        constraint = validator_info.is_active @ ValidatorError::ValidatorInactive,
+        constraint = validator_info.stake >= validator_config.min_stake @ ValidatorError::InsufficientStake
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+    #[account(
+        init,
+        payer = validator,
+        space = 8 + ValidationVote::INIT_SPACE,
+        seeds = [b"vote", attestation.digest().as_ref(), validator.key().as_ref()],
         bump
     )]
-    pub validator_state: Account<'info, ValidatorState>,
+    pub validation_vote: Account<'info, ValidationVote>,
     #[account(
         init_if_needed,
         payer = validator,
-        space = 8 + 32 + 1,
-        seeds = [b"validation", transaction_hash.as_ref()],
+        space = 8 + ValidationState::INIT_SPACE,
+        seeds = [b"validation", attestation.digest().as_ref()],
         bump
     )]
     pub validation_state: Account<'info, ValidationState>,
@@ -24,12 +43,61 @@ pub struct ValidateTransaction<'info> {
 
 pub fn handler(
     ctx: Context<ValidateTransaction>,
-    transaction_hash: [u8; 32],
+    attestation: Attestation,
     is_valid: bool,
 ) -> Result<()> {
+    let transaction_hash = attestation.digest();
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let validation_vote = &mut ctx.accounts.validation_vote;
+    validation_vote.validator = ctx.accounts.validator.key();
+    validation_vote.transaction_hash = transaction_hash;
+    validation_vote.lock_id = attestation.lock_id;
+    validation_vote.is_valid = is_valid;
+    validation_vote.voted_at = now;
+    validation_vote.bump = ctx.bumps.validation_vote;
+
     let validation_state = &mut ctx.accounts.validation_state;
-    validation_state.transaction_hash = transaction_hash;
-    validation_state.is_valid = is_valid;
+    if validation_state.created_at == 0 {
+        // First vote on this attestation
+        validation_state.transaction_hash = transaction_hash;
+        validation_state.lock_id = attestation.lock_id;
+        validation_state.threshold = ctx.accounts.validator_config.threshold;
+        validation_state.created_at = now;
+        validation_state.deadline_slot = clock.slot
+            .checked_add(ctx.accounts.validator_config.attestation_window_slots)
+            .ok_or(ValidatorError::ArithmeticOverflow)?;
+        validation_state.bump = ctx.bumps.validation_state;
+    }
+
+    // Each validator can only get here once per attestation (the vote uses `init`),
+    // so these counters are counts of distinct validators
+    if is_valid {
+        validation_state.approvals = validation_state.approvals
+            .checked_add(1)
+            .ok_or(ValidatorError::ArithmeticOverflow)?;
+    } else {
+        validation_state.rejections = validation_state.rejections
+            .checked_add(1)
+            .ok_or(ValidatorError::ArithmeticOverflow)?;
+    }
+    validation_state.is_approved = validation_state.approvals >= validation_state.threshold;
+
+    // Performance tracking
+    let validator_info = &mut ctx.accounts.validator_info;
+    validator_info.attestations_signed = validator_info.attestations_signed
+        .checked_add(1)
+        .ok_or(ValidatorError::ArithmeticOverflow)?;
+    validator_info.last_seen_slot = clock.slot;
+    if clock.slot > validation_state.deadline_slot {
+        validator_info.late_signatures = validator_info.late_signatures
+            .checked_add(1)
+            .ok_or(ValidatorError::ArithmeticOverflow)?;
+        if validator_info.enforce_sla(&mut ctx.accounts.validator_config) {
+            msg!("Validator {} deactivated: below SLA", validator_info.validator_pubkey);
+        }
+    }
     
     Ok(())
 }
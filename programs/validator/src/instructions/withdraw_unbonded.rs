@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ValidatorError;
+
+#[derive(Accounts)]
+pub struct WithdrawUnbonded<'info> {
+    pub validator: Signer<'info>,
+    #[account(
+        seeds = [b"validator_config"],
+        bump = validator_config.bump
+    )]
+    pub validator_config: Account<'info, ValidatorConfig>,
+    #[account(
+        mut,
+        seeds = [b"validator", validator.key().as_ref()],
+        bump = validator_info.bump
+    )]
+    pub validator_info: Account<'info, ValidatorInfo>,
+    #[account(
+        mut,
+        constraint = validator_token_account.owner == validator.key() @ ValidatorError::InvalidStakeAccount,
+        constraint = validator_token_account.mint == validator_config.stake_mint @ ValidatorError::InvalidStakeAccount
+    )]
+    pub validator_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawUnbonded>) -> Result<()> {
+    let amount = ctx.accounts.validator_info.unbonding_amount;
+    require!(amount > 0, ValidatorError::InvalidArgument);
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.validator_info.unbonding_available_at,
+        ValidatorError::UnbondingPeriodActive
+    );
+
+    let config_seeds = &[b"validator_config".as_ref(), &[ctx.accounts.validator_config.bump]];
+    let config_signer = &[&config_seeds[..]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.validator_token_account.to_account_info(),
+            authority: ctx.accounts.validator_config.to_account_info(),
+        },
+        config_signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let validator_info = &mut ctx.accounts.validator_info;
+    validator_info.unbonding_amount = 0;
+    validator_info.unbonding_available_at = 0;
+    
+    Ok(())
+}
@@ -1,17 +1,35 @@
 use anchor_lang::prelude::*;
 
-mod instructions;
-mod state;
-mod error;
+pub mod instructions;
+pub mod state;
+pub mod error;
+#[cfg(feature = "client")]
+pub mod client;
 
 use crate::instructions::*;
+use crate::state::{Attestation, AttestationDomain, ValidatorRanking};
 
-declare_id!("4LXpG5445Kmv879vWzCFx5bTc7miCJAY96iQDMJB1zaP");
+declare_id!("2r4oKhRMhoZs3t9JvE9yrNsJxiinLarSTNGAR4KRkQDH");
+
+// Validator registry for QuantumLink. quantum_link only unlocks funds once the
+// ValidationState of the matching attestation in this program is approved.
 
 #[program]
 pub mod validator {
     use super::*;
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        threshold: u8,
+        domain: AttestationDomain,
+    ) -> Result<()> {
+        instructions::initialize_config::handler(ctx, threshold, domain)
+    }
+
+    pub fn update_threshold(ctx: Context<UpdateThreshold>, threshold: u8) -> Result<()> {
+        instructions::update_threshold::handler(ctx, threshold)
+    }
+
     pub fn register_validator(
         ctx: Context<RegisterValidator>,
         validator_pubkey: Pubkey,
@@ -30,9 +48,81 @@ pub mod validator {
 
     pub fn validate_transaction(
         ctx: Context<ValidateTransaction>,
-        transaction_hash: [u8; 32],
+        attestation: Attestation,
         is_valid: bool,
     ) -> Result<()> {
-        instructions::validate_transaction::handler(ctx, transaction_hash, is_valid)
+        instructions::validate_transaction::handler(ctx, attestation, is_valid)
+    }
+
+    pub fn configure_staking(
+        ctx: Context<ConfigureStaking>,
+        min_stake: u64,
+        unbonding_period: i64,
+        slash_bps: u16,
+    ) -> Result<()> {
+        instructions::configure_staking::handler(ctx, min_stake, unbonding_period, slash_bps)
+    }
+
+    pub fn bond_stake(ctx: Context<BondStake>, amount: u64) -> Result<()> {
+        instructions::bond_stake::handler(ctx, amount)
+    }
+
+    pub fn request_unbond(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+        instructions::request_unbond::handler(ctx, amount)
+    }
+
+    pub fn withdraw_unbonded(ctx: Context<WithdrawUnbonded>) -> Result<()> {
+        instructions::withdraw_unbonded::handler(ctx)
+    }
+
+    pub fn submit_equivocation_proof(
+        ctx: Context<SubmitEquivocationProof>,
+        validator_pubkey: Pubkey,
+        attestation_a: Attestation,
+        attestation_b: Attestation,
+    ) -> Result<()> {
+        instructions::submit_equivocation_proof::handler(ctx, validator_pubkey, attestation_a, attestation_b)
+    }
+
+    pub fn submit_conflicting_votes(
+        ctx: Context<SubmitConflictingVotes>,
+        validator_pubkey: Pubkey,
+        attestation_a: Attestation,
+        attestation_b: Attestation,
+    ) -> Result<()> {
+        instructions::submit_conflicting_votes::handler(ctx, validator_pubkey, attestation_a, attestation_b)
+    }
+
+    pub fn report_missed_attestation(
+        ctx: Context<ReportMissedAttestation>,
+        validator_pubkey: Pubkey,
+        transaction_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::report_missed_attestation::handler(ctx, validator_pubkey, transaction_hash)
+    }
+
+    pub fn update_sla(
+        ctx: Context<UpdateSla>,
+        attestation_window_slots: u64,
+        min_sla_bps: u16,
+        sla_min_samples: u64,
+    ) -> Result<()> {
+        instructions::update_sla::handler(ctx, attestation_window_slots, min_sla_bps, sla_min_samples)
+    }
+
+    pub fn get_validator_ranking<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetValidatorRanking<'info>>,
+    ) -> Result<Vec<ValidatorRanking>> {
+        instructions::get_validator_ranking::handler(ctx)
+    }
+
+    /// Upgrade a registry entry from an earlier account layout in place
+    pub fn migrate_validator_info(ctx: Context<MigrateValidatorInfo>, validator_pubkey: Pubkey) -> Result<()> {
+        instructions::migrate_validator_info::handler(ctx, validator_pubkey)
+    }
+
+    /// Called by quantum_link when an unlock or delivery confirmation lands
+    pub fn record_activity(ctx: Context<RecordActivity>) -> Result<()> {
+        instructions::record_activity::handler(ctx)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Domain separator so attestation digests can never collide with other signed data.
+pub const ATTESTATION_DOMAIN: &[u8] = b"quantum_link:attestation:v4";
+
+/// Which chain the attested lock was made on. Solana and HM25 both number their locks from 1,
+/// so a lock id only names a lock together with its direction.
+pub const DIRECTION_SOLANA_TO_QUBIC: u8 = 0;
+pub const DIRECTION_QUBIC_TO_SOLANA: u8 = 1;
+
+/// The deployment an attestation is valid for. Signatures made for one cluster, program
+/// copy, Qubic network or bridge instance never verify against another.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AttestationDomain {
+    /// Genesis hash of the Solana cluster the bridge program runs on.
+    pub genesis_hash: [u8; 32],
+    pub bridge_program: Pubkey,
+    pub qubic_network_id: u64,
+    pub bridge_id: u64,
+}
+
+impl AttestationDomain {
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8;
+
+    fn write(&self, message: &mut Vec<u8>) {
+        message.extend_from_slice(&self.genesis_hash);
+        message.extend_from_slice(self.bridge_program.as_ref());
+        message.extend_from_slice(&self.qubic_network_id.to_le_bytes());
+        message.extend_from_slice(&self.bridge_id.to_le_bytes());
+    }
+
+    /// Commitment to the domain, for records that only need to name it.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(Self::INIT_SPACE);
+        self.write(&mut bytes);
+        hashv(&[ATTESTATION_DOMAIN, &bytes]).to_bytes()
+    }
+}
+
+/// Canonical statement validators sign and vote on: "lock `lock_id`, made in `direction`, of
+/// the bridge deployment `domain` releases `amount` of `mint` to `recipient`".
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Attestation {
+    pub domain: AttestationDomain,
+    /// `DIRECTION_SOLANA_TO_QUBIC` or `DIRECTION_QUBIC_TO_SOLANA`.
+    pub direction: u8,
+    pub lock_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+impl Attestation {
+    /// Bytes a validator signs off-chain:
+    /// separator || domain || direction || lock_id || mint || recipient || amount.
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(ATTESTATION_DOMAIN.len() + AttestationDomain::INIT_SPACE + 1 + 8 + 32 + 32 + 8);
+        message.extend_from_slice(ATTESTATION_DOMAIN);
+        self.domain.write(&mut message);
+        message.push(self.direction);
+        message.extend_from_slice(&self.lock_id.to_le_bytes());
+        message.extend_from_slice(self.mint.as_ref());
+        message.extend_from_slice(self.recipient.as_ref());
+        message.extend_from_slice(&self.amount.to_le_bytes());
+        message
+    }
+
+    /// Seed of the ValidationState and ValidationVote accounts for this attestation.
+    pub fn digest(&self) -> [u8; 32] {
+        hashv(&[&self.message()]).to_bytes()
+    }
+
+    /// Whether approving both `self` and `other` is equivocation: two different statements
+    /// about the same lock of the same deployment. Both chains number their locks from 1, so
+    /// the same id in two directions is two locks.
+    pub fn conflicts_with(&self, other: &Attestation) -> bool {
+        self.domain == other.domain
+            && self.direction == other.direction
+            && self.lock_id == other.lock_id
+            && self.digest() != other.digest()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation(direction: u8, lock_id: u64, amount: u64) -> Attestation {
+        Attestation {
+            domain: AttestationDomain::default(),
+            direction,
+            lock_id,
+            mint: Pubkey::new_from_array([1; 32]),
+            recipient: Pubkey::new_from_array([2; 32]),
+            amount,
+        }
+    }
+
+    #[test]
+    fn only_different_statements_about_one_lock_conflict() {
+        let honest = attestation(DIRECTION_QUBIC_TO_SOLANA, 7, 100);
+        assert!(honest.conflicts_with(&attestation(DIRECTION_QUBIC_TO_SOLANA, 7, 1_000)));
+
+        assert!(!honest.conflicts_with(&honest));
+        assert!(!honest.conflicts_with(&attestation(DIRECTION_SOLANA_TO_QUBIC, 7, 1_000)));
+        assert!(!honest.conflicts_with(&attestation(DIRECTION_QUBIC_TO_SOLANA, 8, 1_000)));
+
+        let elsewhere = Attestation {
+            domain: AttestationDomain { bridge_id: 2, ..AttestationDomain::default() },
+            ..attestation(DIRECTION_QUBIC_TO_SOLANA, 7, 1_000)
+        };
+        assert!(!honest.conflicts_with(&elsewhere));
+    }
+}
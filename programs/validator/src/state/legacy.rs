@@ -0,0 +1,115 @@
+// `ValidatorInfo` layouts deployed before it carried a version byte, kept so
+// `migrate_validator_info` can read them. They are told apart by data length; each
+// `upgrade` moves one layout forward by one step.
+use anchor_lang::prelude::*;
+
+use super::ValidatorInfo;
+
+/// Registry entry before staking (layout 0).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatorInfoV0 {
+    pub validator_pubkey: Pubkey,
+    pub qubic_validator_id: [u8; 32],
+    pub is_active: bool,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl ValidatorInfoV0 {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 1;
+
+    /// Validators registered before staking start with nothing bonded.
+    pub fn upgrade(self) -> ValidatorInfoV1 {
+        ValidatorInfoV1 {
+            validator_pubkey: self.validator_pubkey,
+            qubic_validator_id: self.qubic_validator_id,
+            is_active: self.is_active,
+            registered_at: self.registered_at,
+            stake: 0,
+            unbonding_amount: 0,
+            unbonding_available_at: 0,
+            slashed_amount: 0,
+            bump: self.bump,
+        }
+    }
+}
+
+/// Registry entry with staking, before performance tracking (layout 1).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatorInfoV1 {
+    pub validator_pubkey: Pubkey,
+    pub qubic_validator_id: [u8; 32],
+    pub is_active: bool,
+    pub registered_at: i64,
+    pub stake: u64,
+    pub unbonding_amount: u64,
+    pub unbonding_available_at: i64,
+    pub slashed_amount: u64,
+    pub bump: u8,
+}
+
+impl ValidatorInfoV1 {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Performance counters start from a clean history, which scores a perfect SLA.
+    pub fn upgrade(self) -> ValidatorInfoV2 {
+        ValidatorInfoV2 {
+            validator_pubkey: self.validator_pubkey,
+            qubic_validator_id: self.qubic_validator_id,
+            is_active: self.is_active,
+            registered_at: self.registered_at,
+            stake: self.stake,
+            unbonding_amount: self.unbonding_amount,
+            unbonding_available_at: self.unbonding_available_at,
+            slashed_amount: self.slashed_amount,
+            attestations_signed: 0,
+            missed_deadlines: 0,
+            late_signatures: 0,
+            last_seen_slot: 0,
+            bump: self.bump,
+        }
+    }
+}
+
+/// Registry entry with performance counters, before versioning (layout 2).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatorInfoV2 {
+    pub validator_pubkey: Pubkey,
+    pub qubic_validator_id: [u8; 32],
+    pub is_active: bool,
+    pub registered_at: i64,
+    pub stake: u64,
+    pub unbonding_amount: u64,
+    pub unbonding_available_at: i64,
+    pub slashed_amount: u64,
+    pub attestations_signed: u64,
+    pub missed_deadlines: u64,
+    pub late_signatures: u64,
+    pub last_seen_slot: u64,
+    pub bump: u8,
+}
+
+impl ValidatorInfoV2 {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn upgrade(self) -> ValidatorInfo {
+        ValidatorInfo {
+            version: ValidatorInfo::VERSION,
+            validator_pubkey: self.validator_pubkey,
+            qubic_validator_id: self.qubic_validator_id,
+            is_active: self.is_active,
+            registered_at: self.registered_at,
+            stake: self.stake,
+            unbonding_amount: self.unbonding_amount,
+            unbonding_available_at: self.unbonding_available_at,
+            slashed_amount: self.slashed_amount,
+            attestations_signed: self.attestations_signed,
+            missed_deadlines: self.missed_deadlines,
+            late_signatures: self.late_signatures,
+            last_seen_slot: self.last_seen_slot,
+            bump: self.bump,
+            relays_landed: 0,
+            reserved: [0; ValidatorInfo::RESERVED],
+        }
+    }
+}
@@ -1,14 +1,178 @@
+pub mod attestation;
+pub mod legacy;
+
+pub use attestation::*;
+pub use legacy::*;
+
+/// Default time validators have to vote on an attestation (~1 minute).
+pub const DEFAULT_ATTESTATION_WINDOW_SLOTS: u64 = 150;
+
 use anchor_lang::prelude::*;
 
+/// Registry-wide settings; only `admin` may register or (de)activate validators.
+#[account]
+pub struct ValidatorConfig {
+    pub admin: Pubkey,
+    pub threshold: u8,           // Distinct approvals required for a ValidationState
+    pub active_validators: u8,
+    
+    // Staking (all zero until `configure_staking` runs)
+    pub stake_mint: Pubkey,
+    pub slash_treasury: Pubkey,  // Token account receiving slashed stake
+    pub min_stake: u64,          // Bonded stake required to vote
+    pub unbonding_period: i64,   // Seconds between request_unbond and withdraw_unbonded
+    pub slash_bps: u16,          // Share of bonded + unbonding stake slashed per offence
+    
+    // Performance SLA
+    pub attestation_window_slots: u64, // Votes after created slot + window are late
+    pub min_sla_bps: u16,              // Validators scoring below this are deactivated (0 = off)
+    pub sla_min_samples: u64,          // Expected attestations before the SLA is enforced
+
+    // Only attestations for this deployment can be voted on
+    pub domain: AttestationDomain,
+    pub bump: u8,
+}
+
+impl ValidatorConfig {
+    pub const INIT_SPACE: usize = 32 + 1 + 1 + 32 + 32 + 8 + 8 + 2 + 8 + 2 + 8 + AttestationDomain::INIT_SPACE + 1;
+
+    pub fn staking_enabled(&self) -> bool {
+        self.stake_mint != Pubkey::default()
+    }
+}
+
 #[account]
-pub struct ValidatorState {
+pub struct ValidatorInfo {
+    pub version: u8,                  // Layout version, always right after the discriminator
     pub validator_pubkey: Pubkey,
     pub qubic_validator_id: [u8; 32],
     pub is_active: bool,
+    pub registered_at: i64,
+    
+    // Bonded SPL stake held in the stake vault
+    pub stake: u64,
+    pub unbonding_amount: u64,        // Still slashable until withdrawn
+    pub unbonding_available_at: i64,
+    pub slashed_amount: u64,
+    
+    // Performance counters
+    pub attestations_signed: u64,
+    pub missed_deadlines: u64,
+    pub late_signatures: u64,
+    pub last_seen_slot: u64,
+    pub bump: u8,
+    pub relays_landed: u64,           // Unlocks and delivery confirmations it submitted that landed
+    pub reserved: [u8; 56],           // Room for new fields without a realloc
 }
 
+impl ValidatorInfo {
+    /// Layouts 0 to 2 were unversioned; see `state::legacy`.
+    pub const VERSION: u8 = 3;
+    /// Length of `reserved`.
+    pub const RESERVED: usize = 56;
+    pub const INIT_SPACE: usize = 1 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + Self::RESERVED;
+
+    /// Share of expected attestations signed on time, in basis points.
+    /// A validator with no history scores a perfect 10000.
+    pub fn sla_bps(&self) -> u16 {
+        let expected = self.attestations_signed.saturating_add(self.missed_deadlines);
+        if expected == 0 {
+            return 10000;
+        }
+        let on_time = self.attestations_signed.saturating_sub(self.late_signatures);
+        ((on_time as u128) * 10000 / (expected as u128)) as u16
+    }
+
+    pub fn expected_attestations(&self) -> u64 {
+        self.attestations_signed.saturating_add(self.missed_deadlines)
+    }
+
+    /// Deactivate the validator if it has enough history and is below the SLA.
+    /// Returns true when the validator was deactivated by this call.
+    pub fn enforce_sla(&mut self, config: &mut ValidatorConfig) -> bool {
+        if !self.is_active
+            || config.min_sla_bps == 0
+            || self.expected_attestations() < config.sla_min_samples
+            || self.sla_bps() >= config.min_sla_bps
+        {
+            return false;
+        }
+        self.is_active = false;
+        config.active_validators = config.active_validators.saturating_sub(1);
+        true
+    }
+}
+
+/// One validator's verdict on one attestation. Created with `init`, so a vote
+/// can never be overwritten, by its author or anyone else.
 #[account]
-pub struct ValidationState {
+pub struct ValidationVote {
+    pub validator: Pubkey,
     pub transaction_hash: [u8; 32],
+    pub lock_id: u64,
     pub is_valid: bool,
-}
\ No newline at end of file
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl ValidationVote {
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 1 + 8 + 1;
+}
+
+/// Aggregated verdict for an attestation, read by quantum_link before unlocking.
+#[account]
+pub struct ValidationState {
+    pub transaction_hash: [u8; 32],
+    pub lock_id: u64,
+    pub approvals: u8,
+    pub rejections: u8,
+    pub threshold: u8,           // Snapshot of ValidatorConfig::threshold at the first vote
+    pub is_approved: bool,       // Never reset; quantum_link recounts active approvers at unlock
+    pub created_at: i64,
+    pub deadline_slot: u64,      // Votes landing after this slot count as late
+    pub bump: u8,
+}
+
+impl ValidationState {
+    pub const INIT_SPACE: usize = 32 + 8 + 1 + 1 + 1 + 1 + 8 + 8 + 1;
+}
+
+/// Marks that `validator` was already penalised for missing one attestation.
+#[account]
+pub struct MissRecord {
+    pub validator: Pubkey,
+    pub transaction_hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl MissRecord {
+    pub const INIT_SPACE: usize = 32 + 32 + 1;
+}
+
+/// Entry returned by `get_validator_ranking`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorRanking {
+    pub validator: Pubkey,
+    pub sla_bps: u16,
+    pub attestations_signed: u64,
+    pub missed_deadlines: u64,
+    pub late_signatures: u64,
+    pub last_seen_slot: u64,
+    pub is_active: bool,
+}
+
+/// Marks a (validator, direction, lock_id) equivocation as already punished.
+#[account]
+pub struct SlashRecord {
+    pub validator: Pubkey,
+    pub direction: u8,
+    pub lock_id: u64,
+    pub amount: u64,
+    pub reporter: Pubkey,
+    pub slashed_at: i64,
+    pub bump: u8,
+}
+
+impl SlashRecord {
+    pub const INIT_SPACE: usize = 32 + 1 + 8 + 8 + 32 + 8 + 1;
+}
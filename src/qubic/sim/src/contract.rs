@@ -0,0 +1,326 @@
+// The HM25 contract state and its procedures, plus the slice of the Qubic ledger it touches:
+// identity balances, the contract's own balance and the current tick.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::io::*;
+use crate::state::*;
+
+/// Why a procedure reported `success = false`. The invocation amount is refunded either way.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ContractError {
+    #[error("bridge is not active")]
+    NotActive,
+    #[error("only the admin may call this procedure")]
+    NotAdmin,
+    #[error("invocator is not a registered validator")]
+    NotValidator,
+    #[error("amount {0} is outside the lock limits")]
+    InvalidAmount(u64),
+    #[error("invocator holds {balance}, cannot send {amount}")]
+    InsufficientBalance { balance: u64, amount: u64 },
+    #[error("{valid} valid validator signatures, {required} required")]
+    InsufficientSignatures { valid: u8, required: u8 },
+    #[error("lock {0} was already unlocked")]
+    AlreadyProcessed(u64),
+    #[error("unlocking {amount} would release more than the {outstanding} still locked")]
+    ExceedsLocked { amount: u64, outstanding: u64 },
+    #[error("validator already registered")]
+    ValidatorExists,
+    #[error("validator not registered")]
+    UnknownValidator,
+    #[error("validator set is full")]
+    TooManyValidators,
+    #[error("removing the validator would leave fewer than requiredSignatures")]
+    BelowRequiredSignatures,
+    #[error("invalid configuration")]
+    InvalidConfig,
+    #[error("arithmetic overflow")]
+    Overflow,
+}
+
+#[derive(Debug, Clone)]
+pub struct Hm25 {
+    // Contract state, named as in HM25
+    admin: Id,
+    is_active: bool,
+    min_lock_amount: u64,
+    max_lock_amount: u64,
+    /// Basis points taken from each lock.
+    bridge_fee: u64,
+    required_signatures: u8,
+    validators: Vec<Id>,
+    next_lock_id: u64,
+    locks: BTreeMap<u64, Lock>,
+    /// Solana lock ids already unlocked here (`lockProcessed`).
+    processed: HashSet<u64>,
+    total_locked: u64,
+    total_unlocked: u64,
+    total_fees: u64,
+    total_transfers: u64,
+    total_validator_actions: u64,
+
+    // Ledger
+    tick: u64,
+    balances: HashMap<Id, u64>,
+    contract_balance: u64,
+}
+
+impl Hm25 {
+    /// The contract as `INITIALIZE` leaves it, administered by `admin`.
+    pub fn new(admin: Id, required_signatures: u8) -> Self {
+        Hm25 {
+            admin,
+            is_active: true,
+            min_lock_amount: DEFAULT_MIN_LOCK_AMOUNT,
+            max_lock_amount: DEFAULT_MAX_LOCK_AMOUNT,
+            bridge_fee: DEFAULT_BRIDGE_FEE,
+            required_signatures,
+            validators: Vec::new(),
+            next_lock_id: 1,
+            locks: BTreeMap::new(),
+            processed: HashSet::new(),
+            total_locked: 0,
+            total_unlocked: 0,
+            total_fees: 0,
+            total_transfers: 0,
+            total_validator_actions: 0,
+            tick: 0,
+            balances: HashMap::new(),
+            contract_balance: 0,
+        }
+    }
+
+    // ----- Procedures -----
+
+    /// `LockAssets`: take `amount` from the invocator, keep the fee and record a lock for
+    /// `amount - fee` to be released on Solana.
+    pub fn lock_assets(&mut self, invocator: Id, input: LockAssetsInput) -> Result<LockAssetsOutput, ContractError> {
+        if !self.is_active {
+            return Err(ContractError::NotActive);
+        }
+        if input.amount < self.min_lock_amount || input.amount > self.max_lock_amount {
+            return Err(ContractError::InvalidAmount(input.amount));
+        }
+        let balance = self.balance(&invocator);
+        if balance < input.amount {
+            return Err(ContractError::InsufficientBalance { balance, amount: input.amount });
+        }
+
+        let (actual_amount, fee) = self.calculate_fee(input.amount)?;
+        let total_locked = self.total_locked.checked_add(actual_amount).ok_or(ContractError::Overflow)?;
+        let total_fees = self.total_fees.checked_add(fee).ok_or(ContractError::Overflow)?;
+        let contract_balance = self.contract_balance.checked_add(input.amount).ok_or(ContractError::Overflow)?;
+
+        let lock_id = self.next_lock_id;
+        self.locks.insert(lock_id, Lock {
+            locker: invocator,
+            amount: input.amount,
+            actual_amount,
+            fee,
+            timestamp: self.tick,
+            solana_destination: input.solana_destination,
+            memo: input.memo,
+            status: LOCK_STATUS_PENDING,
+        });
+        self.balances.insert(invocator, balance - input.amount);
+        self.contract_balance = contract_balance;
+        self.total_locked = total_locked;
+        self.total_fees = total_fees;
+        self.total_transfers += 1;
+        self.next_lock_id += 1;
+
+        Ok(LockAssetsOutput { lock_id, actual_amount, fee })
+    }
+
+    /// `UnlockAssets`: a validator releases `amount` to `recipient` for a Solana lock, once
+    /// `requiredSignatures` distinct registered validators have signed. Each lock id unlocks once.
+    pub fn unlock_assets(&mut self, invocator: Id, input: UnlockAssetsInput) -> Result<UnlockAssetsOutput, ContractError> {
+        if !self.is_active {
+            return Err(ContractError::NotActive);
+        }
+        if !self.is_validator(&invocator) {
+            return Err(ContractError::NotValidator);
+        }
+        if input.amount == 0 {
+            return Err(ContractError::InvalidAmount(0));
+        }
+        if self.processed.contains(&input.lock_id) {
+            return Err(ContractError::AlreadyProcessed(input.lock_id));
+        }
+
+        let valid_signatures = self.count_valid_signatures(&input.validator_signatures);
+        if valid_signatures < self.required_signatures {
+            return Err(ContractError::InsufficientSignatures {
+                valid: valid_signatures,
+                required: self.required_signatures,
+            });
+        }
+
+        // Only what was locked here can be released; fees stay with the contract
+        let outstanding = self.total_locked - self.total_unlocked;
+        if input.amount > outstanding {
+            return Err(ContractError::ExceedsLocked { amount: input.amount, outstanding });
+        }
+        let recipient_balance = self.balance(&input.recipient)
+            .checked_add(input.amount)
+            .ok_or(ContractError::Overflow)?;
+
+        self.processed.insert(input.lock_id);
+        self.balances.insert(input.recipient, recipient_balance);
+        self.contract_balance -= input.amount;
+        self.total_unlocked += input.amount;
+        self.total_validator_actions += 1;
+
+        Ok(UnlockAssetsOutput { valid_signatures })
+    }
+
+    pub fn add_validator(&mut self, invocator: Id, input: ValidatorInput) -> Result<(), ContractError> {
+        self.require_admin(&invocator)?;
+        if input.validator == NULL_ID {
+            return Err(ContractError::InvalidConfig);
+        }
+        if self.is_validator(&input.validator) {
+            return Err(ContractError::ValidatorExists);
+        }
+        if self.validators.len() >= MAX_VALIDATORS {
+            return Err(ContractError::TooManyValidators);
+        }
+        self.validators.push(input.validator);
+        Ok(())
+    }
+
+    pub fn remove_validator(&mut self, invocator: Id, input: ValidatorInput) -> Result<(), ContractError> {
+        self.require_admin(&invocator)?;
+        let index = self.validators
+            .iter()
+            .position(|validator| *validator == input.validator)
+            .ok_or(ContractError::UnknownValidator)?;
+        if self.validators.len() - 1 < self.required_signatures as usize {
+            return Err(ContractError::BelowRequiredSignatures);
+        }
+        self.validators.remove(index);
+        Ok(())
+    }
+
+    /// `UpdateConfig`, with the same bounds `update_config` enforces on Solana.
+    pub fn update_config(&mut self, invocator: Id, input: UpdateConfigInput) -> Result<(), ContractError> {
+        self.require_admin(&invocator)?;
+        if input.min_lock_amount == 0
+            || input.max_lock_amount < input.min_lock_amount
+            || input.bridge_fee > MAX_BRIDGE_FEE
+            || input.required_signatures == 0
+            || input.required_signatures as usize > MAX_VALIDATORS
+        {
+            return Err(ContractError::InvalidConfig);
+        }
+
+        self.min_lock_amount = input.min_lock_amount;
+        self.max_lock_amount = input.max_lock_amount;
+        self.bridge_fee = input.bridge_fee;
+        self.required_signatures = input.required_signatures;
+        self.is_active = input.is_active;
+        Ok(())
+    }
+
+    // ----- Functions -----
+
+    pub fn get_bridge_info(&self) -> GetBridgeInfoOutput {
+        GetBridgeInfoOutput {
+            admin: self.admin,
+            min_lock_amount: self.min_lock_amount,
+            max_lock_amount: self.max_lock_amount,
+            bridge_fee: self.bridge_fee,
+            is_active: self.is_active,
+            validator_count: self.validators.len() as u64,
+            required_signatures: self.required_signatures as u64,
+            total_locked: self.total_locked,
+            total_unlocked: self.total_unlocked,
+            total_transfers: self.total_transfers,
+            total_validator_actions: self.total_validator_actions,
+            next_lock_id: self.next_lock_id,
+        }
+    }
+
+    pub fn get_lock_info(&self, input: GetLockInfoInput) -> GetLockInfoOutput {
+        match self.locks.get(&input.lock_id) {
+            Some(lock) => GetLockInfoOutput { found: true, lock: lock.clone() },
+            None => GetLockInfoOutput::default(),
+        }
+    }
+
+    // ----- Ledger -----
+
+    /// Credit `identity` with `amount` QU, as a transfer from outside the bridge would.
+    pub fn fund(&mut self, identity: Id, amount: u64) {
+        *self.balances.entry(identity).or_default() += amount;
+    }
+
+    pub fn balance(&self, identity: &Id) -> u64 {
+        self.balances.get(identity).copied().unwrap_or(0)
+    }
+
+    /// QU held by the contract: everything locked and not yet unlocked, plus fees.
+    pub fn contract_balance(&self) -> u64 {
+        self.contract_balance
+    }
+
+    pub fn total_fees(&self) -> u64 {
+        self.total_fees
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Move to the next tick. Locks made afterwards carry the new tick as their timestamp.
+    pub fn end_tick(&mut self) {
+        self.tick += 1;
+    }
+
+    pub fn is_processed(&self, lock_id: u64) -> bool {
+        self.processed.contains(&lock_id)
+    }
+
+    pub fn validators(&self) -> &[Id] {
+        &self.validators
+    }
+
+    /// Locks made on this contract, in id order.
+    pub fn locks(&self) -> impl Iterator<Item = (u64, &Lock)> {
+        self.locks.iter().map(|(id, lock)| (*id, lock))
+    }
+
+    // ----- Private -----
+
+    fn require_admin(&self, invocator: &Id) -> Result<(), ContractError> {
+        if *invocator == self.admin {
+            Ok(())
+        } else {
+            Err(ContractError::NotAdmin)
+        }
+    }
+
+    fn is_validator(&self, identity: &Id) -> bool {
+        self.validators.contains(identity)
+    }
+
+    /// Distinct registered validators among `signatures`. Empty slots and unknown or repeated
+    /// identities don't count, so one validator can't fill the quorum alone.
+    fn count_valid_signatures(&self, signatures: &[Id]) -> u8 {
+        let mut seen = HashSet::new();
+        signatures
+            .iter()
+            .take(MAX_VALIDATORS)
+            .filter(|signer| self.is_validator(signer) && seen.insert(**signer))
+            .count() as u8
+    }
+
+    /// `(amount - fee, fee)` with the fee in basis points, rounded down like on Solana.
+    fn calculate_fee(&self, amount: u64) -> Result<(u64, u64), ContractError> {
+        let fee = u64::try_from(amount as u128 * self.bridge_fee as u128 / 10_000)
+            .map_err(|_| ContractError::Overflow)?;
+        Ok((amount - fee, fee))
+    }
+}
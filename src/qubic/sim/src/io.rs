@@ -0,0 +1,100 @@
+// Inputs and outputs of the HM25 procedures and functions. Field names follow the contract;
+// a procedure's `success = false` is reported as an `Err` by `Hm25` instead.
+use serde::{Deserialize, Serialize};
+
+use crate::state::{Id, Lock};
+
+// Registration indices (`REGISTER_USER_PROCEDURE` / `REGISTER_USER_FUNCTION`)
+pub const PROCEDURE_LOCK_ASSETS: u16 = 1;
+pub const PROCEDURE_UNLOCK_ASSETS: u16 = 2;
+pub const PROCEDURE_ADD_VALIDATOR: u16 = 3;
+pub const PROCEDURE_REMOVE_VALIDATOR: u16 = 4;
+pub const PROCEDURE_UPDATE_CONFIG: u16 = 5;
+pub const FUNCTION_GET_BRIDGE_INFO: u16 = 1;
+pub const FUNCTION_GET_LOCK_INFO: u16 = 2;
+
+/// `LockAssets_input`. The amount is the invocation reward sent with the call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockAssetsInput {
+    pub solana_destination: [u8; 32],
+    pub amount: u64,
+    #[serde(with = "crate::state::memo_bytes")]
+    pub memo: [u8; 64],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockAssetsOutput {
+    pub lock_id: u64,
+    pub actual_amount: u64,
+    pub fee: u64,
+}
+
+/// `UnlockAssets_input`: release `amount` to `recipient` for Solana lock `lock_id`.
+/// `validator_signatures` lists the validators that signed off, unused slots `NULL_ID`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockAssetsInput {
+    pub lock_id: u64,
+    pub recipient: Id,
+    pub amount: u64,
+    pub validator_signatures: Vec<Id>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockAssetsOutput {
+    /// Distinct registered validators among the signatures.
+    pub valid_signatures: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorInput {
+    pub validator: Id,
+}
+
+/// `UpdateConfig_input`, the same parameters as `update_config` on Solana.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateConfigInput {
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub required_signatures: u8,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBridgeInfoOutput {
+    pub admin: Id,
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub is_active: bool,
+    pub validator_count: u64,
+    pub required_signatures: u64,
+    pub total_locked: u64,
+    pub total_unlocked: u64,
+    pub total_transfers: u64,
+    pub total_validator_actions: u64,
+    pub next_lock_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLockInfoInput {
+    pub lock_id: u64,
+}
+
+/// `GetLockInfo_output`. Functions cannot fail, so an unknown lock id reads as `found: false`
+/// with every other field zeroed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLockInfoOutput {
+    pub found: bool,
+    #[serde(flatten)]
+    pub lock: Lock,
+}
@@ -0,0 +1,9 @@
+// In-process model of the HM25 bridge contract on Qubic: the same procedures, functions
+// and state, run against a simulated ledger so the Qubic side can be tested without a node.
+
+pub mod contract;
+pub mod io;
+pub mod state;
+
+pub use contract::{ContractError, Hm25};
+pub use state::{Id, Lock, NULL_ID};
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// A Qubic identity (public key).
+pub type Id = [u8; 32];
+
+/// `NULL_ID`: the zero identity, which never owns anything.
+pub const NULL_ID: Id = [0; 32];
+
+// Lock status values (same as the Solana program's `BRIDGE_STATUS_*`)
+pub const LOCK_STATUS_PENDING: u8 = 0;
+pub const LOCK_STATUS_CONFIRMED: u8 = 1;
+pub const LOCK_STATUS_COMPLETED: u8 = 2;
+pub const LOCK_STATUS_FAILED: u8 = 3;
+
+/// `validatorSignatures` is an `Array<id, 16>`, so at most this many validators can sign.
+pub const MAX_VALIDATORS: usize = 16;
+
+/// Bridge fee ceiling in basis points (10%), as `UpdateConfig` enforces.
+pub const MAX_BRIDGE_FEE: u64 = 1000;
+
+/// Contract defaults at construction, matching `initialize_bridge` on Solana.
+pub const DEFAULT_MIN_LOCK_AMOUNT: u64 = 1_000_000;
+pub const DEFAULT_MAX_LOCK_AMOUNT: u64 = 1_000_000_000_000;
+pub const DEFAULT_BRIDGE_FEE: u64 = 100;
+
+/// One `LockAssets` call, as `GetLockInfo` reports it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lock {
+    pub locker: Id,
+    /// Amount sent with the call, before the fee.
+    pub amount: u64,
+    /// Amount to release on Solana (`amount - fee`).
+    pub actual_amount: u64,
+    pub fee: u64,
+    /// Tick the lock was made in.
+    pub timestamp: u64,
+    pub solana_destination: [u8; 32],
+    #[serde(with = "memo_bytes")]
+    pub memo: [u8; 64],
+    pub status: u8,
+}
+
+impl Default for Lock {
+    fn default() -> Self {
+        Lock {
+            locker: NULL_ID,
+            amount: 0,
+            actual_amount: 0,
+            fee: 0,
+            timestamp: 0,
+            solana_destination: [0; 32],
+            memo: [0; 64],
+            status: LOCK_STATUS_PENDING,
+        }
+    }
+}
+
+/// `[u8; 64]` has no serde impls of its own.
+pub(crate) mod memo_bytes {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 64], D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        bytes.try_into().map_err(|bytes: Vec<u8>| D::Error::invalid_length(bytes.len(), &"64 bytes"))
+    }
+}
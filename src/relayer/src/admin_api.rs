@@ -0,0 +1,161 @@
+// src/relayer/src/admin_api.rs
+// Operator-facing HTTP API for triaging transfers. Bind it to a private interface:
+// it can pause relaying and requeue dead-lettered transfers.
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+
+use shared::types::BridgeDirection;
+use crate::bridge_relayer::{BridgeRelayer, RetryRejected, TransferRecord, TransferState};
+
+#[derive(Debug, Deserialize)]
+struct TransferFilter {
+    status: Option<TransferState>,
+}
+
+/// `GET /health`, `GET /ready`, `GET /metrics`, `GET /transfers?status=`,
+/// `GET /transfers/:direction/:id`, `POST /transfers/:direction/:id/retry`, `POST /pause` and
+/// `POST /resume`. `:direction` is `SolanaToQubic` or `QubicToSolana`; lock ids repeat across them.
+pub fn router(relayer: Arc<BridgeRelayer>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/ready", get(ready))
+        .route("/metrics", get(metrics))
+        .route("/transfers", get(list_transfers))
+        .route("/transfers/:direction/:id", get(get_transfer))
+        .route("/transfers/:direction/:id/retry", post(retry_transfer))
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+        .with_state(relayer)
+}
+
+/// Liveness: the process is up. Always 200, with the full status for context.
+async fn health(State(relayer): State<Arc<BridgeRelayer>>) -> Json<serde_json::Value> {
+    Json(serde_json::json!(relayer.get_bridge_status().await))
+}
+
+/// Readiness: both chains polled successfully within the staleness window.
+async fn ready(State(relayer): State<Arc<BridgeRelayer>>) -> (StatusCode, Json<serde_json::Value>) {
+    let solana_connected = relayer.solana_connected();
+    let qubic_connected = relayer.qubic_connected();
+    let status = if solana_connected && qubic_connected {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(serde_json::json!({
+            "solana_connected": solana_connected,
+            "qubic_connected": qubic_connected,
+            "paused": relayer.is_paused(),
+        })),
+    )
+}
+
+/// Prometheus scrape endpoint.
+async fn metrics(State(relayer): State<Arc<BridgeRelayer>>) -> Result<String, (StatusCode, String)> {
+    relayer
+        .render_metrics()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+async fn list_transfers(
+    State(relayer): State<Arc<BridgeRelayer>>,
+    Query(filter): Query<TransferFilter>,
+) -> Json<Vec<TransferRecord>> {
+    Json(relayer.transfers(filter.status).await)
+}
+
+async fn get_transfer(
+    State(relayer): State<Arc<BridgeRelayer>>,
+    Path((direction, id)): Path<(BridgeDirection, u64)>,
+) -> Result<Json<TransferRecord>, StatusCode> {
+    relayer.transfer(direction, id).await.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn retry_transfer(
+    State(relayer): State<Arc<BridgeRelayer>>,
+    Path((direction, id)): Path<(BridgeDirection, u64)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    match relayer.retry_transfer(direction, id).await {
+        Ok(()) => Ok(StatusCode::ACCEPTED),
+        Err(e @ RetryRejected::NotFound(..)) => Err((StatusCode::NOT_FOUND, e.to_string())),
+        Err(e @ RetryRejected::NotDeadLettered(..)) => Err((StatusCode::CONFLICT, e.to_string())),
+    }
+}
+
+async fn pause(State(relayer): State<Arc<BridgeRelayer>>) -> StatusCode {
+    relayer.pause();
+    StatusCode::NO_CONTENT
+}
+
+async fn resume(State(relayer): State<Arc<BridgeRelayer>>) -> StatusCode {
+    relayer.resume();
+    StatusCode::NO_CONTENT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{relayer, serve, temp_path, MockSolana};
+
+    async fn admin(name: &str) -> (Arc<BridgeRelayer>, String) {
+        let solana = MockSolana::default().serve().await;
+        let relayer = Arc::new(relayer(&solana, &temp_path(name)).await);
+        let url = serve(router(relayer.clone())).await;
+        (relayer, url)
+    }
+
+    #[tokio::test]
+    async fn transfers_are_addressed_by_direction_and_id() {
+        let (_, url) = admin("admin-transfers").await;
+        let client = reqwest::Client::new();
+
+        let unknown = client.get(format!("{}/transfers/QubicToSolana/9", url)).send().await.unwrap();
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+        let bad_direction = client.get(format!("{}/transfers/Sideways/9", url)).send().await.unwrap();
+        assert_eq!(bad_direction.status(), StatusCode::BAD_REQUEST);
+
+        let retry = client.post(format!("{}/transfers/SolanaToQubic/9/retry", url)).send().await.unwrap();
+        assert_eq!(retry.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn transfers_can_be_filtered_by_state() {
+        let (_, url) = admin("admin-filter").await;
+        let client = reqwest::Client::new();
+
+        let listed = client.get(format!("{}/transfers?status=dead_lettered", url)).send().await.unwrap();
+        assert_eq!(listed.status(), StatusCode::OK);
+        assert_eq!(listed.json::<Vec<serde_json::Value>>().await.unwrap().len(), 0);
+
+        let bogus = client.get(format!("{}/transfers?status=lost", url)).send().await.unwrap();
+        assert_eq!(bogus.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn operators_can_pause_and_resume_relaying() {
+        let (relayer, url) = admin("admin-pause").await;
+        let client = reqwest::Client::new();
+
+        let paused = client.post(format!("{}/pause", url)).send().await.unwrap();
+        assert_eq!(paused.status(), StatusCode::NO_CONTENT);
+        assert!(relayer.is_paused());
+
+        // Not ready until both chains have been polled, and says why
+        let ready = client.get(format!("{}/ready", url)).send().await.unwrap();
+        assert_eq!(ready.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(ready.json::<serde_json::Value>().await.unwrap()["paused"], true);
+
+        client.post(format!("{}/resume", url)).send().await.unwrap();
+        assert!(!relayer.is_paused());
+        let metrics = client.get(format!("{}/metrics", url)).send().await.unwrap();
+        assert_eq!(metrics.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,203 @@
+// src/relayer/src/anomaly.rs
+// Off-chain mirror of the bridge's circuit breaker. The program trips on what one unlock can
+// see; the relayer watches flows across polls and deliveries, pages operators when they look
+// wrong and, unless told otherwise, stops delivering until someone resumes it.
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+
+/// A webhook that doesn't answer within this long is given up on; the page is still logged.
+const PAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AnomalyConfig {
+    /// Receives a JSON POST for every page. Unset pages to the log only.
+    pub page_webhook_url: Option<String>,
+    /// An inbound unlock above this share of the vault, in basis points. 0 disables.
+    pub max_unlock_bps: u16,
+    /// More deliveries than this within `window_seconds` is anomalous. 0 disables.
+    pub max_deliveries_per_window: u32,
+    pub window_seconds: u64,
+    /// Pause relaying on any anomaly, until an operator resumes it.
+    pub pause_on_anomaly: bool,
+    /// The same anomaly is paged again only after this long.
+    pub repage_after_seconds: u64,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        AnomalyConfig {
+            page_webhook_url: None,
+            max_unlock_bps: 2_000,
+            max_deliveries_per_window: 50,
+            window_seconds: 60,
+            pause_on_anomaly: true,
+            repage_after_seconds: 900,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Anomaly {
+    /// A vault holds less than its mint ledger says the bridge owes.
+    VaultShortfall { mint: String, balance: u64, expected: u64 },
+    /// A delivery would release an outsized share of a vault.
+    LargeUnlock { lock_id: u64, mint: String, amount: u64, vault_balance: u64 },
+    /// Deliveries are going out faster than configured.
+    DeliveryRate { deliveries: usize, window_seconds: u64 },
+    /// The on-chain breaker paused the bridge.
+    CircuitBreakerTripped { reason: String, paused_at: i64 },
+}
+
+impl Anomaly {
+    /// Metric label.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Anomaly::VaultShortfall { .. } => "vault_shortfall",
+            Anomaly::LargeUnlock { .. } => "large_unlock",
+            Anomaly::DeliveryRate { .. } => "delivery_rate",
+            Anomaly::CircuitBreakerTripped { .. } => "circuit_breaker_tripped",
+        }
+    }
+
+    /// Occurrences with the same key are the same incident and are not paged twice in a row.
+    fn incident(&self) -> String {
+        match self {
+            Anomaly::VaultShortfall { mint, .. } => format!("vault_shortfall:{}", mint),
+            Anomaly::LargeUnlock { lock_id, .. } => format!("large_unlock:{}", lock_id),
+            Anomaly::DeliveryRate { .. } => "delivery_rate".to_string(),
+            Anomaly::CircuitBreakerTripped { paused_at, .. } => format!("circuit_breaker:{}", paused_at),
+        }
+    }
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anomaly::VaultShortfall { mint, balance, expected } => {
+                write!(f, "vault for mint {} holds {}, ledger expects {}", mint, balance, expected)
+            }
+            Anomaly::LargeUnlock { lock_id, mint, amount, vault_balance } => write!(
+                f,
+                "lock {} would unlock {} of mint {} from a vault of {}",
+                lock_id, amount, mint, vault_balance
+            ),
+            Anomaly::DeliveryRate { deliveries, window_seconds } => {
+                write!(f, "{} deliveries in the last {}s", deliveries, window_seconds)
+            }
+            Anomaly::CircuitBreakerTripped { reason, paused_at } => {
+                write!(f, "on-chain circuit breaker tripped ({}) at {}", reason, paused_at)
+            }
+        }
+    }
+}
+
+pub struct AnomalyDetector {
+    config: AnomalyConfig,
+    http: reqwest::Client,
+    /// Timestamps of recent deliveries, oldest first.
+    deliveries: Mutex<VecDeque<i64>>,
+    /// When each incident was last paged.
+    paged: Mutex<HashMap<String, i64>>,
+}
+
+impl AnomalyDetector {
+    pub fn new(config: AnomalyConfig) -> Self {
+        AnomalyDetector {
+            config,
+            http: reqwest::Client::new(),
+            deliveries: Mutex::new(VecDeque::new()),
+            paged: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn pauses_on_anomaly(&self) -> bool {
+        self.config.pause_on_anomaly
+    }
+
+    pub fn check_vault(&self, mint: &str, balance: u64, expected: u64) -> Option<Anomaly> {
+        (balance < expected).then(|| Anomaly::VaultShortfall { mint: mint.to_string(), balance, expected })
+    }
+
+    pub fn check_unlock(&self, lock_id: u64, mint: &str, amount: u64, vault_balance: u64) -> Option<Anomaly> {
+        let limit = self.config.max_unlock_bps as u128;
+        (limit > 0 && amount as u128 * 10_000 > vault_balance as u128 * limit).then(|| Anomaly::LargeUnlock {
+            lock_id,
+            mint: mint.to_string(),
+            amount,
+            vault_balance,
+        })
+    }
+
+    /// Count a delivery made at `now` (unix seconds) against the rate limit.
+    pub fn record_delivery(&self, now: i64) -> Option<Anomaly> {
+        if self.config.max_deliveries_per_window == 0 {
+            return None;
+        }
+        let mut deliveries = self.deliveries.lock().expect("delivery window lock");
+        deliveries.push_back(now);
+        let window_start = now.saturating_sub(self.config.window_seconds as i64);
+        while deliveries.front().is_some_and(|&at| at <= window_start) {
+            deliveries.pop_front();
+        }
+        (deliveries.len() > self.config.max_deliveries_per_window as usize).then(|| Anomaly::DeliveryRate {
+            deliveries: deliveries.len(),
+            window_seconds: self.config.window_seconds,
+        })
+    }
+
+    /// The bridge state's pause, if a tripwire rather than a person set it.
+    pub fn check_breaker(&self, is_paused: bool, pause_reason: u8, paused_at: i64) -> Option<Anomaly> {
+        use bridge::state::{pause_reason_name, PAUSE_REASON_MANUAL, PAUSE_REASON_NONE};
+
+        (is_paused && pause_reason != PAUSE_REASON_NONE && pause_reason != PAUSE_REASON_MANUAL).then(|| {
+            Anomaly::CircuitBreakerTripped { reason: pause_reason_name(pause_reason).to_string(), paused_at }
+        })
+    }
+
+    /// Whether `anomaly`, seen at `now`, should page: only the first time, and again once
+    /// `repage_after_seconds` has passed while it persists.
+    pub fn should_page(&self, anomaly: &Anomaly, now: i64) -> bool {
+        let mut paged = self.paged.lock().expect("page history lock");
+        let incident = anomaly.incident();
+        match paged.get(&incident) {
+            Some(&at) if now.saturating_sub(at) < self.config.repage_after_seconds as i64 => false,
+            _ => {
+                paged.insert(incident, now);
+                true
+            }
+        }
+    }
+
+    /// Log the page and post it to the webhook, if one is configured.
+    pub async fn page(&self, bridge_id: u64, anomaly: &Anomaly) {
+        error!("ANOMALY on bridge {}: {}", bridge_id, anomaly);
+
+        let Some(url) = &self.config.page_webhook_url else {
+            return;
+        };
+        let body = serde_json::json!({
+            "bridgeId": bridge_id,
+            "summary": format!("Anomaly on bridge {}: {}", bridge_id, anomaly),
+            "anomaly": anomaly,
+            "at": chrono::Utc::now().timestamp(),
+        });
+        let result = self
+            .http
+            .post(url)
+            .timeout(PAGE_TIMEOUT)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(e) = result {
+            warn!("Could not page {}: {}", url, e);
+        }
+    }
+}
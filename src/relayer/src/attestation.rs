@@ -0,0 +1,179 @@
+// src/relayer/src/attestation.rs
+// Canonical validator attestations and the signed form validators publish to the collector.
+use std::str::FromStr;
+
+use anchor_lang::AccountDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use thiserror::Error;
+
+use bridge::state::BridgeState;
+use shared::types::{BridgeDirection, BridgeTransaction};
+pub use validator::state::{Attestation, AttestationDomain, DIRECTION_QUBIC_TO_SOLANA, DIRECTION_SOLANA_TO_QUBIC};
+
+#[derive(Debug, Error)]
+pub enum AttestationError {
+    #[error("invalid public key: {0}")]
+    InvalidPubkey(String),
+    #[error("invalid signature encoding: {0}")]
+    InvalidSignatureEncoding(String),
+    #[error("signature does not verify for validator {0}")]
+    BadSignature(Pubkey),
+    #[error("bridge was initialized for cluster {expected}, but the RPC serves {actual}")]
+    WrongCluster { expected: String, actual: String },
+}
+
+/// An attestation signed by one validator, as sent over the wire (base58 strings).
+/// The domain is not sent: the receiver verifies against its own, so a signature made
+/// for another deployment never checks out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignedAttestation {
+    pub direction: BridgeDirection,
+    pub lock_id: u64,
+    pub mint: String,
+    pub recipient: String,
+    pub amount: u64,
+    pub validator: String,
+    pub signature: String,
+}
+
+impl SignedAttestation {
+    /// Sign `attestation` with the operator's own key.
+    pub fn sign(keypair: &Keypair, attestation: &Attestation) -> Self {
+        let signature = keypair.sign_message(&attestation.message());
+        SignedAttestation {
+            direction: bridge_direction(attestation.direction),
+            lock_id: attestation.lock_id,
+            mint: attestation.mint.to_string(),
+            recipient: attestation.recipient.to_string(),
+            amount: attestation.amount,
+            validator: keypair.pubkey().to_string(),
+            signature: signature.to_string(),
+        }
+    }
+
+    pub fn attestation(&self, domain: &AttestationDomain) -> Result<Attestation, AttestationError> {
+        Ok(Attestation {
+            domain: *domain,
+            direction: direction_tag(self.direction),
+            lock_id: self.lock_id,
+            mint: parse_pubkey(&self.mint)?,
+            recipient: parse_pubkey(&self.recipient)?,
+            amount: self.amount,
+        })
+    }
+
+    /// Decode and check the signature for `domain`, returning what was signed and by whom.
+    pub fn verify(&self, domain: &AttestationDomain) -> Result<(Attestation, Pubkey, Signature), AttestationError> {
+        let attestation = self.attestation(domain)?;
+        let validator = parse_pubkey(&self.validator)?;
+        let signature = Signature::from_str(&self.signature)
+            .map_err(|_| AttestationError::InvalidSignatureEncoding(self.signature.clone()))?;
+
+        if !signature.verify(validator.as_ref(), &attestation.message()) {
+            return Err(AttestationError::BadSignature(validator));
+        }
+
+        Ok((attestation, validator, signature))
+    }
+}
+
+/// The attestation validators sign for a bridge transaction observed on the source chain.
+/// Qubic identities are 32-byte public keys, so they fit the `recipient` field as-is.
+pub fn attestation_for(tx: &BridgeTransaction, domain: &AttestationDomain) -> Attestation {
+    let recipient = match tx.direction {
+        BridgeDirection::SolanaToQubic => Pubkey::new_from_array(tx.qubic_destination),
+        BridgeDirection::QubicToSolana => tx.solana_destination,
+    };
+
+    Attestation {
+        domain: *domain,
+        direction: direction_tag(tx.direction),
+        lock_id: tx.id,
+        mint: tx.token_mint,
+        recipient,
+        amount: tx.amount,
+    }
+}
+
+/// How attestations encode `direction`.
+pub fn direction_tag(direction: BridgeDirection) -> u8 {
+    match direction {
+        BridgeDirection::SolanaToQubic => DIRECTION_SOLANA_TO_QUBIC,
+        BridgeDirection::QubicToSolana => DIRECTION_QUBIC_TO_SOLANA,
+    }
+}
+
+/// The direction an attestation's tag names. Any tag but Solana -> Qubic reads as inbound.
+fn bridge_direction(tag: u8) -> BridgeDirection {
+    if tag == DIRECTION_SOLANA_TO_QUBIC {
+        BridgeDirection::SolanaToQubic
+    } else {
+        BridgeDirection::QubicToSolana
+    }
+}
+
+/// The deployment the bridge program was initialized for. Fails if the RPC serves a
+/// different cluster, so nothing is ever signed or relayed for the wrong one.
+pub async fn fetch_domain(client: &RpcClient) -> Result<AttestationDomain, anyhow::Error> {
+    let data = client.get_account_data(&bridge::client::bridge_state_address()).await?;
+    let domain = BridgeState::try_deserialize(&mut data.as_slice())?.attestation_domain();
+
+    let genesis_hash = client.get_genesis_hash().await?;
+    if genesis_hash.to_bytes() != domain.genesis_hash {
+        return Err(AttestationError::WrongCluster {
+            expected: Hash::new_from_array(domain.genesis_hash).to_string(),
+            actual: genesis_hash.to_string(),
+        }
+        .into());
+    }
+
+    Ok(domain)
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, AttestationError> {
+    Pubkey::from_str(value).map_err(|_| AttestationError::InvalidPubkey(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::transaction;
+
+    #[test]
+    fn both_legs_of_a_lock_id_are_different_attestations() {
+        let domain = AttestationDomain::default();
+        let outbound = transaction(1, BridgeDirection::SolanaToQubic);
+        let mut inbound = transaction(1, BridgeDirection::QubicToSolana);
+        inbound.token_mint = outbound.token_mint;
+        inbound.solana_destination = Pubkey::new_from_array(outbound.qubic_destination);
+
+        // Same id, mint, recipient and amount: only the direction tells them apart
+        let (outbound, inbound) = (attestation_for(&outbound, &domain), attestation_for(&inbound, &domain));
+        assert_eq!((outbound.lock_id, outbound.mint, outbound.recipient), (inbound.lock_id, inbound.mint, inbound.recipient));
+        assert_ne!(outbound.digest(), inbound.digest());
+    }
+
+    #[test]
+    fn signed_attestations_carry_their_direction() {
+        let domain = AttestationDomain::default();
+        let keypair = Keypair::new();
+        for direction in [BridgeDirection::SolanaToQubic, BridgeDirection::QubicToSolana] {
+            let attestation = attestation_for(&transaction(1, direction), &domain);
+            let signed = SignedAttestation::sign(&keypair, &attestation);
+            assert_eq!(signed.direction, direction);
+            assert_eq!(signed.verify(&domain).unwrap().0, attestation);
+
+            // Relabelled as the other leg, the signature no longer checks out
+            let mut relabelled = signed.clone();
+            relabelled.direction = match direction {
+                BridgeDirection::SolanaToQubic => BridgeDirection::QubicToSolana,
+                BridgeDirection::QubicToSolana => BridgeDirection::SolanaToQubic,
+            };
+            assert!(matches!(relabelled.verify(&domain), Err(AttestationError::BadSignature(_))));
+        }
+    }
+}
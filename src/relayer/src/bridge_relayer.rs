@@ -1,100 +1,499 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+use futures::StreamExt;
 use tokio::sync::Mutex;
 use tokio::time::Duration;
 use serde::{Deserialize, Serialize};
-use reqwest::Client;
+use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
-use solana_client::rpc_client::RpcClient;
-use log::{info, error};
-use anyhow;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use log::{info, warn, error};
 
-use crate::qubic_monitor::QubicMonitor;
+use crate::admin_api;
+use crate::anomaly::{Anomaly, AnomalyConfig, AnomalyDetector};
+use crate::attestation::{attestation_for, fetch_domain, AttestationDomain};
+use crate::finality::{Finality, FinalityConfig, FinalityTracker};
+use crate::metrics::RelayerMetrics;
+use crate::qubic_monitor::{QubicCursor, QubicMonitor};
+use crate::qubic_tx::{read_seed_file, QubicBroadcaster, QubicTxConfig};
+use crate::retry_scheduler::{DeadLetter, FailureOutcome, RetryPolicy, RetryScheduler};
+use crate::rpc_pool::{QubicEndpoints, SolanaEndpoints};
+use crate::signature_collector::SignatureCollector;
 use crate::solana_monitor::SolanaMonitor;
-use shared::types::BridgeTransaction;
+use crate::solana_rpc::{SolanaRpc, SolanaRpcConfig};
+use crate::supervisor::{shutdown_requested, spawn_supervised, Shutdown};
+use bridge::state::BRIDGE_STATUS_COMPLETED;
+use shared::qubic::hm25::{UnlockAssetsInput, PROCEDURE_UNLOCK_ASSETS};
+use shared::qubic::{contract_id, QpiStruct, HM25_CONTRACT_INDEX};
+use shared::types::{BridgeDirection, BridgeTransaction};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeConfig {
-    pub solana_rpc_url: String,
-    pub qubic_rpc_url: String,
+    /// Tried in order of health; at least one.
+    pub solana_rpc_urls: Vec<String>,
+    /// Endpoints that must agree on a lock before it is delivered, if set.
+    pub solana_quorum: Option<usize>,
+    /// Stream locks over this WebSocket endpoint; `None` polls instead.
+    pub solana_ws_url: Option<String>,
+    pub solana_backfill_interval_seconds: u64,
+    pub qubic_rpc_urls: Vec<String>,
+    pub qubic_quorum: Option<usize>,
+    /// The Solana mint Qubic locks are released as.
+    pub qubic_mint: Pubkey,
+    /// The Qubic identity `UnlockAssets` is sent from, and how its transactions are aimed.
+    pub qubic_tx: QubicTxConfig,
     pub bridge_program_id: Pubkey,
-    pub validator_private_key: String,
-    pub confirmation_threshold: u64,
+    pub keypair_path: String,
+    pub finality: FinalityConfig,
+    /// Off-chain tripwires and where to page when one fires.
+    pub anomaly: AnomalyConfig,
+    pub solana_rpc: SolanaRpcConfig,
     pub max_retry_attempts: u32,
+    pub max_concurrent_submissions: usize,
     pub retry_delay_seconds: u64,
+    pub collector_listen_addr: String,
+    pub admin_listen_addr: String,
+    pub checkpoint_path: String,
+}
+
+/// How often the validator set and threshold are reloaded, so joins, unbonds and slashes apply.
+const VALIDATOR_SET_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Vault balances are sampled on every Nth successful Solana poll.
+const VAULT_SAMPLE_EVERY_POLLS: u64 = 12;
+
+/// Solana poll interval, in polling mode and while a dropped subscription is down.
+const SOLANA_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to fall back to polling before trying to resubscribe.
+const RESUBSCRIBE_AFTER: Duration = Duration::from_secs(30);
+
+/// A chain counts as connected while its last successful poll is at most this old.
+pub const POLL_STALE_AFTER_SECS: i64 = 60;
+
+/// Where a transfer is in the relayer's pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferState {
+    AwaitingFinality,
+    Queued,
+    Delivered,
+    DeadLettered,
+    Invalidated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferRecord {
+    pub transaction: BridgeTransaction,
+    pub state: TransferState,
+    pub last_error: Option<String>,
+    pub updated_at: i64,
+}
+
+/// Unfinished work written on shutdown and picked up again on the next start.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    transfers: Vec<TransferRecord>,
+    dead_letters: Vec<DeadLetter>,
+    /// Where the Qubic monitor resumes, so locks made while the relayer was down are still seen.
+    #[serde(default)]
+    qubic_cursor: Option<QubicCursor>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RetryRejected {
+    #[error("{0:?} transfer {1} is unknown")]
+    NotFound(BridgeDirection, u64),
+    #[error("{0:?} transfer {1} is {2:?}, only dead-lettered transfers can be retried")]
+    NotDeadLettered(BridgeDirection, u64, TransferState),
 }
 
 pub struct BridgeRelayer {
     config: BridgeConfig,
+    keypair: Keypair,
+    /// The deployment every attestation this relayer collects or submits is bound to.
+    domain: AttestationDomain,
     solana_monitor: SolanaMonitor,
     qubic_monitor: QubicMonitor,
-    pending_transactions: Mutex<HashMap<u64, BridgeTransaction>>,
-    http_client: Client,
-    solana_client: RpcClient,
+    pending_transactions: Mutex<HashMap<(BridgeDirection, u64), BridgeTransaction>>,
+    qubic: QubicEndpoints,
+    /// `None` without a Qubic seed; deliveries to Qubic then fail until one is configured.
+    qubic_broadcaster: Option<QubicBroadcaster>,
+    /// Held across each `UnlockAssets` call, so this relayer's own unlocks never overlap in
+    /// HM25's unlock total.
+    qubic_unlocks: Mutex<()>,
+    solana_rpc: SolanaRpc,
+    signature_collector: Arc<SignatureCollector>,
+    retry_scheduler: RetryScheduler,
+    finality_tracker: FinalityTracker,
+    transfers: Mutex<HashMap<(BridgeDirection, u64), TransferRecord>>,
+    last_solana_poll: AtomicI64,
+    last_qubic_poll: AtomicI64,
+    paused: AtomicBool,
+    anomalies: AnomalyDetector,
+    metrics: RelayerMetrics,
 }
 
 impl BridgeRelayer {
     pub async fn new(config: BridgeConfig) -> Result<Self, anyhow::Error> {
-        let solana_client = RpcClient::new(config.solana_rpc_url.clone());
+        let keypair = read_keypair_file(&config.keypair_path)
+            .map_err(|e| anyhow::anyhow!("could not load keypair from {}: {}", config.keypair_path, e))?;
+        // One nonblocking failover client with request timeouts, shared by everything that reads Solana
+        let solana_endpoints = SolanaEndpoints::new(
+            &config.solana_rpc_urls,
+            config.solana_rpc.timeout(),
+            config.solana_quorum,
+        );
+        let solana_rpc = SolanaRpc::new(solana_endpoints.clone(), config.solana_rpc.clone());
+        let qubic = QubicEndpoints::new(&config.qubic_rpc_urls, config.qubic_quorum);
         let solana_monitor = SolanaMonitor::new(
-            &config.solana_rpc_url,
+            solana_rpc.client(),
             config.bridge_program_id,
         );
-        let qubic_monitor = QubicMonitor::new(Duration::from_secs(10));
+        let qubic_monitor = QubicMonitor::new(qubic.clone(), config.qubic_mint);
+        let qubic_broadcaster = match &config.qubic_tx.seed_path {
+            Some(path) => {
+                let keypair = read_seed_file(path)?;
+                info!("Delivering to Qubic as {}", keypair.identity());
+                Some(QubicBroadcaster::new(qubic.clone(), keypair, config.qubic_tx.clone()))
+            }
+            None => {
+                warn!("No Qubic seed configured; deliveries to Qubic will fail");
+                None
+            }
+        };
+
+        // Refuses to start against a cluster the bridge wasn't initialized for
+        let domain = fetch_domain(&solana_rpc.client())
+            .await
+            .map_err(|e| anyhow::anyhow!("could not load the bridge deployment identity: {}", e))?;
+        info!(
+            "Relaying for bridge {} (Qubic network {}) on cluster {}",
+            domain.bridge_id,
+            domain.qubic_network_id,
+            solana_sdk::hash::Hash::new_from_array(domain.genesis_hash)
+        );
+
+        // Validators sign independently and publish to this collector; the
+        // relayer itself holds no validator keys. Without a validator set nothing could ever
+        // reach threshold, so that is fatal too
+        let signature_collector = Arc::new(SignatureCollector::new(domain, Vec::new(), 0));
+        signature_collector
+            .refresh_from_chain(&solana_rpc.client())
+            .await
+            .map_err(|e| anyhow::anyhow!("could not load the validator set: {}", e))?;
+
+        let retry_scheduler = RetryScheduler::new(RetryPolicy::new(
+            config.max_retry_attempts,
+            Duration::from_secs(config.retry_delay_seconds),
+        ));
+        let finality_tracker = FinalityTracker::new(
+            config.finality.clone(),
+            solana_endpoints,
+            qubic.clone(),
+        );
+        let anomalies = AnomalyDetector::new(config.anomaly.clone());
         
         Ok(BridgeRelayer {
             config,
+            keypair,
+            domain,
             solana_monitor,
             qubic_monitor,
             pending_transactions: Mutex::new(HashMap::new()),
-            http_client: Client::new(),
-            solana_client,
+            qubic,
+            qubic_broadcaster,
+            qubic_unlocks: Mutex::new(()),
+            solana_rpc,
+            signature_collector,
+            retry_scheduler,
+            finality_tracker,
+            transfers: Mutex::new(HashMap::new()),
+            last_solana_poll: AtomicI64::new(0),
+            last_qubic_poll: AtomicI64::new(0),
+            paused: AtomicBool::new(false),
+            anomalies,
+            metrics: RelayerMetrics::new()?,
         })
     }
 
-    pub async fn start(&self) -> Result<(), anyhow::Error> {
-        info!("Starting Bridge Relayer...");
+    /// Run until `shutdown` fires, then let in-flight deliveries finish and checkpoint
+    /// whatever is still unfinished.
+    pub async fn start(self: Arc<Self>, shutdown: Shutdown) -> Result<(), anyhow::Error> {
+        info!("Starting Bridge Relayer as {}...", self.keypair.pubkey());
 
-        // Start monitoring tasks
-        let solana_task = self.start_solana_monitoring();
-        let qubic_task = self.start_qubic_monitoring();
-        let processing_task = self.start_transaction_processing();
+        if let Err(e) = self.restore_checkpoint().await {
+            error!("Could not restore checkpoint from {}: {}", self.config.checkpoint_path, e);
+        }
 
-        // Wait for all tasks to complete
-        tokio::try_join!(solana_task, qubic_task, processing_task)?;
+        // Each task is restarted with backoff if it fails; none can take the others down
+        let relayer = self.clone();
+        let solana_task = spawn_supervised("solana_monitor", shutdown.clone(), move |shutdown| {
+            let relayer = relayer.clone();
+            async move { relayer.start_solana_monitoring(shutdown).await }
+        });
+        let relayer = self.clone();
+        let qubic_task = spawn_supervised("qubic_monitor", shutdown.clone(), move |shutdown| {
+            let relayer = relayer.clone();
+            async move { relayer.start_qubic_monitoring(shutdown).await }
+        });
+        let relayer = self.clone();
+        let processing_task = spawn_supervised("transaction_processing", shutdown.clone(), move |shutdown| {
+            let relayer = relayer.clone();
+            async move { relayer.start_transaction_processing(shutdown).await }
+        });
+        let relayer = self.clone();
+        let collector_task = spawn_supervised("signature_collector", shutdown.clone(), move |shutdown| {
+            let relayer = relayer.clone();
+            async move { relayer.start_signature_collector(shutdown).await }
+        });
+        let relayer = self.clone();
+        let validator_set_task = spawn_supervised("validator_set", shutdown.clone(), move |shutdown| {
+            let relayer = relayer.clone();
+            async move { relayer.refresh_validator_set(shutdown).await }
+        });
+        let relayer = self.clone();
+        let admin_task = spawn_supervised("admin_api", shutdown.clone(), move |shutdown| {
+            let relayer = relayer.clone();
+            async move { relayer.start_admin_api(shutdown).await }
+        });
 
+        // Supervisors only return once shutdown was requested and their task stopped
+        for task in [solana_task, qubic_task, processing_task, collector_task, validator_set_task, admin_task] {
+            task.await?;
+        }
+
+        self.write_checkpoint().await?;
+        info!("Bridge Relayer stopped");
         Ok(())
     }
 
-    async fn start_solana_monitoring(&self) -> Result<(), anyhow::Error> {
-        let mut interval = tokio::time::interval(Duration::from_secs(5));
+    async fn write_checkpoint(&self) -> Result<(), anyhow::Error> {
+        let transfers: Vec<TransferRecord> = self
+            .transfers(None)
+            .await
+            .into_iter()
+            .filter(|record| {
+                matches!(
+                    record.state,
+                    TransferState::AwaitingFinality | TransferState::Queued | TransferState::DeadLettered
+                )
+            })
+            .collect();
+        let checkpoint = Checkpoint {
+            transfers,
+            dead_letters: self.retry_scheduler.dead_letters().await,
+            qubic_cursor: self.qubic_monitor.cursor(),
+        };
+
+        // Write then rename, so a kill mid-write never leaves a truncated checkpoint
+        let temp_path = format!("{}.tmp", self.config.checkpoint_path);
+        std::fs::write(&temp_path, serde_json::to_vec_pretty(&checkpoint)?)?;
+        std::fs::rename(&temp_path, &self.config.checkpoint_path)?;
+
+        info!(
+            "Checkpointed {} unfinished transfer(s) to {}",
+            checkpoint.transfers.len(),
+            self.config.checkpoint_path
+        );
+        Ok(())
+    }
+
+    async fn restore_checkpoint(&self) -> Result<(), anyhow::Error> {
+        let contents = match std::fs::read(&self.config.checkpoint_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let checkpoint: Checkpoint = serde_json::from_slice(&contents)?;
+        let restored = checkpoint.transfers.len();
+
+        if let Some(cursor) = checkpoint.qubic_cursor {
+            self.qubic_monitor.restore(cursor);
+        }
+        for dead in checkpoint.dead_letters {
+            self.retry_scheduler.restore_dead_letter(dead).await;
+        }
+        for record in checkpoint.transfers {
+            let tx = record.transaction.clone();
+            match record.state {
+                TransferState::AwaitingFinality => {
+                    self.pending_transactions.lock().await.insert(tx.key(), tx);
+                }
+                TransferState::Queued => self.retry_scheduler.schedule(tx).await,
+                _ => {}
+            }
+            self.transfers.lock().await.insert(record.transaction.key(), record);
+        }
+
+        // Consumed: a later crash must not replay transfers that were delivered since
+        std::fs::remove_file(&self.config.checkpoint_path)?;
+        info!("Restored {} unfinished transfer(s) from {}", restored, self.config.checkpoint_path);
+        Ok(())
+    }
+
+    async fn start_admin_api(self: &Arc<Self>, mut shutdown: Shutdown) -> Result<(), anyhow::Error> {
+        let addr = self.config.admin_listen_addr.parse()?;
+        info!("Admin API listening on {}", addr);
+
+        axum::Server::bind(&addr)
+            .serve(admin_api::router(self.clone()).into_make_service())
+            .with_graceful_shutdown(async move { shutdown_requested(&mut shutdown).await })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn start_signature_collector(&self, mut shutdown: Shutdown) -> Result<(), anyhow::Error> {
+        let addr = self.config.collector_listen_addr.parse()?;
+        info!("Signature collector listening on {}", addr);
+
+        axum::Server::bind(&addr)
+            .serve(self.signature_collector.clone().router().into_make_service())
+            .with_graceful_shutdown(async move { shutdown_requested(&mut shutdown).await })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Reload the validator set on an interval. A failed reload fails the task, so the
+    /// supervisor retries it with backoff while the last good set stays in use.
+    async fn refresh_validator_set(&self, mut shutdown: Shutdown) -> Result<(), anyhow::Error> {
+        let mut interval = tokio::time::interval(VALIDATOR_SET_REFRESH_INTERVAL);
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_requested(&mut shutdown) => return Ok(()),
+            }
+            self.signature_collector.refresh_from_chain(&self.solana_rpc.client()).await?;
+        }
+    }
+
+    async fn start_solana_monitoring(&self, shutdown: Shutdown) -> Result<(), anyhow::Error> {
+        match &self.config.solana_ws_url {
+            Some(ws_url) => self.stream_solana_locks(ws_url, shutdown).await,
+            None => self.poll_solana_locks(shutdown).await,
+        }
+    }
+
+    async fn poll_solana_locks(&self, mut shutdown: Shutdown) -> Result<(), anyhow::Error> {
+        let mut interval = tokio::time::interval(SOLANA_POLL_INTERVAL);
+        let mut polls: u64 = 0;
         
         loop {
-            interval.tick().await;
-            
-            match self.solana_monitor.get_pending_transactions().await {
-                Ok(transactions) => {
-                    for tx in transactions {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_requested(&mut shutdown) => return Ok(()),
+            }
+            self.poll_solana(&mut polls).await;
+        }
+    }
+
+    /// Subscription mode: locks arrive as soon as their account is written, and a periodic
+    /// backfill scan picks up anything the stream missed. While the subscription is down the
+    /// monitor polls at the normal rate; the lock id dedupe makes overlaps harmless.
+    async fn stream_solana_locks(&self, ws_url: &str, mut shutdown: Shutdown) -> Result<(), anyhow::Error> {
+        let backfill_interval = Duration::from_secs(self.config.solana_backfill_interval_seconds);
+        let mut polls: u64 = 0;
+
+        loop {
+            let (sender, mut streamed) = tokio::sync::mpsc::unbounded_channel();
+            let subscription = self.solana_monitor.subscribe(ws_url, sender);
+            tokio::pin!(subscription);
+
+            // The first tick fires immediately, covering the gap before (re)subscribing
+            let mut backfill = tokio::time::interval(backfill_interval);
+            loop {
+                tokio::select! {
+                    result = &mut subscription => {
+                        match result {
+                            Ok(()) => warn!("Solana subscription closed, polling until it is restored"),
+                            Err(e) => warn!("Solana subscription failed: {}, polling until it is restored", e),
+                        }
+                        break;
+                    }
+                    Some(tx) = streamed.recv() => {
+                        self.last_solana_poll.store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
                         self.handle_solana_transaction(tx).await;
                     }
+                    _ = backfill.tick() => self.poll_solana(&mut polls).await,
+                    _ = shutdown_requested(&mut shutdown) => return Ok(()),
                 }
-                Err(e) => {
-                    error!("Error monitoring Solana transactions: {}", e);
+            }
+
+            let resubscribe = tokio::time::sleep(RESUBSCRIBE_AFTER);
+            tokio::pin!(resubscribe);
+            let mut interval = tokio::time::interval(SOLANA_POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = &mut resubscribe => break,
+                    _ = interval.tick() => self.poll_solana(&mut polls).await,
+                    _ = shutdown_requested(&mut shutdown) => return Ok(()),
+                }
+            }
+        }
+    }
+
+    /// One scan for pending locks, plus chain heights and the periodic vault sample.
+    async fn poll_solana(&self, polls: &mut u64) {
+        let result = self.solana_monitor.get_pending_transactions().await;
+        self.metrics.rpc_result("solana", &result);
+        match result {
+            Ok(transactions) => {
+                self.last_solana_poll.store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
+                for tx in transactions {
+                    self.handle_solana_transaction(tx).await;
                 }
+
+                let heights = self.solana_monitor.chain_heights().await;
+                self.metrics.rpc_result("solana", &heights);
+                if let Ok((seen, head)) = heights {
+                    self.metrics.chain_heights("solana", seen, head);
+                }
+
+                if polls.is_multiple_of(VAULT_SAMPLE_EVERY_POLLS) {
+                    let sampled = self.sample_vault_balances().await;
+                    self.metrics.rpc_result("solana", &sampled);
+                    if let Err(e) = sampled {
+                        error!("Error sampling vault balances: {}", e);
+                    }
+                }
+                *polls += 1;
+            }
+            Err(e) => {
+                error!("Error monitoring Solana transactions: {}", e);
             }
         }
     }
 
-    async fn start_qubic_monitoring(&self) -> Result<(), anyhow::Error> {
+    async fn start_qubic_monitoring(&self, mut shutdown: Shutdown) -> Result<(), anyhow::Error> {
         let mut interval = tokio::time::interval(Duration::from_secs(5));
         
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_requested(&mut shutdown) => return Ok(()),
+            }
             
-            match self.qubic_monitor.get_pending_transactions().await {
+            let result = self.qubic_monitor.get_pending_transactions().await;
+            self.metrics.rpc_result("qubic", &result);
+            match result {
                 Ok(transactions) => {
+                    self.last_qubic_poll.store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
                     for tx in transactions {
                         self.handle_qubic_transaction(tx).await;
                     }
+
+                    let heights = self.qubic_monitor.chain_heights().await;
+                    self.metrics.rpc_result("qubic", &heights);
+                    if let Ok((seen, head)) = heights {
+                        self.metrics.chain_heights("qubic", seen, head);
+                    }
                 }
                 Err(e) => {
                     error!("Error monitoring Qubic transactions: {}", e);
@@ -103,144 +502,468 @@ impl BridgeRelayer {
         }
     }
 
-    async fn start_transaction_processing(&self) -> Result<(), anyhow::Error> {
+    /// Vault token balances next to what the ledgers say they should be, per mint.
+    async fn sample_vault_balances(&self) -> Result<(), anyhow::Error> {
+        use anchor_lang::{AccountDeserialize, Discriminator};
+        use bridge::state::{BridgeState, MintLedger};
+
+        let bridge_state_address = bridge::client::bridge_state_address();
+        let client = self.solana_rpc.client();
+        let data = client.get_account_data(&bridge_state_address).await?;
+        let bridge_state = BridgeState::try_deserialize(&mut data.as_slice())?;
+        self.metrics.total_locked_tokens(bridge_state.total_locked_tokens);
+        let breaker = self.anomalies.check_breaker(
+            bridge_state.is_paused,
+            bridge_state.pause_reason,
+            bridge_state.emergency_pause_timestamp,
+        );
+        if let Some(anomaly) = breaker {
+            self.raise(anomaly).await;
+        }
+
+        let filter = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &MintLedger::DISCRIMINATOR));
+        let ledgers = client
+            .get_program_accounts_with_config(
+                &self.config.bridge_program_id,
+                RpcProgramAccountsConfig {
+                    filters: Some(vec![filter]),
+                    account_config: RpcAccountInfoConfig::default(),
+                    ..RpcProgramAccountsConfig::default()
+                },
+            )
+            .await?;
+
+        for (_, account) in ledgers {
+            let ledger = MintLedger::try_deserialize(&mut account.data.as_slice())?;
+            // Only the canonical vault backs the ledger; other token accounts the bridge state
+            // owns hold nothing it owes
+            let vault = bridge::client::vault_address(&ledger.mint);
+            let balance: u64 = client.get_token_account_balance(&vault).await?.amount.parse()?;
+
+            let mint = ledger.mint.to_string();
+            let expected = ledger.expected_vault_balance()?;
+            self.metrics.vault(&mint, balance, expected);
+            if let Some(anomaly) = self.anomalies.check_vault(&mint, balance, expected) {
+                self.raise(anomaly).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn start_transaction_processing(&self, mut shutdown: Shutdown) -> Result<(), anyhow::Error> {
         let mut interval = tokio::time::interval(Duration::from_secs(10));
         
         loop {
-            interval.tick().await;
+            // Shutdown is only observed between rounds, so a delivery in flight always completes
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_requested(&mut shutdown) => return Ok(()),
+            }
+            self.deliver_due_transactions().await;
             self.process_pending_transactions().await;
         }
     }
 
-    async fn handle_solana_transaction(&self, tx: BridgeTransaction) {
-        info!("Processing Solana transaction: {:?}", tx.id);
-        
-        // Add to pending transactions
-        {
-            let mut pending = self.pending_transactions.lock().await;
-            pending.insert(tx.id, tx.clone());
+    async fn deliver_due_transactions(&self) {
+        // Paused: jobs stay queued and finality tracking carries on
+        if self.is_paused() {
+            return;
+        }
+
+        // Deliveries for different locks are independent, so a slow one doesn't hold up the rest
+        let due = self.retry_scheduler.take_due().await;
+        futures::stream::iter(due)
+            .for_each_concurrent(self.config.max_concurrent_submissions, |tx| self.deliver(tx))
+            .await;
+    }
+
+    async fn deliver(&self, mut tx: BridgeTransaction) {
+        let result = match tx.direction {
+            BridgeDirection::SolanaToQubic => self.submit_to_qubic(&mut tx).await,
+            BridgeDirection::QubicToSolana => self.submit_to_solana(tx.clone()).await,
+        };
+        let destination = match tx.direction {
+            BridgeDirection::SolanaToQubic => "qubic",
+            BridgeDirection::QubicToSolana => "solana",
+        };
+        self.metrics.rpc_result(destination, &result);
+
+        match result {
+            Ok(()) => {
+                let now = chrono::Utc::now().timestamp();
+                self.metrics.transfer_latency(&tx.direction, now.saturating_sub(tx.created_at) as f64);
+                self.set_transfer_state(&tx, TransferState::Delivered, None).await;
+                if let Some(anomaly) = self.anomalies.record_delivery(now) {
+                    self.raise(anomaly).await;
+                }
+            }
+            Err(e) => {
+                let error = e.to_string();
+                let outcome = self.retry_scheduler.record_failure(tx.clone(), e).await;
+                let dead_lettered = matches!(outcome, FailureOutcome::DeadLettered(_));
+                self.metrics.retry(&tx.direction, dead_lettered);
+                let state = if dead_lettered {
+                    TransferState::DeadLettered
+                } else {
+                    TransferState::Queued
+                };
+                self.set_transfer_state(&tx, state, Some(error)).await;
+            }
         }
+    }
 
-        // Submit to Qubic
-        if let Err(e) = self.submit_to_qubic(tx).await {
-            error!("Failed to submit transaction to Qubic: {}", e);
+    async fn set_transfer_state(&self, tx: &BridgeTransaction, state: TransferState, last_error: Option<String>) {
+        let mut transfers = self.transfers.lock().await;
+        let record = transfers.entry(tx.key()).or_insert_with(|| TransferRecord {
+            transaction: tx.clone(),
+            state,
+            last_error: None,
+            updated_at: 0,
+        });
+
+        if record.updated_at == 0 || record.state != state {
+            self.metrics.transfer_state(&tx.direction, state);
         }
+        record.transaction = tx.clone();
+        record.state = state;
+        if last_error.is_some() {
+            record.last_error = last_error;
+        }
+        record.updated_at = chrono::Utc::now().timestamp();
+    }
+
+    /// Every transfer the relayer has seen, optionally only those in one state.
+    pub async fn transfers(&self, state: Option<TransferState>) -> Vec<TransferRecord> {
+        let transfers = self.transfers.lock().await;
+        let mut records: Vec<_> = transfers
+            .values()
+            .filter(|record| state.is_none_or(|state| record.state == state))
+            .cloned()
+            .collect();
+        records.sort_by_key(|record| (record.transaction.direction as u8, record.transaction.id));
+        records
+    }
+
+    pub async fn transfer(&self, direction: BridgeDirection, id: u64) -> Option<TransferRecord> {
+        self.transfers.lock().await.get(&(direction, id)).cloned()
+    }
+
+    /// Requeue a dead-lettered transfer with a fresh attempt budget.
+    pub async fn retry_transfer(&self, direction: BridgeDirection, id: u64) -> Result<(), RetryRejected> {
+        let state = self
+            .transfer(direction, id)
+            .await
+            .ok_or(RetryRejected::NotFound(direction, id))?
+            .state;
+        if state != TransferState::DeadLettered || !self.requeue_dead_letter(direction, id).await {
+            return Err(RetryRejected::NotDeadLettered(direction, id, state));
+        }
+
+        if let Some(record) = self.transfers.lock().await.get_mut(&(direction, id)) {
+            record.state = TransferState::Queued;
+            record.transaction.retry_count = 0;
+            self.metrics.transfer_state(&record.transaction.direction, TransferState::Queued);
+            record.updated_at = chrono::Utc::now().timestamp();
+        }
+        info!("{:?} transfer {} requeued by operator", direction, id);
+        Ok(())
+    }
+
+    /// Stop submitting deliveries. Observation and finality tracking keep running.
+    pub fn pause(&self) {
+        warn!("Relaying paused by operator");
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Page `anomaly`, at most once per incident and cooldown, then pause relaying unless
+    /// configured not to.
+    async fn raise(&self, anomaly: Anomaly) {
+        if !self.anomalies.should_page(&anomaly, chrono::Utc::now().timestamp()) {
+            return;
+        }
+        self.metrics.anomaly(anomaly.kind());
+        self.anomalies.page(self.domain.bridge_id, &anomaly).await;
+        if self.anomalies.pauses_on_anomaly() && !self.paused.swap(true, Ordering::SeqCst) {
+            warn!("Relaying paused on anomaly: {}", anomaly);
+        }
+    }
+
+    pub fn resume(&self) {
+        info!("Relaying resumed by operator");
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Current metrics in Prometheus text format.
+    pub fn render_metrics(&self) -> Result<String, prometheus::Error> {
+        self.metrics.render()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn solana_connected(&self) -> bool {
+        poll_is_fresh(&self.last_solana_poll)
+    }
+
+    pub fn qubic_connected(&self) -> bool {
+        poll_is_fresh(&self.last_qubic_poll)
+    }
+
+    /// Deliveries that failed fatally or exhausted their retries.
+    pub async fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.retry_scheduler.dead_letters().await
+    }
+
+    /// Give a dead-lettered delivery a fresh set of attempts.
+    pub async fn requeue_dead_letter(&self, direction: BridgeDirection, id: u64) -> bool {
+        self.retry_scheduler.requeue(direction, id).await
+    }
+
+    async fn handle_solana_transaction(&self, tx: BridgeTransaction) {
+        // Monitors report a lock on every poll; only the first sighting starts a transfer
+        if self.transfers.lock().await.contains_key(&tx.key()) {
+            return;
+        }
+        info!("Processing Solana transaction: {:?}", tx.id);
+        
+        // Held until the source transaction is final, then handed to the retry scheduler
+        self.set_transfer_state(&tx, TransferState::AwaitingFinality, None).await;
+        let mut pending = self.pending_transactions.lock().await;
+        pending.entry(tx.key()).or_insert(tx);
     }
 
     async fn handle_qubic_transaction(&self, tx: BridgeTransaction) {
+        // Monitors report a lock on every poll; only the first sighting starts a transfer
+        if self.transfers.lock().await.contains_key(&tx.key()) {
+            return;
+        }
         info!("Processing Qubic transaction: {:?}", tx.id);
         
-        // Add to pending transactions
-        {
-            let mut pending = self.pending_transactions.lock().await;
-            pending.insert(tx.id, tx.clone());
+        // Held until the source transaction is final, then handed to the retry scheduler
+        self.set_transfer_state(&tx, TransferState::AwaitingFinality, None).await;
+        let mut pending = self.pending_transactions.lock().await;
+        pending.entry(tx.key()).or_insert(tx);
+    }
+
+    /// Release `tx` on Qubic and record the delivery on Solana. Once `UnlockAssets` is included
+    /// its digest is kept in `tx`, so a retry that finds it only finishes the Solana side: a
+    /// rebroadcast would be turned down as already processed and look like a failed unlock.
+    async fn submit_to_qubic(&self, tx: &mut BridgeTransaction) -> Result<(), anyhow::Error> {
+        let attestation = attestation_for(tx, &self.domain);
+        let broadcaster = self.qubic_broadcaster
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no Qubic seed configured (qubic.seedPath)"))?;
+
+        // A retry after the delivery was recorded only has to finish completing the lock
+        let lock = self.solana_lock(tx.id).await?;
+        if lock.is_completed() {
+            self.signature_collector.forget(&attestation).await;
+            return Ok(());
+        }
+        if lock.qubic_tx_hash != [0; 32] {
+            return self.complete_lock(tx, None).await;
+        }
+        if let Some(digest) = tx.qubic_tx_hash {
+            self.complete_lock(tx, Some(digest)).await?;
+            self.signature_collector.forget(&attestation).await;
+            return Ok(());
         }
 
-        // Submit to Solana
-        if let Err(e) = self.submit_to_solana(tx).await {
-            error!("Failed to submit transaction to Solana: {}", e);
+        // Send UnlockAssets and follow it until its tick includes it
+        let input = self.create_qubic_unlock_input(tx).await?;
+        let _unlocking = self.qubic_unlocks.lock().await;
+        let unlocked_before = self.qubic_monitor.bridge_info().await?.total_unlocked;
+        let broadcast = broadcaster
+            .submit(contract_id(HM25_CONTRACT_INDEX), 0, PROCEDURE_UNLOCK_ASSETS, &input.encode())
+            .await?;
+        tx.qubic_tx_hash = Some(broadcast.digest);
+
+        // Inclusion only means the call ran: HM25 turns down an unlock (too few signers,
+        // inactive, already processed) by clearing `success`, and nodes don't serve procedure
+        // outputs. Its unlock total is what shows the release happened; other unlocks may land
+        // in the same ticks, so it has to have grown by at least this one
+        let unlocked_after = self.qubic_monitor.bridge_info().await?.total_unlocked;
+        if unlocked_after.saturating_sub(unlocked_before) < tx.amount {
+            tx.qubic_tx_hash = None;
+            return Err(anyhow::anyhow!(
+                "HM25 did not unlock lock {}: {} was included in tick {} but the unlocked total went from {} to {}",
+                tx.id,
+                broadcast.id,
+                broadcast.tick,
+                unlocked_before,
+                unlocked_after
+            ));
         }
+
+        info!("Successfully submitted transaction to Qubic: {} in tick {}", broadcast.id, broadcast.tick);
+        self.complete_lock(tx, Some(broadcast.digest)).await?;
+        self.signature_collector.forget(&attestation).await;
+
+        Ok(())
     }
 
-    async fn submit_to_qubic(&self, tx: BridgeTransaction) -> Result<(), anyhow::Error> {
-        // Create Qubic transaction
-        let qubic_tx = self.create_qubic_transaction(tx).await?;
-        
-        // Submit to Qubic network
-        let response = self.http_client
-            .post(&format!("{}/api/v1/transactions", self.config.qubic_rpc_url))
-            .json(&qubic_tx)
-            .send()
-            .await?;
+    /// The outbound lock account for `lock_id` as quantum_link stores it.
+    async fn solana_lock(&self, lock_id: u64) -> Result<bridge::state::BridgeTransaction, anyhow::Error> {
+        use anchor_lang::AccountDeserialize;
 
-        if response.status().is_success() {
-            info!("Successfully submitted transaction to Qubic");
-        } else {
-            error!("Failed to submit transaction to Qubic: {}", response.status());
+        let address = bridge::client::bridge_transaction_address(lock_id);
+        let data = self.solana_rpc.client().get_account_data(&address).await?;
+        Ok(bridge::state::BridgeTransaction::try_deserialize(&mut data.as_slice())?)
+    }
+
+    /// Mark `tx` completed on Solana so it can no longer be refunded and becomes closable,
+    /// first recording Qubic transaction `delivery` against it unless that is already done.
+    async fn complete_lock(&self, tx: &BridgeTransaction, delivery: Option<[u8; 32]>) -> Result<(), anyhow::Error> {
+        let validator = self.keypair.pubkey();
+        let mut instructions = Vec::new();
+        if let Some(digest) = delivery {
+            let attestation = attestation_for(tx, &self.domain);
+            instructions.push(bridge::client::validate_bridge(&validator, &attestation, digest));
         }
+        instructions.push(bridge::client::update_bridge_state(
+            &validator,
+            tx.id,
+            BRIDGE_STATUS_COMPLETED,
+            tx.confirmations,
+        ));
 
+        let signature = self.solana_rpc.send_instructions(instructions, &self.keypair).await?;
+        info!("Lock {} marked completed on Solana: {}", tx.id, signature);
         Ok(())
     }
 
     async fn submit_to_solana(&self, tx: BridgeTransaction) -> Result<(), anyhow::Error> {
-        // Create Solana transaction
-        let solana_tx = self.create_solana_transaction(tx).await?;
+        use anchor_lang::AccountDeserialize;
+
+        // Create the unlock instruction
+        let unlock = self.create_unlock_instruction(&tx).await?;
         
-        // Submit to Solana network
-        let signature = self.solana_client.send_and_confirm_transaction(&solana_tx)?;
+        // Submit to Solana network, with the cached blockhash and any configured priority fee
+        let signature = self.solana_rpc.send_instructions(vec![unlock], &self.keypair).await?;
+
+        // A tripped breaker lands the unlock without releasing anything; keep the job queued
+        let data = self.solana_rpc.client().get_account_data(&bridge::client::bridge_state_address()).await?;
+        let bridge_state = bridge::state::BridgeState::try_deserialize(&mut data.as_slice())?;
+        let breaker = self.anomalies.check_breaker(
+            bridge_state.is_paused,
+            bridge_state.pause_reason,
+            bridge_state.emergency_pause_timestamp,
+        );
+        if let Some(anomaly) = breaker {
+            self.raise(anomaly.clone()).await;
+            return Err(anyhow::anyhow!("unlock {} held on chain: {}", signature, anomaly));
+        }
         
         info!("Successfully submitted transaction to Solana: {}", signature);
 
         Ok(())
     }
 
-    async fn create_qubic_transaction(&self, tx: BridgeTransaction) -> Result<serde_json::Value, anyhow::Error> {
-        // Create Qubic transaction structure
-        let qubic_tx = serde_json::json!({
-            "contractId": "BRIDGE_CONTRACT_ID",
-            "function": "UnlockAssets",
-            "input": {
-                "asset": {
-                    "id": tx.token_mint.to_string(),
-                    "tokenType": 1,
-                    "decimals": 9
-                },
-                "amount": tx.amount,
-                "bridgeId": tx.id,
-                "validatorSignatures": self.generate_validator_signatures(&tx).await?
-            }
-        });
+    async fn create_qubic_unlock_input(&self, tx: &BridgeTransaction) -> Result<UnlockAssetsInput, anyhow::Error> {
+        // HM25 counts the validators that signed off by their registered Qubic identities, not
+        // by the Solana keys they attest with
+        let attestation = attestation_for(tx, &self.domain);
+        let approvals = self.signature_collector
+            .threshold_signatures(&attestation)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("validator threshold not reached for lock {}", tx.id))?;
 
-        Ok(qubic_tx)
+        let mut signers = Vec::with_capacity(approvals.len());
+        for (validator, _) in approvals {
+            let qubic_id = self.signature_collector
+                .qubic_validator_id(&validator)
+                .await
+                .filter(|id| *id != [0; 32])
+                .ok_or_else(|| anyhow::anyhow!("validator {} has no registered Qubic identity", validator))?;
+            signers.push(qubic_id);
+        }
+
+        Ok(UnlockAssetsInput::new(tx.id, tx.qubic_destination, tx.amount, &signers)?)
     }
 
-    async fn create_solana_transaction(&self, _tx: BridgeTransaction) -> Result<solana_sdk::transaction::Transaction, anyhow::Error> {
-        // This would create the actual Solana transaction
-        // For now, return a placeholder
-        todo!("Implement Solana transaction creation")
+    async fn create_unlock_instruction(&self, tx: &BridgeTransaction) -> Result<Instruction, anyhow::Error> {
+        use anchor_spl::associated_token::get_associated_token_address;
+
+        // Only the signers' on-chain votes are submitted; the program recounts those
+        let approvers: Vec<Pubkey> = self.generate_validator_signatures(tx)
+            .await?
+            .into_iter()
+            .map(|(validator, _)| validator)
+            .collect();
+
+        let vault = bridge::client::vault_address(&tx.token_mint);
+
+        // The program has its own limit; this one pages, and holds the delivery if configured to
+        let vault_balance: u64 = self.solana_rpc.client().get_token_account_balance(&vault).await?.amount.parse()?;
+        if let Some(anomaly) = self.anomalies.check_unlock(tx.id, &tx.token_mint.to_string(), tx.amount, vault_balance) {
+            self.raise(anomaly.clone()).await;
+            if self.anomalies.pauses_on_anomaly() {
+                return Err(anyhow::anyhow!("delivery held: {}", anomaly));
+            }
+        }
+
+        Ok(bridge::client::unlock_assets(
+            &self.keypair.pubkey(),
+            &attestation_for(tx, &self.domain),
+            &get_associated_token_address(&tx.solana_destination, &tx.token_mint),
+            &vault,
+            &approvers,
+        ))
     }
 
-    async fn generate_validator_signatures(&self, _tx: &BridgeTransaction) -> Result<Vec<Vec<u8>>, anyhow::Error> {
-        // Generate validator signatures for the transaction
-        // This is a simplified implementation
-        let mut signatures = Vec::new();
-        
-        // In a real implementation, this would involve:
-        // 1. Creating a hash of the transaction data
-        // 2. Signing with the validator's private key
-        // 3. Collecting signatures from multiple validators
-        
-        signatures.push(vec![0u8; 64]); // Placeholder signature
-        
-        Ok(signatures)
+    async fn generate_validator_signatures(&self, tx: &BridgeTransaction) -> Result<Vec<(Pubkey, [u8; 64])>, anyhow::Error> {
+        // Signatures were produced by the validators themselves and published
+        // to the collector; only submit once threshold-many have arrived
+        let attestation = attestation_for(tx, &self.domain);
+        let signatures = self.signature_collector
+            .threshold_signatures(&attestation)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("validator threshold not reached for lock {}", tx.id))?;
+
+        Ok(signatures
+            .into_iter()
+            .map(|(validator, signature)| (validator, signature.into()))
+            .collect())
     }
 
     async fn process_pending_transactions(&self) {
-        let mut completed_transactions = Vec::new();
-        
-        {
-            let mut pending = self.pending_transactions.lock().await;
-            
-            for (id, tx) in pending.iter_mut() {
-                if self.check_transaction_confirmations(tx).await {
-                    info!("Transaction {} confirmed", id);
-                    completed_transactions.push(*id);
+        let awaiting: Vec<BridgeTransaction> = {
+            let pending = self.pending_transactions.lock().await;
+            pending.values().cloned().collect()
+        };
+
+        for mut tx in awaiting {
+            match self.finality_tracker.check(&tx).await {
+                Ok(Finality::Final(depth)) => {
+                    info!("Transaction {} final at depth {}", tx.id, depth);
+                    tx.confirmations = depth;
+                    self.pending_transactions.lock().await.remove(&tx.key());
+                    self.set_transfer_state(&tx, TransferState::Queued, None).await;
+                    self.retry_scheduler.schedule(tx).await;
+                }
+                Ok(Finality::Pending(depth)) => {
+                    if let Some(pending) = self.pending_transactions.lock().await.get_mut(&tx.key()) {
+                        pending.confirmations = depth;
+                    }
+                    tx.confirmations = depth;
+                    self.set_transfer_state(&tx, TransferState::AwaitingFinality, None).await;
+                }
+                Ok(Finality::Dropped) => {
+                    warn!("Source transaction for {} was dropped before finality, invalidating job", tx.id);
+                    self.pending_transactions.lock().await.remove(&tx.key());
+                    self.signature_collector.forget(&attestation_for(&tx, &self.domain)).await;
+                    let reason = "source transaction dropped before finality".to_string();
+                    self.set_transfer_state(&tx, TransferState::Invalidated, Some(reason)).await;
+                }
+                Err(e) => {
+                    error!("Could not check finality of transaction {}: {}", tx.id, e);
                 }
-            }
-            
-            // Remove completed transactions
-            for id in completed_transactions {
-                pending.remove(&id);
             }
         }
     }
 
-    async fn check_transaction_confirmations(&self, tx: &BridgeTransaction) -> bool {
-        // Check if transaction has enough confirmations
-        // This would involve querying both chains
-        tx.confirmations >= self.config.confirmation_threshold
-    }
-
     pub async fn get_bridge_status(&self) -> HashMap<String, serde_json::Value> {
         let mut status = HashMap::new();
         
@@ -250,32 +973,38 @@ impl BridgeRelayer {
         };
         
         status.insert("pending_transactions".to_string(), serde_json::json!(pending_count));
-        status.insert("solana_connected".to_string(), serde_json::json!(true));
-        status.insert("qubic_connected".to_string(), serde_json::json!(true));
+        status.insert("queued_deliveries".to_string(), serde_json::json!(self.retry_scheduler.queued().await));
+        status.insert("dead_letters".to_string(), serde_json::json!(self.retry_scheduler.dead_letters().await.len()));
+        status.insert("solana_connected".to_string(), serde_json::json!(self.solana_connected()));
+        status.insert("qubic_connected".to_string(), serde_json::json!(self.qubic_connected()));
+        status.insert("last_solana_poll".to_string(), serde_json::json!(self.last_solana_poll.load(Ordering::Relaxed)));
+        status.insert("last_qubic_poll".to_string(), serde_json::json!(self.last_qubic_poll.load(Ordering::Relaxed)));
+        status.insert("paused".to_string(), serde_json::json!(self.is_paused()));
+        status.insert(
+            "rpc_endpoints".to_string(),
+            serde_json::json!({
+                "solana": self.solana_rpc.endpoints().status(),
+                "qubic": self.qubic.status(),
+            }),
+        );
         
         status
     }
 }
 
+fn poll_is_fresh(last_poll: &AtomicI64) -> bool {
+    let last_poll = last_poll.load(Ordering::Relaxed);
+    last_poll > 0 && chrono::Utc::now().timestamp() - last_poll <= POLL_STALE_AFTER_SECS
+}
+
 // Public relay function for standalone usage
-pub async fn relay() {
+pub async fn relay(config: BridgeConfig, shutdown: Shutdown) {
     println!("Starting bridge relayer...");
     
-    // Load configuration (placeholder)
-    let config = BridgeConfig {
-        solana_rpc_url: "https://api.devnet.solana.com".to_string(),
-        qubic_rpc_url: "https://qubic-api.org".to_string(),
-        bridge_program_id: solana_sdk::pubkey::Pubkey::new_unique(),
-        validator_private_key: "placeholder".to_string(),
-        confirmation_threshold: 6,
-        max_retry_attempts: 3,
-        retry_delay_seconds: 30,
-    };
-    
     // Create and start the bridge relayer
     match BridgeRelayer::new(config).await {
         Ok(relayer) => {
-            if let Err(e) = relayer.start().await {
+            if let Err(e) = Arc::new(relayer).start(shutdown).await {
                 eprintln!("Error running bridge relayer: {}", e);
             }
         }
@@ -283,4 +1012,120 @@ pub async fn relay() {
             eprintln!("Error creating bridge relayer: {}", e);
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::SignedAttestation;
+    use crate::retry_scheduler::DeliveryRejected;
+    use crate::test_support::{config, relayer, temp_path, transaction, MockSolana};
+
+    #[tokio::test]
+    async fn unfinished_transfers_survive_a_restart() {
+        let solana = MockSolana::default().serve().await;
+        let checkpoint_path = temp_path("checkpoint.json");
+        let before = relayer(&solana, &checkpoint_path).await;
+
+        // The same lock id in both directions, plus one of each unfinished state and a delivered one
+        let awaiting = transaction(1, BridgeDirection::SolanaToQubic);
+        let queued = transaction(1, BridgeDirection::QubicToSolana);
+        let dead = transaction(2, BridgeDirection::QubicToSolana);
+        let delivered = transaction(3, BridgeDirection::SolanaToQubic);
+        before.set_transfer_state(&awaiting, TransferState::AwaitingFinality, None).await;
+        before.pending_transactions.lock().await.insert(awaiting.key(), awaiting.clone());
+        before.set_transfer_state(&queued, TransferState::Queued, None).await;
+        before.retry_scheduler.schedule(queued.clone()).await;
+        before.retry_scheduler.record_failure(dead.clone(), DeliveryRejected(400).into()).await;
+        before.set_transfer_state(&dead, TransferState::DeadLettered, Some("rejected".to_string())).await;
+        before.set_transfer_state(&delivered, TransferState::Delivered, None).await;
+        let cursor = QubicCursor { latest_tick: 500, next_lock_id: 7 };
+        before.qubic_monitor.restore(cursor);
+        before.write_checkpoint().await.unwrap();
+
+        let after = relayer(&solana, &checkpoint_path).await;
+        after.restore_checkpoint().await.unwrap();
+        assert!(!std::path::Path::new(&checkpoint_path).exists(), "a checkpoint is consumed on restore");
+        std::fs::remove_file(format!("{}.keypair", checkpoint_path)).unwrap();
+
+        let states: Vec<_> = after
+            .transfers(None)
+            .await
+            .into_iter()
+            .map(|record| (record.transaction.key(), record.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (awaiting.key(), TransferState::AwaitingFinality),
+                (queued.key(), TransferState::Queued),
+                (dead.key(), TransferState::DeadLettered),
+            ]
+        );
+        assert!(after.pending_transactions.lock().await.contains_key(&awaiting.key()));
+        assert_eq!(after.qubic_monitor.cursor(), Some(cursor));
+        assert_eq!(after.retry_scheduler.queued().await, 1);
+        assert_eq!(after.dead_letters().await[0].transaction.key(), dead.key());
+        assert_eq!(
+            after.transfer(dead.direction, dead.id).await.unwrap().last_error.as_deref(),
+            Some("rejected")
+        );
+
+        // A restored dead letter can still be retried
+        after.retry_transfer(dead.direction, dead.id).await.unwrap();
+        assert_eq!(after.retry_scheduler.queued().await, 2);
+        assert!(matches!(
+            after.retry_transfer(queued.direction, queued.id).await,
+            Err(RetryRejected::NotDeadLettered(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn qubic_unlocks_name_signers_by_their_qubic_identity() {
+        let solana = MockSolana::default().serve().await;
+        let checkpoint_path = temp_path("qubic-signers.json");
+        let mut relayer = relayer(&solana, &checkpoint_path).await;
+        std::fs::remove_file(format!("{}.keypair", checkpoint_path)).unwrap();
+
+        let signers: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+        let validators: Vec<(Pubkey, [u8; 32])> = signers
+            .iter()
+            .enumerate()
+            .map(|(index, keypair)| (keypair.pubkey(), [index as u8 + 1; 32]))
+            .collect();
+        relayer.signature_collector = Arc::new(SignatureCollector::new(relayer.domain, validators.clone(), 2));
+
+        let tx = transaction(4, BridgeDirection::SolanaToQubic);
+        let attestation = attestation_for(&tx, &relayer.domain);
+        for keypair in &signers[..2] {
+            relayer.signature_collector.submit(&SignedAttestation::sign(keypair, &attestation)).await.unwrap();
+        }
+
+        let input = relayer.create_qubic_unlock_input(&tx).await.unwrap();
+        let mut encoded: Vec<[u8; 32]> = input.signers().copied().collect();
+        encoded.sort();
+        let mut expected: Vec<[u8; 32]> = validators[..2].iter().map(|(_, qubic_id)| *qubic_id).collect();
+        expected.sort();
+        assert_eq!(encoded, expected);
+        assert!(input.signers().all(|id| signers.iter().all(|keypair| *id != keypair.pubkey().to_bytes())));
+
+        // A validator that never registered a Qubic identity can't be named to HM25
+        let unregistered = vec![(signers[0].pubkey(), [0; 32]), (signers[1].pubkey(), [2; 32])];
+        relayer.signature_collector = Arc::new(SignatureCollector::new(relayer.domain, unregistered, 2));
+        for keypair in &signers[..2] {
+            relayer.signature_collector.submit(&SignedAttestation::sign(keypair, &attestation)).await.unwrap();
+        }
+        assert!(relayer.create_qubic_unlock_input(&tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn a_relayer_without_a_validator_set_does_not_start() {
+        let solana = MockSolana { without_validator_config: true, ..MockSolana::default() }.serve().await;
+        let checkpoint_path = temp_path("no-validator-set.json");
+        let result = BridgeRelayer::new(config(&solana, &checkpoint_path)).await;
+        std::fs::remove_file(format!("{}.keypair", checkpoint_path)).unwrap();
+
+        let error = result.err().expect("started without a validator set").to_string();
+        assert!(error.contains("validator set"), "{}", error);
+    }
+}
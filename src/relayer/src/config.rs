@@ -0,0 +1,440 @@
+// src/relayer/src/config.rs
+// Relayer configuration: a JSON or TOML file (the ones in `config/` load as-is),
+// then `RELAYER_*` environment overrides, then validation before anything starts.
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use log::{info, warn};
+use serde::{Deserialize, Deserializer};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair};
+use thiserror::Error;
+
+use crate::anomaly::AnomalyConfig;
+use crate::bridge_relayer::{BridgeConfig, POLL_STALE_AFTER_SECS};
+use crate::finality::FinalityConfig;
+use crate::qubic_tx::{read_seed_file, QubicTxConfig};
+use crate::solana_rpc::{rpc_client, SolanaRpcConfig};
+
+/// Used when `RELAYER_CONFIG` is not set.
+pub const DEFAULT_CONFIG_PATH: &str = "config/devnet.json";
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("could not read {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("could not parse {0}: {1}")]
+    Parse(PathBuf, String),
+    #[error("environment variable {0} is not a valid value: {1}")]
+    InvalidOverride(&'static str, String),
+    #[error("invalid config: {0}")]
+    Invalid(String),
+    #[error("could not load keypair from {0}: {1}")]
+    Keypair(String, String),
+    #[error("{0} RPC at {1} is unreachable: {2}")]
+    Unreachable(&'static str, String, String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayerConfig {
+    pub network: String,
+    pub solana: SolanaSection,
+    pub qubic: QubicSection,
+    pub relayer: RelayerSection,
+    #[serde(default)]
+    pub finality: FinalityConfig,
+    #[serde(default)]
+    pub anomaly: AnomalyConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolanaSection {
+    /// `rpcUrl` with one endpoint or `rpcUrls` with several, tried in order of health.
+    #[serde(alias = "rpcUrl", deserialize_with = "one_or_many")]
+    pub rpc_urls: Vec<String>,
+    /// Endpoints that must report identical lock data before it is acted on. Unset disables.
+    #[serde(default)]
+    pub quorum: Option<usize>,
+    /// `ws(s)://` endpoint. When set, locks are streamed with `programSubscribe` instead of polled.
+    #[serde(default)]
+    pub ws_url: Option<String>,
+    /// In subscription mode, how often to rescan for locks the stream may have missed.
+    #[serde(default = "default_backfill_interval_seconds")]
+    pub backfill_interval_seconds: u64,
+    pub program_id: String,
+    #[serde(flatten)]
+    pub rpc: SolanaRpcConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QubicSection {
+    #[serde(alias = "rpcUrl", deserialize_with = "one_or_many")]
+    pub rpc_urls: Vec<String>,
+    #[serde(default)]
+    pub quorum: Option<usize>,
+    /// The Solana mint HM25's asset is paired with: what Qubic locks are released as.
+    pub bridged_mint: String,
+    #[serde(flatten)]
+    pub tx: QubicTxConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayerSection {
+    /// Solana keypair file: the relayer's fee payer, or the operator's key in validator mode.
+    pub keypair_path: String,
+    pub collector_url: String,
+    pub collector_listen_addr: String,
+    /// Operator API (`/health`, `/transfers`, `/pause`, ...). Keep it off public interfaces.
+    pub admin_listen_addr: String,
+    /// Unfinished transfers are written here on shutdown and restored on start. In validator
+    /// mode the daemon keeps its Qubic cursor and signed locks here instead.
+    pub checkpoint_path: String,
+    pub max_retry_attempts: u32,
+    pub retry_delay_seconds: u64,
+    /// Deliveries for different locks are independent and go out in parallel, up to this many.
+    #[serde(default = "default_max_concurrent_submissions")]
+    pub max_concurrent_submissions: usize,
+    /// Probe both RPC endpoints, and check the bridge holds a vault for `qubic.bridgedMint`,
+    /// during startup validation.
+    #[serde(default)]
+    pub check_rpc_reachability: bool,
+}
+
+impl RelayerConfig {
+    /// Load the file named by `RELAYER_CONFIG` (or the default), apply overrides and validate.
+    pub async fn load_from_env() -> Result<Self, ConfigError> {
+        let path = std::env::var("RELAYER_CONFIG").unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string());
+        let mut config = Self::from_file(&path)?;
+        config.apply_env_overrides()?;
+        config.validate().await?;
+
+        info!("Loaded {} configuration from {}", config.network, path);
+        Ok(config)
+    }
+
+    /// Parse a config file, as TOML when the extension says so and as JSON otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Read(path.to_path_buf(), e))?;
+
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&contents).map_err(|e| ConfigError::Parse(path.to_path_buf(), e.to_string()))
+        } else {
+            serde_json::from_str(&contents).map_err(|e| ConfigError::Parse(path.to_path_buf(), e.to_string()))
+        }
+    }
+
+    pub fn apply_env_overrides(&mut self) -> Result<(), ConfigError> {
+        override_from_env("RELAYER_NETWORK", &mut self.network)?;
+        override_list_from_env("RELAYER_SOLANA_RPC_URL", &mut self.solana.rpc_urls);
+        override_optional_from_env("RELAYER_SOLANA_QUORUM", &mut self.solana.quorum)?;
+        override_from_env("RELAYER_PROGRAM_ID", &mut self.solana.program_id)?;
+        override_list_from_env("RELAYER_QUBIC_RPC_URL", &mut self.qubic.rpc_urls);
+        override_optional_from_env("RELAYER_QUBIC_QUORUM", &mut self.qubic.quorum)?;
+        override_optional_from_env("RELAYER_QUBIC_SEED_PATH", &mut self.qubic.tx.seed_path)?;
+        override_from_env("RELAYER_QUBIC_BRIDGED_MINT", &mut self.qubic.bridged_mint)?;
+        override_from_env("RELAYER_KEYPAIR_PATH", &mut self.relayer.keypair_path)?;
+        override_from_env("RELAYER_COLLECTOR_URL", &mut self.relayer.collector_url)?;
+        override_from_env("RELAYER_COLLECTOR_LISTEN_ADDR", &mut self.relayer.collector_listen_addr)?;
+        override_from_env("RELAYER_ADMIN_LISTEN_ADDR", &mut self.relayer.admin_listen_addr)?;
+        override_from_env("RELAYER_CHECKPOINT_PATH", &mut self.relayer.checkpoint_path)?;
+        override_from_env("RELAYER_MAX_RETRY_ATTEMPTS", &mut self.relayer.max_retry_attempts)?;
+        override_from_env("RELAYER_RETRY_DELAY_SECONDS", &mut self.relayer.retry_delay_seconds)?;
+        override_from_env("RELAYER_MAX_CONCURRENT_SUBMISSIONS", &mut self.relayer.max_concurrent_submissions)?;
+        override_from_env(
+            "RELAYER_SOLANA_BACKFILL_INTERVAL_SECONDS",
+            &mut self.solana.backfill_interval_seconds,
+        )?;
+        override_from_env("RELAYER_SOLANA_RPC_TIMEOUT_SECONDS", &mut self.solana.rpc.timeout_seconds)?;
+        override_from_env("RELAYER_CHECK_RPC_REACHABILITY", &mut self.relayer.check_rpc_reachability)?;
+        override_from_env("RELAYER_QUBIC_TICK_DEPTH", &mut self.finality.qubic_tick_depth)?;
+        override_optional_from_env("RELAYER_PAGE_WEBHOOK_URL", &mut self.anomaly.page_webhook_url)?;
+        override_from_env("RELAYER_PAUSE_ON_ANOMALY", &mut self.anomaly.pause_on_anomaly)?;
+
+        if let Ok(value) = std::env::var("RELAYER_SOLANA_WS_URL") {
+            // Empty falls back to polling
+            self.solana.ws_url = Some(value).filter(|url| !url.is_empty());
+        }
+
+        if let Ok(value) = std::env::var("RELAYER_SOLANA_SLOT_DEPTH") {
+            self.finality.solana_slot_depth = match value.as_str() {
+                "" | "finalized" => None,
+                depth => Some(
+                    depth
+                        .parse()
+                        .map_err(|_| ConfigError::InvalidOverride("RELAYER_SOLANA_SLOT_DEPTH", value.clone()))?,
+                ),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Reject anything the relayer could not run with. Runs before any task is spawned.
+    pub async fn validate(&self) -> Result<(), ConfigError> {
+        let program_id = self.program_id()?;
+        if program_id != bridge::ID {
+            return Err(ConfigError::Invalid(format!(
+                "programId {} does not match the bridge program this relayer was built for ({})",
+                program_id,
+                bridge::ID
+            )));
+        }
+        self.bridged_mint()?;
+
+        for (name, urls, quorum) in [
+            ("solana", &self.solana.rpc_urls, self.solana.quorum),
+            ("qubic", &self.qubic.rpc_urls, self.qubic.quorum),
+        ] {
+            if urls.is_empty() {
+                return Err(ConfigError::Invalid(format!("{}.rpcUrls must list at least one endpoint", name)));
+            }
+            for url in urls {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(ConfigError::Invalid(format!("{}.rpcUrls must be http(s) URLs, got {:?}", name, url)));
+                }
+            }
+            if quorum.is_some_and(|quorum| quorum == 0 || quorum > urls.len()) {
+                return Err(ConfigError::Invalid(format!(
+                    "{}.quorum must be between 1 and the number of rpcUrls ({})",
+                    name,
+                    urls.len()
+                )));
+            }
+        }
+        if let Some(url) = &self.solana.ws_url {
+            if !(url.starts_with("ws://") || url.starts_with("wss://")) {
+                return Err(ConfigError::Invalid(format!("solana.wsUrl must be a ws(s) URL, got {:?}", url)));
+            }
+        }
+
+        for (name, addr) in [
+            ("relayer.collectorListenAddr", &self.relayer.collector_listen_addr),
+            ("relayer.adminListenAddr", &self.relayer.admin_listen_addr),
+        ] {
+            if addr.parse::<std::net::SocketAddr>().is_err() {
+                return Err(ConfigError::Invalid(format!("{} {:?} is not a socket address", name, addr)));
+            }
+        }
+        if self.relayer.checkpoint_path.is_empty() {
+            return Err(ConfigError::Invalid("relayer.checkpointPath must not be empty".to_string()));
+        }
+        if self.relayer.max_retry_attempts == 0 {
+            return Err(ConfigError::Invalid("relayer.maxRetryAttempts must be at least 1".to_string()));
+        }
+        if self.relayer.retry_delay_seconds == 0 {
+            return Err(ConfigError::Invalid("relayer.retryDelaySeconds must be at least 1".to_string()));
+        }
+        if self.relayer.max_concurrent_submissions == 0 {
+            return Err(ConfigError::Invalid("relayer.maxConcurrentSubmissions must be at least 1".to_string()));
+        }
+        if self.solana.rpc.timeout_seconds == 0 {
+            return Err(ConfigError::Invalid("solana.timeoutSeconds must be at least 1".to_string()));
+        }
+        // Backfills double as the connectivity heartbeat in subscription mode
+        if self.solana.backfill_interval_seconds == 0
+            || self.solana.backfill_interval_seconds as i64 >= POLL_STALE_AFTER_SECS
+        {
+            return Err(ConfigError::Invalid(format!(
+                "solana.backfillIntervalSeconds must be between 1 and {}",
+                POLL_STALE_AFTER_SECS - 1
+            )));
+        }
+        if self.qubic.tx.tick_offset == 0 {
+            return Err(ConfigError::Invalid("qubic.tickOffset must be at least 1".to_string()));
+        }
+        if self.qubic.tx.max_broadcasts == 0 {
+            return Err(ConfigError::Invalid("qubic.maxBroadcasts must be at least 1".to_string()));
+        }
+        if let Some(path) = &self.qubic.tx.seed_path {
+            read_seed_file(path).map_err(|e| ConfigError::Keypair(path.clone(), format!("{:#}", e)))?;
+        }
+        if self.finality.qubic_tick_depth == 0 {
+            return Err(ConfigError::Invalid("finality.qubicTickDepth must be at least 1".to_string()));
+        }
+        if self.finality.solana_slot_depth == Some(0) {
+            return Err(ConfigError::Invalid(
+                "finality.solanaSlotDepth must be at least 1, or null to wait for finalized".to_string(),
+            ));
+        }
+
+        if let Some(url) = &self.anomaly.page_webhook_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(ConfigError::Invalid(format!("anomaly.pageWebhookUrl must be an http(s) URL, got {:?}", url)));
+            }
+        }
+        if self.anomaly.max_unlock_bps > 10_000 {
+            return Err(ConfigError::Invalid("anomaly.maxUnlockBps must be at most 10000".to_string()));
+        }
+        if self.anomaly.max_deliveries_per_window > 0 && self.anomaly.window_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "anomaly.windowSeconds must be at least 1 while maxDeliveriesPerWindow is set".to_string(),
+            ));
+        }
+
+        self.keypair()?;
+
+        if self.relayer.check_rpc_reachability {
+            self.check_reachability().await?;
+            self.check_bridged_mint().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Enough endpoints must answer to serve reads, and to reach quorum if one is configured.
+    async fn check_reachability(&self) -> Result<(), ConfigError> {
+        let mut solana_failures = Vec::new();
+        for url in &self.solana.rpc_urls {
+            if let Err(e) = rpc_client(url, self.solana.rpc.timeout()).get_version().await {
+                solana_failures.push(format!("{}: {}", url, e));
+            }
+        }
+        require_reachable("Solana", &self.solana.rpc_urls, self.solana.quorum, solana_failures)?;
+
+        let mut qubic_failures = Vec::new();
+        for url in &self.qubic.rpc_urls {
+            let result = reqwest::get(format!("{}/v1/tick-info", url))
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                qubic_failures.push(format!("{}: {}", url, e));
+            }
+        }
+        require_reachable("Qubic", &self.qubic.rpc_urls, self.qubic.quorum, qubic_failures)?;
+
+        Ok(())
+    }
+
+    /// Qubic locks are released from the bridge's vault for the bridged mint, so a mint the
+    /// bridge has no vault for could never be delivered.
+    async fn check_bridged_mint(&self) -> Result<(), ConfigError> {
+        let mint = self.bridged_mint()?;
+        let mut failures = Vec::new();
+        for url in &self.solana.rpc_urls {
+            let vault = rpc_client(url, self.solana.rpc.timeout())
+                .get_account_with_commitment(&bridge::client::vault_address(&mint), CommitmentConfig::confirmed())
+                .await;
+            match vault {
+                Ok(vault) if vault.value.is_none() => {
+                    return Err(ConfigError::Invalid(format!("qubic.bridgedMint {} has no vault on the bridge", mint)))
+                }
+                Ok(_) => return Ok(()),
+                Err(e) => failures.push(format!("{}: {}", url, e)),
+            }
+        }
+        Err(ConfigError::Unreachable("Solana", self.solana.rpc_urls.join(", "), failures.join("; ")))
+    }
+
+    pub fn program_id(&self) -> Result<Pubkey, ConfigError> {
+        Pubkey::from_str(&self.solana.program_id)
+            .map_err(|_| ConfigError::Invalid(format!("solana.programId {:?} is not a public key", self.solana.program_id)))
+    }
+
+    pub fn bridged_mint(&self) -> Result<Pubkey, ConfigError> {
+        Pubkey::from_str(&self.qubic.bridged_mint)
+            .map_err(|_| ConfigError::Invalid(format!("qubic.bridgedMint {:?} is not a public key", self.qubic.bridged_mint)))
+    }
+
+    pub fn keypair(&self) -> Result<Keypair, ConfigError> {
+        read_keypair_file(&self.relayer.keypair_path)
+            .map_err(|e| ConfigError::Keypair(self.relayer.keypair_path.clone(), e.to_string()))
+    }
+
+    pub fn bridge_config(&self) -> Result<BridgeConfig, ConfigError> {
+        Ok(BridgeConfig {
+            solana_rpc_urls: self.solana.rpc_urls.clone(),
+            solana_quorum: self.solana.quorum,
+            solana_ws_url: self.solana.ws_url.clone(),
+            solana_backfill_interval_seconds: self.solana.backfill_interval_seconds,
+            qubic_rpc_urls: self.qubic.rpc_urls.clone(),
+            qubic_quorum: self.qubic.quorum,
+            qubic_mint: self.bridged_mint()?,
+            qubic_tx: self.qubic.tx.clone(),
+            bridge_program_id: self.program_id()?,
+            keypair_path: self.relayer.keypair_path.clone(),
+            finality: self.finality.clone(),
+            anomaly: self.anomaly.clone(),
+            solana_rpc: self.solana.rpc.clone(),
+            max_retry_attempts: self.relayer.max_retry_attempts,
+            max_concurrent_submissions: self.relayer.max_concurrent_submissions,
+            retry_delay_seconds: self.relayer.retry_delay_seconds,
+            collector_listen_addr: self.relayer.collector_listen_addr.clone(),
+            admin_listen_addr: self.relayer.admin_listen_addr.clone(),
+            checkpoint_path: self.relayer.checkpoint_path.clone(),
+        })
+    }
+}
+
+fn default_max_concurrent_submissions() -> usize {
+    8
+}
+
+fn default_backfill_interval_seconds() -> u64 {
+    30
+}
+
+fn require_reachable(
+    chain: &'static str,
+    urls: &[String],
+    quorum: Option<usize>,
+    failures: Vec<String>,
+) -> Result<(), ConfigError> {
+    for failure in &failures {
+        warn!("{} RPC unreachable: {}", chain, failure);
+    }
+    let required = quorum.unwrap_or(1);
+    if urls.len() - failures.len() < required {
+        return Err(ConfigError::Unreachable(chain, urls.join(", "), failures.join("; ")));
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(url) => vec![url],
+        OneOrMany::Many(urls) => urls,
+    })
+}
+
+fn override_from_env<T: FromStr>(name: &'static str, target: &mut T) -> Result<(), ConfigError> {
+    if let Ok(value) = std::env::var(name) {
+        *target = value.parse().map_err(|_| ConfigError::InvalidOverride(name, value))?;
+    }
+    Ok(())
+}
+
+/// A comma-separated list, e.g. several RPC endpoints.
+fn override_list_from_env(name: &'static str, target: &mut Vec<String>) {
+    if let Ok(value) = std::env::var(name) {
+        *target = value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+}
+
+/// An empty value unsets the option.
+fn override_optional_from_env<T: FromStr>(name: &'static str, target: &mut Option<T>) -> Result<(), ConfigError> {
+    if let Ok(value) = std::env::var(name) {
+        *target = match value.as_str() {
+            "" => None,
+            _ => Some(value.parse().map_err(|_| ConfigError::InvalidOverride(name, value))?),
+        };
+    }
+    Ok(())
+}
@@ -0,0 +1,476 @@
+// src/relayer/src/finality.rs
+// Source-chain finality. A job is only delivered once its source transaction is final;
+// one whose source transaction disappears before that point is invalidated. With quorum
+// reads enabled, enough independent endpoints must also agree the lock is what it claims:
+// the bridge account on Solana, `GetLockInfo` on Qubic, decoded and compared field by field.
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anchor_lang::AccountDeserialize;
+use log::{error, warn};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use tokio::sync::Mutex;
+
+use bridge::state::BRIDGE_STATUS_FAILED;
+use shared::qubic::hm25::{GetLockInfoInput, GetLockInfoOutput, FUNCTION_GET_LOCK_INFO};
+use shared::qubic::identity::lowercase_identity;
+use shared::types::{BridgeDirection, BridgeTransaction};
+
+use crate::qubic_monitor::{query_output, query_request, tick_info, QUERY_SMART_CONTRACT};
+use crate::rpc_pool::{QubicEndpoints, SolanaEndpoints};
+
+/// Consecutive lookups that must miss before a source transaction counts as gone.
+/// Guards against one lagging RPC node invalidating a perfectly good job.
+const MISSES_BEFORE_DROPPED: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finality {
+    /// Safe to relay. Carries the depth observed (slots or ticks).
+    Final(u64),
+    /// Seen, but not final yet. Carries the depth observed so far.
+    Pending(u64),
+    /// The source transaction failed or is no longer on chain.
+    Dropped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityConfig {
+    /// Relay once the source slot is this many slots behind the confirmed tip.
+    /// `None` waits for the `finalized` commitment.
+    pub solana_slot_depth: Option<u64>,
+    /// Relay once the Qubic tick holding the source transaction is this many ticks old,
+    /// or as soon as its epoch has ended.
+    pub qubic_tick_depth: u64,
+}
+
+impl Default for FinalityConfig {
+    fn default() -> Self {
+        FinalityConfig {
+            solana_slot_depth: None,
+            qubic_tick_depth: 6,
+        }
+    }
+}
+
+/// `GET /v2/transactions/{id}`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QubicArchivedTransaction {
+    transaction: QubicTransactionTick,
+    /// Whether the amount was transferred; only an explicit `false` counts as failed.
+    money_flew: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QubicTransactionTick {
+    tick_number: u64,
+}
+
+pub struct FinalityTracker {
+    config: FinalityConfig,
+    solana: SolanaEndpoints,
+    solana_client: Arc<RpcClient>,
+    qubic: QubicEndpoints,
+    /// Consecutive misses per job. Lock ids repeat across directions, so keyed by both.
+    misses: Mutex<HashMap<(BridgeDirection, u64), u32>>,
+}
+
+impl FinalityTracker {
+    pub fn new(config: FinalityConfig, solana: SolanaEndpoints, qubic: QubicEndpoints) -> Self {
+        FinalityTracker {
+            config,
+            solana_client: solana.client(),
+            solana,
+            qubic,
+            misses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Finality of the transaction that created this job on its source chain.
+    pub async fn check(&self, tx: &BridgeTransaction) -> Result<Finality, anyhow::Error> {
+        let observed = match tx.direction {
+            BridgeDirection::SolanaToQubic => match &tx.solana_tx_signature {
+                Some(signature) => self.check_solana(signature).await?,
+                None => return Ok(Finality::Pending(0)),
+            },
+            BridgeDirection::QubicToSolana => match &tx.qubic_tx_hash {
+                Some(hash) => self.check_qubic(hash).await?,
+                None => return Ok(Finality::Pending(0)),
+            },
+        };
+
+        let mut misses = self.misses.lock().await;
+        match observed {
+            Some(Finality::Final(depth)) => {
+                misses.remove(&tx.key());
+                drop(misses);
+                self.corroborate(tx, depth).await
+            }
+            Some(finality) => {
+                misses.remove(&tx.key());
+                Ok(finality)
+            }
+            None => {
+                let count = misses.entry(tx.key()).or_insert(0);
+                *count += 1;
+                warn!("Source transaction for {:?} lock {} not found ({}/{})", tx.direction, tx.id, count, MISSES_BEFORE_DROPPED);
+
+                if *count >= MISSES_BEFORE_DROPPED {
+                    misses.remove(&tx.key());
+                    Ok(Finality::Dropped)
+                } else {
+                    Ok(Finality::Pending(0))
+                }
+            }
+        }
+    }
+
+    /// `None` when the node has no record of the signature.
+    async fn check_solana(&self, signature: &str) -> Result<Option<Finality>, anyhow::Error> {
+        let signature = Signature::from_str(signature)?;
+        let status = self
+            .solana_client
+            .get_signature_statuses_with_history(&[signature])
+            .await?
+            .value
+            .into_iter()
+            .next()
+            .flatten();
+
+        let Some(status) = status else {
+            return Ok(None);
+        };
+
+        if status.err.is_some() {
+            return Ok(Some(Finality::Dropped));
+        }
+
+        // Statuses below `confirmed` are never trusted, whatever the client's default
+        let tip = self.solana_client.get_slot_with_commitment(CommitmentConfig::confirmed()).await?;
+        let depth = tip.saturating_sub(status.slot);
+
+        if status.satisfies_commitment(CommitmentConfig::finalized()) {
+            return Ok(Some(Finality::Final(depth)));
+        }
+
+        // A slot depth only counts from `confirmed`; `processed` data never relays
+        let deep_enough = self.config.solana_slot_depth.is_some_and(|required| depth >= required);
+        if deep_enough && status.satisfies_commitment(CommitmentConfig::confirmed()) {
+            Ok(Some(Finality::Final(depth)))
+        } else {
+            Ok(Some(Finality::Pending(depth)))
+        }
+    }
+
+    async fn check_qubic(&self, hash: &[u8; 32]) -> Result<Option<Finality>, anyhow::Error> {
+        let response = self.qubic
+            .get(&format!("/v2/transactions/{}", lowercase_identity(hash)))
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let archived: QubicArchivedTransaction = response.error_for_status()?.json().await?;
+
+        if archived.money_flew == Some(false) {
+            return Ok(Some(Finality::Dropped));
+        }
+
+        let tip = tick_info(&self.qubic).await?;
+        let tick = archived.transaction.tick_number;
+        let depth = tip.tick.saturating_sub(tick);
+        if tick < tip.initial_tick || depth >= self.config.qubic_tick_depth {
+            Ok(Some(Finality::Final(depth)))
+        } else {
+            Ok(Some(Finality::Pending(depth)))
+        }
+    }
+
+    /// A final source transaction is only acted on once a quorum of endpoints reports the same
+    /// lock. No quorum yet keeps the job pending; a quorum that contradicts it drops the job.
+    async fn corroborate(&self, tx: &BridgeTransaction, depth: u64) -> Result<Finality, anyhow::Error> {
+        let corroborated = match tx.direction {
+            BridgeDirection::SolanaToQubic => {
+                let Some(required) = self.solana.quorum() else {
+                    return Ok(Finality::Final(depth));
+                };
+                let address = bridge::client::bridge_transaction_address(tx.id);
+                match self.solana.quorum_account(&address, required).await {
+                    Ok(Some((owner, data))) => owner == bridge::ID && lock_matches(tx, &data),
+                    Ok(None) => false,
+                    Err(e) => {
+                        warn!("Lock {} is final but not corroborated yet: {}", tx.id, e);
+                        return Ok(Finality::Pending(depth));
+                    }
+                }
+            }
+            BridgeDirection::QubicToSolana => {
+                let Some(required) = self.qubic.quorum() else {
+                    return Ok(Finality::Final(depth));
+                };
+                let request = query_request(FUNCTION_GET_LOCK_INFO, &GetLockInfoInput { lock_id: tx.id });
+                match self.qubic
+                    .quorum_post_json(QUERY_SMART_CONTRACT, &request, required, query_output::<GetLockInfoOutput>)
+                    .await
+                {
+                    Ok(lock) => qubic_lock_matches(tx, &lock),
+                    Err(e) => {
+                        warn!("Lock {} is final but not corroborated yet: {}", tx.id, e);
+                        return Ok(Finality::Pending(depth));
+                    }
+                }
+            }
+        };
+
+        if corroborated {
+            Ok(Finality::Final(depth))
+        } else {
+            error!("A quorum of endpoints contradicts lock {}, dropping it", tx.id);
+            Ok(Finality::Dropped)
+        }
+    }
+}
+
+/// Whether the contract's record of a Qubic lock agrees with the job on what gets released.
+fn qubic_lock_matches(tx: &BridgeTransaction, lock: &GetLockInfoOutput) -> bool {
+    lock.found
+        && lock.status != BRIDGE_STATUS_FAILED
+        && lock.actual_amount == tx.amount
+        && lock.solana_destination == tx.solana_destination.to_bytes()
+}
+
+/// Whether the lock account agrees with the job on everything a delivery depends on.
+fn lock_matches(tx: &BridgeTransaction, data: &[u8]) -> bool {
+    let Ok(lock) = bridge::state::BridgeTransaction::try_deserialize(&mut &data[..]) else {
+        return false;
+    };
+    lock.id == tx.id
+        && lock.user == tx.user
+        && lock.token_mint == tx.token_mint
+        && lock.actual_amount == tx.amount
+        && lock.qubic_destination == tx.qubic_destination
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    use axum::extract::{Path, State};
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use base64::Engine;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Signature;
+    use tokio::time::Duration;
+
+    use shared::qubic::QpiStruct;
+
+    use crate::test_support::{serve, transaction, MockSolana};
+
+    /// Transactions the node has archived, as (tick, money flew) by id, its current tick and
+    /// the first tick of the epoch, and what `GetLockInfo` answers for each lock id.
+    #[derive(Clone)]
+    struct Node {
+        transactions: Arc<StdMutex<HashMap<String, (u64, bool)>>>,
+        tip: (u64, u64),
+        locks: HashMap<u64, GetLockInfoOutput>,
+    }
+
+    impl Node {
+        fn archive(&self, hash: [u8; 32], tick: u64, money_flew: bool) {
+            self.transactions.lock().unwrap().insert(lowercase_identity(&hash), (tick, money_flew));
+        }
+
+        fn forget(&self) {
+            self.transactions.lock().unwrap().clear();
+        }
+    }
+
+    async fn archived_transaction(State(node): State<Node>, Path(id): Path<String>) -> Result<Json<serde_json::Value>, StatusCode> {
+        let (tick, money_flew) = *node.transactions.lock().unwrap().get(&id).ok_or(StatusCode::NOT_FOUND)?;
+        Ok(Json(serde_json::json!({
+            "transaction": { "txId": id, "tickNumber": tick, "inputType": 1 },
+            "timestamp": "0",
+            "moneyFlew": money_flew,
+        })))
+    }
+
+    async fn current_tick(State(node): State<Node>) -> Json<serde_json::Value> {
+        Json(serde_json::json!({ "tickInfo": { "tick": node.tip.0, "duration": 1, "epoch": 1, "initialTick": node.tip.1 } }))
+    }
+
+    async fn query(State(node): State<Node>, Json(request): Json<serde_json::Value>) -> Json<serde_json::Value> {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let input = GetLockInfoInput::decode(&engine.decode(request["requestData"].as_str().unwrap()).unwrap()).unwrap();
+        let lock = node.locks.get(&input.lock_id).cloned().unwrap_or_else(|| {
+            GetLockInfoOutput::decode(&[0; GetLockInfoOutput::SIZE]).unwrap()
+        });
+        Json(serde_json::json!({ "responseData": engine.encode(lock.encode()) }))
+    }
+
+    async fn tracker(node: &Node) -> FinalityTracker {
+        tracker_with_quorum(std::slice::from_ref(node), None).await
+    }
+
+    async fn tracker_with_quorum(nodes: &[Node], quorum: Option<usize>) -> FinalityTracker {
+        let mut urls = Vec::new();
+        for node in nodes {
+            urls.push(
+                serve(
+                    Router::new()
+                        .route("/v2/transactions/:id", get(archived_transaction))
+                        .route("/v1/tick-info", get(current_tick))
+                        .route(QUERY_SMART_CONTRACT, post(query))
+                        .with_state(node.clone()),
+                )
+                .await,
+            );
+        }
+        FinalityTracker::new(
+            FinalityConfig::default(),
+            SolanaEndpoints::new(&[MockSolana::default().serve().await], Duration::from_secs(1), None),
+            QubicEndpoints::new(&urls, quorum),
+        )
+    }
+
+    fn node(tip: (u64, u64)) -> Node {
+        Node { transactions: Arc::default(), tip, locks: HashMap::new() }
+    }
+
+    /// A node on which `tx` is final and `GetLockInfo` reports `lock` for it.
+    fn node_reporting(tx: &BridgeTransaction, lock: GetLockInfoOutput) -> Node {
+        let node = node((100, 1));
+        node.archive(tx.qubic_tx_hash.unwrap(), 50, true);
+        Node { locks: HashMap::from([(tx.id, lock)]), ..node }
+    }
+
+    fn lock_info(tx: &BridgeTransaction) -> GetLockInfoOutput {
+        GetLockInfoOutput {
+            found: true,
+            locker: [9; 32],
+            amount: tx.amount + 10,
+            actual_amount: tx.amount,
+            fee: 10,
+            timestamp: 50,
+            solana_destination: tx.solana_destination.to_bytes(),
+            memo: [0; 64],
+            status: 0,
+        }
+    }
+
+    fn qubic_lock(id: u64, hash: u8) -> BridgeTransaction {
+        let mut tx = transaction(id, BridgeDirection::QubicToSolana);
+        tx.qubic_tx_hash = Some([hash; 32]);
+        tx
+    }
+
+    #[tokio::test]
+    async fn a_lock_is_invalidated_only_after_consecutive_misses() {
+        let node = node((100, 1));
+        let tracker = tracker(&node).await;
+        let tx = qubic_lock(1, 0xaa);
+
+        for _ in 1..MISSES_BEFORE_DROPPED {
+            assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Pending(0));
+        }
+
+        // Found again before the last miss: the count starts over
+        node.archive([0xaa; 32], 99, true);
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Pending(1));
+        node.forget();
+
+        for _ in 1..MISSES_BEFORE_DROPPED {
+            assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Pending(0));
+        }
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Dropped);
+    }
+
+    #[tokio::test]
+    async fn misses_are_counted_per_direction() {
+        let tracker = tracker(&node((100, 1))).await;
+        let inbound = qubic_lock(5, 0xcc);
+        let mut outbound = transaction(5, BridgeDirection::SolanaToQubic);
+        outbound.solana_tx_signature = Some(Signature::from([7; 64]).to_string());
+
+        for _ in 1..MISSES_BEFORE_DROPPED {
+            assert_eq!(tracker.check(&inbound).await.unwrap(), Finality::Pending(0));
+            assert_eq!(tracker.check(&outbound).await.unwrap(), Finality::Pending(0));
+        }
+        assert_eq!(tracker.check(&inbound).await.unwrap(), Finality::Dropped);
+        assert_eq!(tracker.check(&outbound).await.unwrap(), Finality::Dropped);
+    }
+
+    #[tokio::test]
+    async fn a_quorum_must_report_the_same_lock_contents() {
+        let mut tx = qubic_lock(8, 0xdd);
+        tx.solana_destination = Pubkey::new_unique();
+        let honest = lock_info(&tx);
+        let inflated = GetLockInfoOutput { actual_amount: tx.amount * 100, ..honest.clone() };
+        let redirected = GetLockInfoOutput { solana_destination: [1; 32], ..honest.clone() };
+
+        // Two of three agree with the job; the odd one out is outvoted
+        let nodes = [node_reporting(&tx, honest.clone()), node_reporting(&tx, inflated.clone()), node_reporting(&tx, honest.clone())];
+        let tracker = tracker_with_quorum(&nodes, Some(2)).await;
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Final(50));
+
+        // No two endpoints agree: wait rather than act on either answer
+        let nodes = [node_reporting(&tx, honest.clone()), node_reporting(&tx, inflated.clone()), node_reporting(&tx, redirected)];
+        let tracker = tracker_with_quorum(&nodes, Some(2)).await;
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Pending(50));
+
+        // A quorum agrees on contents the job does not match
+        let nodes = [node_reporting(&tx, inflated.clone()), node_reporting(&tx, inflated), node_reporting(&tx, honest)];
+        let tracker = tracker_with_quorum(&nodes, Some(2)).await;
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Dropped);
+    }
+
+    #[tokio::test]
+    async fn a_quorum_reporting_the_lock_unknown_or_failed_drops_it() {
+        let tx = qubic_lock(9, 0xee);
+        let unknown = node_reporting(&tx, GetLockInfoOutput::decode(&[0; GetLockInfoOutput::SIZE]).unwrap());
+        let tracker = tracker_with_quorum(&[unknown.clone(), unknown], Some(2)).await;
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Dropped);
+
+        let failed = node_reporting(&tx, GetLockInfoOutput { status: BRIDGE_STATUS_FAILED, ..lock_info(&tx) });
+        let tracker = tracker_with_quorum(&[failed.clone(), failed], Some(2)).await;
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Dropped);
+    }
+
+    #[tokio::test]
+    async fn a_failed_source_transaction_is_dropped_at_once() {
+        let node = node((100, 1));
+        node.archive([0xbb; 32], 10, false);
+        let tracker = tracker(&node).await;
+
+        assert_eq!(tracker.check(&qubic_lock(2, 0xbb)).await.unwrap(), Finality::Dropped);
+    }
+
+    #[tokio::test]
+    async fn qubic_locks_are_final_by_tick_depth_or_epoch_change() {
+        // The current epoch started at tick 97
+        let node = node((100, 97));
+        node.archive([1; 32], 98, true);
+        node.archive([2; 32], 94, true);
+        node.archive([3; 32], 96, true);
+        let tracker = tracker(&node).await;
+
+        assert_eq!(tracker.check(&qubic_lock(1, 1)).await.unwrap(), Finality::Pending(2));
+        assert_eq!(tracker.check(&qubic_lock(2, 2)).await.unwrap(), Finality::Final(6));
+        assert_eq!(tracker.check(&qubic_lock(3, 3)).await.unwrap(), Finality::Final(4));
+    }
+
+    #[tokio::test]
+    async fn a_job_without_a_source_transaction_waits() {
+        let tracker = tracker(&node((0, 0))).await;
+        let tx = transaction(4, BridgeDirection::QubicToSolana);
+        assert_eq!(tracker.check(&tx).await.unwrap(), Finality::Pending(0));
+    }
+}
@@ -0,0 +1,21 @@
+// Solana-Qubic bridge relayer: chain monitors and the relaying loop.
+
+pub mod qubic_monitor;
+pub mod qubic_tx;
+pub mod solana_monitor;
+pub mod solana_rpc;
+pub mod supervisor;
+pub mod bridge_relayer;
+pub mod admin_api;
+pub mod anomaly;
+pub mod attestation;
+pub mod config;
+pub mod finality;
+pub mod metrics;
+pub mod retry_scheduler;
+pub mod rpc_pool;
+pub mod signature_collector;
+pub mod validator_daemon;
+
+#[cfg(test)]
+mod test_support;
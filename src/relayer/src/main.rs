@@ -1,31 +1,58 @@
 // filepath: /solana-qubic-bridge/solana-qubic-bridge/src/relayer/src/main.rs
 use std::error::Error;
-use tokio::task;
-use tokio::time::{sleep, Duration};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::watch;
 
-mod qubic_monitor;
-mod solana_monitor;
-mod bridge_relayer;
+use relayer::bridge_relayer;
+use relayer::config::RelayerConfig;
+use relayer::rpc_pool::{QubicEndpoints, SolanaEndpoints};
+use relayer::supervisor::{shutdown_requested, Shutdown};
+use relayer::validator_daemon::ValidatorDaemon;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    let qubic_task = task::spawn(async {
-        qubic_monitor::monitor().await;
-    });
+    env_logger::init();
 
-    let solana_task = task::spawn(async {
-        solana_monitor::monitor().await;
-    });
+    // Refuse to start on a bad config rather than failing somewhere inside a task
+    let config = RelayerConfig::load_from_env().await?;
 
-    let relayer_task = task::spawn(async {
-        bridge_relayer::relay().await;
+    let (shutdown_tx, shutdown) = watch::channel(false);
+    let mut sigterm = signal(SignalKind::terminate())?;
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = sigterm.recv() => log::info!("SIGTERM received, shutting down"),
+            _ = tokio::signal::ctrl_c() => log::info!("Interrupt received, shutting down"),
+        }
+        let _ = shutdown_tx.send(true);
     });
 
-    // Wait for all tasks to complete
-    let _ = tokio::join!(qubic_task, solana_task, relayer_task);
+    // `relayer validator` runs a single operator's signing daemon instead of the coordinator
+    if std::env::args().nth(1).as_deref() == Some("validator") {
+        return run_validator(config, shutdown).await;
+    }
+
+    bridge_relayer::relay(config.bridge_config()?, shutdown).await;
 
-    // Keep the main thread alive
-    loop {
-        sleep(Duration::from_secs(60)).await;
+    Ok(())
+}
+
+async fn run_validator(config: RelayerConfig, mut shutdown: Shutdown) -> Result<(), Box<dyn Error>> {
+    let daemon = ValidatorDaemon::new(
+        config.keypair()?,
+        config.relayer.collector_url.clone(),
+        SolanaEndpoints::new(&config.solana.rpc_urls, config.solana.rpc.timeout(), config.solana.quorum),
+        QubicEndpoints::new(&config.qubic.rpc_urls, config.qubic.quorum),
+        config.program_id()?,
+        config.bridged_mint()?,
+        config.finality.clone(),
+        config.relayer.checkpoint_path.clone(),
+    );
+
+    // Signing is idempotent per lock, so the daemon can stop between any two polls
+    tokio::select! {
+        result = daemon.run() => result?,
+        _ = shutdown_requested(&mut shutdown) => {}
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
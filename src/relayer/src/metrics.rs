@@ -0,0 +1,220 @@
+// src/relayer/src/metrics.rs
+// Prometheus metrics for the relayer, served as text on the admin API's `/metrics`.
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use shared::types::BridgeDirection;
+
+use crate::bridge_relayer::TransferState;
+
+/// End-to-end latency buckets in seconds, from source lock to delivery.
+const LATENCY_BUCKETS: &[f64] = &[5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0, 3600.0];
+
+pub struct RelayerMetrics {
+    registry: Registry,
+    transfers: IntCounterVec,
+    transfer_latency: HistogramVec,
+    retries: IntCounterVec,
+    chain_head: IntGaugeVec,
+    last_seen_height: IntGaugeVec,
+    poll_lag: IntGaugeVec,
+    rpc_requests: IntCounterVec,
+    rpc_errors: IntCounterVec,
+    vault_balance: IntGaugeVec,
+    expected_vault_balance: IntGaugeVec,
+    total_locked_tokens: IntGauge,
+    anomalies: IntCounterVec,
+}
+
+impl RelayerMetrics {
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new_custom(Some("relayer".to_string()), None)?;
+
+        let transfers = IntCounterVec::new(
+            Opts::new("transfers_total", "Transfers entering each pipeline state"),
+            &["direction", "status"],
+        )?;
+        let transfer_latency = HistogramVec::new(
+            HistogramOpts::new("transfer_latency_seconds", "Time from source lock to delivery")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["direction"],
+        )?;
+        let retries = IntCounterVec::new(
+            Opts::new("delivery_retries_total", "Failed delivery attempts, by outcome"),
+            &["direction", "outcome"],
+        )?;
+        let chain_head = IntGaugeVec::new(
+            Opts::new("chain_head", "Latest slot or tick reported by the chain"),
+            &["chain"],
+        )?;
+        let last_seen_height = IntGaugeVec::new(
+            Opts::new("last_seen_height", "Latest slot or tick the relayer has processed"),
+            &["chain"],
+        )?;
+        let poll_lag = IntGaugeVec::new(
+            Opts::new("poll_lag", "Chain head minus the latest processed slot or tick"),
+            &["chain"],
+        )?;
+        let rpc_requests = IntCounterVec::new(
+            Opts::new("rpc_requests_total", "RPC calls made by the monitor and delivery loops"),
+            &["chain"],
+        )?;
+        let rpc_errors = IntCounterVec::new(
+            Opts::new("rpc_errors_total", "RPC calls that failed"),
+            &["chain"],
+        )?;
+        let vault_balance = IntGaugeVec::new(
+            Opts::new("vault_balance", "Token balance held by the bridge vault"),
+            &["mint"],
+        )?;
+        let expected_vault_balance = IntGaugeVec::new(
+            Opts::new("expected_vault_balance", "Locked minus unlocked plus fees, from the mint ledger"),
+            &["mint"],
+        )?;
+        let total_locked_tokens = IntGauge::new("total_locked_tokens", "BridgeState total_locked_tokens")?;
+        let anomalies = IntCounterVec::new(
+            Opts::new("anomalies_total", "Anomalous flows paged, by kind"),
+            &["kind"],
+        )?;
+
+        registry.register(Box::new(transfers.clone()))?;
+        registry.register(Box::new(transfer_latency.clone()))?;
+        registry.register(Box::new(retries.clone()))?;
+        registry.register(Box::new(chain_head.clone()))?;
+        registry.register(Box::new(last_seen_height.clone()))?;
+        registry.register(Box::new(poll_lag.clone()))?;
+        registry.register(Box::new(rpc_requests.clone()))?;
+        registry.register(Box::new(rpc_errors.clone()))?;
+        registry.register(Box::new(vault_balance.clone()))?;
+        registry.register(Box::new(expected_vault_balance.clone()))?;
+        registry.register(Box::new(total_locked_tokens.clone()))?;
+        registry.register(Box::new(anomalies.clone()))?;
+
+        Ok(RelayerMetrics {
+            registry,
+            transfers,
+            transfer_latency,
+            retries,
+            chain_head,
+            last_seen_height,
+            poll_lag,
+            rpc_requests,
+            rpc_errors,
+            vault_balance,
+            expected_vault_balance,
+            total_locked_tokens,
+            anomalies,
+        })
+    }
+
+    pub fn transfer_state(&self, direction: &BridgeDirection, state: TransferState) {
+        self.transfers
+            .with_label_values(&[direction_label(direction), state_label(state)])
+            .inc();
+    }
+
+    pub fn transfer_latency(&self, direction: &BridgeDirection, seconds: f64) {
+        self.transfer_latency
+            .with_label_values(&[direction_label(direction)])
+            .observe(seconds);
+    }
+
+    pub fn retry(&self, direction: &BridgeDirection, dead_lettered: bool) {
+        let outcome = if dead_lettered { "dead_lettered" } else { "rescheduled" };
+        self.retries
+            .with_label_values(&[direction_label(direction), outcome])
+            .inc();
+    }
+
+    pub fn chain_heights(&self, chain: &str, seen: u64, head: u64) {
+        self.chain_head.with_label_values(&[chain]).set(head as i64);
+        self.last_seen_height.with_label_values(&[chain]).set(seen as i64);
+        self.poll_lag
+            .with_label_values(&[chain])
+            .set(head.saturating_sub(seen) as i64);
+    }
+
+    pub fn rpc_result<T, E>(&self, chain: &str, result: &Result<T, E>) {
+        self.rpc_requests.with_label_values(&[chain]).inc();
+        if result.is_err() {
+            self.rpc_errors.with_label_values(&[chain]).inc();
+        }
+    }
+
+    pub fn vault(&self, mint: &str, balance: u64, expected: u64) {
+        self.vault_balance.with_label_values(&[mint]).set(balance as i64);
+        self.expected_vault_balance.with_label_values(&[mint]).set(expected as i64);
+    }
+
+    pub fn total_locked_tokens(&self, total: u64) {
+        self.total_locked_tokens.set(total as i64);
+    }
+
+    pub fn anomaly(&self, kind: &str) {
+        self.anomalies.with_label_values(&[kind]).inc();
+    }
+
+    /// Prometheus text exposition format.
+    pub fn render(&self) -> Result<String, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+}
+
+fn direction_label(direction: &BridgeDirection) -> &'static str {
+    match direction {
+        BridgeDirection::SolanaToQubic => "solana_to_qubic",
+        BridgeDirection::QubicToSolana => "qubic_to_solana",
+    }
+}
+
+fn state_label(state: TransferState) -> &'static str {
+    match state {
+        TransferState::AwaitingFinality => "awaiting_finality",
+        TransferState::Queued => "queued",
+        TransferState::Delivered => "delivered",
+        TransferState::DeadLettered => "dead_lettered",
+        TransferState::Invalidated => "invalidated",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfers_are_counted_by_direction_and_state() {
+        let metrics = RelayerMetrics::new().unwrap();
+        metrics.transfer_state(&BridgeDirection::QubicToSolana, TransferState::DeadLettered);
+        metrics.transfer_state(&BridgeDirection::QubicToSolana, TransferState::DeadLettered);
+        metrics.retry(&BridgeDirection::SolanaToQubic, false);
+
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains(r#"relayer_transfers_total{direction="qubic_to_solana",status="dead_lettered"} 2"#));
+        assert!(rendered.contains(r#"relayer_delivery_retries_total{direction="solana_to_qubic",outcome="rescheduled"} 1"#));
+    }
+
+    #[test]
+    fn poll_lag_never_goes_negative() {
+        let metrics = RelayerMetrics::new().unwrap();
+        metrics.chain_heights("qubic", 90, 100);
+        metrics.chain_heights("solana", 120, 100);
+
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains(r#"relayer_poll_lag{chain="qubic"} 10"#));
+        assert!(rendered.contains(r#"relayer_poll_lag{chain="solana"} 0"#));
+    }
+
+    #[test]
+    fn rpc_errors_are_counted_with_requests() {
+        let metrics = RelayerMetrics::new().unwrap();
+        metrics.rpc_result::<(), ()>("solana", &Ok(()));
+        metrics.rpc_result::<(), ()>("solana", &Err(()));
+
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains(r#"relayer_rpc_requests_total{chain="solana"} 2"#));
+        assert!(rendered.contains(r#"relayer_rpc_errors_total{chain="solana"} 1"#));
+    }
+}
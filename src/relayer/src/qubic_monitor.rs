@@ -1,40 +1,453 @@
 // src/relayer/src/qubic_monitor.rs
-use std::time::Duration;
-use tokio::time::interval;
-use anyhow;
+// Finds `LockAssets` calls on the HM25 bridge contract by reading each new tick's transactions
+// from the node, and turns the ones the contract accepted into jobs for Solana.
+use std::sync::Mutex;
+
+use base64::Engine;
+use log::warn;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use shared::qubic::hm25::{
+    GetBridgeInfoInput, GetBridgeInfoOutput, GetLockInfoInput, GetLockInfoOutput, LockAssetsInput,
+    FUNCTION_GET_BRIDGE_INFO, FUNCTION_GET_LOCK_INFO, PROCEDURE_LOCK_ASSETS,
+};
+use shared::qubic::identity::digest_from_lowercase_identity;
+use shared::qubic::{contract_id, identity, public_key_from_identity, QpiStruct, HM25_CONTRACT_INDEX};
+use shared::types::{BridgeDirection, BridgeTransaction};
+
+use crate::rpc_pool::QubicEndpoints;
+
+/// Where contract functions are called.
+pub const QUERY_SMART_CONTRACT: &str = "/v1/querySmartContract";
+
+/// Ticks read per poll at most; a monitor that fell behind catches up over several polls.
+const MAX_TICKS_PER_POLL: u64 = 100;
+
+/// How far the monitor has read. Persisted in the relayer's checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QubicCursor {
+    /// The last tick whose transactions have been read.
+    pub latest_tick: u64,
+    /// The id the contract gives the next lock it accepts.
+    pub next_lock_id: u64,
+}
 
 pub struct QubicMonitor {
-    interval: Duration,
+    qubic: QubicEndpoints,
+    /// The Solana mint HM25's asset is released as. Qubic locks name no asset of their own.
+    mint: Pubkey,
+    cursor: Mutex<Option<QubicCursor>>,
+}
+
+/// `GET /v1/tick-info`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickInfo {
+    pub tick: u64,
+    pub epoch: u32,
+    /// First tick of the current epoch.
+    pub initial_tick: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TickInfoResponse {
+    tick_info: TickInfo,
+}
+
+/// `GET /v2/ticks/{tick}/transactions`.
+#[derive(Debug, Deserialize)]
+struct TickTransactions {
+    #[serde(default)]
+    transactions: Vec<ListedTransaction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListedTransaction {
+    transaction: RawTransaction,
+    /// Whether the amount was transferred; `false` means the call never ran.
+    money_flew: Option<bool>,
+}
+
+/// A transaction as the node archives it. Only the fields the monitor reads.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTransaction {
+    source_id: String,
+    dest_id: String,
+    tick_number: u64,
+    input_type: u16,
+    #[serde(default)]
+    input_hex: String,
+    tx_id: String,
+}
+
+/// A `LockAssets` call, decoded.
+struct LockCall {
+    locker: [u8; 32],
+    tick: u64,
+    input: LockAssetsInput,
+    /// The transaction id's digest.
+    hash: [u8; 32],
+}
+
+impl LockCall {
+    fn decode(call: &RawTransaction) -> Result<Self, anyhow::Error> {
+        Ok(LockCall {
+            locker: public_key_from_identity(&call.source_id)?,
+            tick: call.tick_number,
+            input: LockAssetsInput::decode(&hex::decode(&call.input_hex)?)?,
+            hash: digest_from_lowercase_identity(&call.tx_id)?,
+        })
+    }
+
+    /// Whether the contract's record of a lock is this call.
+    fn recorded_as(&self, lock: &GetLockInfoOutput) -> bool {
+        lock.found
+            && lock.locker == self.locker
+            && lock.timestamp == self.tick
+            && lock.amount == self.input.amount
+            && lock.solana_destination == self.input.solana_destination
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryResponse {
+    /// Base64 of the function's output struct.
+    response_data: String,
 }
 
 impl QubicMonitor {
-    pub fn new(interval: Duration) -> Self {
-        QubicMonitor { interval }
+    pub fn new(qubic: QubicEndpoints, mint: Pubkey) -> Self {
+        QubicMonitor {
+            qubic,
+            mint,
+            cursor: Mutex::new(None),
+        }
+    }
+
+    /// Locks the contract accepted in the ticks since the last poll. The first poll starts at
+    /// the current tick; earlier locks come back from the checkpoint.
+    pub async fn get_pending_transactions(&self) -> Result<Vec<BridgeTransaction>, anyhow::Error> {
+        let head = tick_info(&self.qubic).await?.tick;
+        let Some(mut cursor) = self.cursor() else {
+            let info = self.bridge_info().await?;
+            self.restore(QubicCursor { latest_tick: head, next_lock_id: info.next_lock_id });
+            return Ok(vec![]);
+        };
+
+        let contract = identity(&contract_id(HM25_CONTRACT_INDEX));
+        let mut transactions = Vec::new();
+        for tick in cursor.latest_tick + 1..=head.min(cursor.latest_tick + MAX_TICKS_PER_POLL) {
+            for listed in self.tick_transactions(tick).await? {
+                let call = listed.transaction;
+                if call.dest_id != contract || call.input_type != PROCEDURE_LOCK_ASSETS || listed.money_flew == Some(false) {
+                    continue;
+                }
+                // A malformed call must not stall the cursor for every lock after it
+                let decoded = match LockCall::decode(&call) {
+                    Ok(decoded) => decoded,
+                    Err(e) => {
+                        warn!("Skipping unreadable LockAssets call {}: {}", call.tx_id, e);
+                        continue;
+                    }
+                };
+                // Locks are numbered in execution order, so an accepted call is always the next id
+                let lock = self.lock_info(cursor.next_lock_id).await?;
+                if !decoded.recorded_as(&lock) {
+                    warn!("LockAssets call {} was not accepted by the contract", call.tx_id);
+                    continue;
+                }
+
+                let mut tx = BridgeTransaction::new(
+                    cursor.next_lock_id,
+                    Pubkey::default(),
+                    self.mint,
+                    lock.actual_amount,
+                    BridgeDirection::QubicToSolana,
+                );
+                tx.solana_destination = Pubkey::new_from_array(decoded.input.solana_destination);
+                tx.qubic_tx_hash = Some(decoded.hash);
+                transactions.push(tx);
+                cursor.next_lock_id += 1;
+            }
+            cursor.latest_tick = tick;
+        }
+
+        // Only advance once the whole range has been read, so a failed poll is retried
+        self.restore(cursor);
+        Ok(transactions)
     }
 
-    pub async fn start(&self) {
-        let mut interval = interval(self.interval);
-        loop {
-            interval.tick().await;
-            self.check_events().await;
+    async fn tick_transactions(&self, tick: u64) -> Result<Vec<ListedTransaction>, anyhow::Error> {
+        let response = self.qubic.get(&format!("/v2/ticks/{}/transactions", tick)).await?;
+        // Empty and skipped ticks are not archived
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
         }
+        let listed: TickTransactions = response.error_for_status()?.json().await?;
+        Ok(listed.transactions)
     }
 
-    async fn check_events(&self) {
-        // Logic to monitor the Qubic Network for relevant events
-        // This could include checking for asset locks, unlocks, or other transactions
+    /// How far the monitor has read, for the checkpoint. `None` before the first poll.
+    pub fn cursor(&self) -> Option<QubicCursor> {
+        *self.cursor.lock().unwrap()
     }
-    
-    pub async fn get_pending_transactions(&self) -> Result<Vec<shared::types::BridgeTransaction>, anyhow::Error> {
-        // Implement logic to get pending transactions from Qubic
-        Ok(vec![])
+
+    /// Resume from a checkpointed cursor.
+    pub fn restore(&self, cursor: QubicCursor) {
+        *self.cursor.lock().unwrap() = Some(cursor);
     }
+
+    /// The latest tick a poll has covered and the node's current tick.
+    pub async fn chain_heights(&self) -> Result<(u64, u64), anyhow::Error> {
+        let head = tick_info(&self.qubic).await?.tick;
+        Ok((self.cursor().map_or(0, |cursor| cursor.latest_tick), head))
+    }
+
+    /// A lock as the contract itself reports it, through `GetLockInfo`.
+    pub async fn lock_info(&self, lock_id: u64) -> Result<GetLockInfoOutput, anyhow::Error> {
+        self.query(FUNCTION_GET_LOCK_INFO, &GetLockInfoInput { lock_id }).await
+    }
+
+    /// The contract's configuration and running totals, through `GetBridgeInfo`.
+    pub async fn bridge_info(&self) -> Result<GetBridgeInfoOutput, anyhow::Error> {
+        self.query(FUNCTION_GET_BRIDGE_INFO, &GetBridgeInfoInput).await
+    }
+
+    /// Call a function of the bridge contract. Functions are read-only and answered by the
+    /// node directly, without a transaction.
+    async fn query<I: QpiStruct, O: QpiStruct>(&self, input_type: u16, input: &I) -> Result<O, anyhow::Error> {
+        let response = self.qubic
+            .post_json(QUERY_SMART_CONTRACT, &query_request(input_type, input))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        query_output(response)
+    }
+}
+
+/// The node's current tick and epoch.
+pub async fn tick_info(qubic: &QubicEndpoints) -> Result<TickInfo, anyhow::Error> {
+    let response: TickInfoResponse = qubic.get("/v1/tick-info").await?.error_for_status()?.json().await?;
+    Ok(response.tick_info)
+}
+
+/// The `querySmartContract` body calling bridge function `input_type` with `input`.
+pub fn query_request<I: QpiStruct>(input_type: u16, input: &I) -> serde_json::Value {
+    serde_json::json!({
+        "contractIndex": HM25_CONTRACT_INDEX,
+        "inputType": input_type,
+        "inputSize": I::SIZE,
+        "requestData": base64::engine::general_purpose::STANDARD.encode(input.encode()),
+    })
 }
 
-pub async fn monitor() {
-    // Monitor function for standalone usage
-    println!("Monitoring Qubic...");
-    loop {
-        tokio::time::sleep(Duration::from_secs(10)).await;
+/// The function output in a `querySmartContract` answer.
+pub fn query_output<O: QpiStruct>(response: serde_json::Value) -> Result<O, anyhow::Error> {
+    let response: QueryResponse = serde_json::from_value(response)?;
+    Ok(O::decode(&base64::engine::general_purpose::STANDARD.decode(response.response_data)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode as HttpStatus;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+
+    use shared::qubic::identity::lowercase_identity;
+
+    use crate::test_support::serve;
+
+    const LOCKER: [u8; 32] = [9; 32];
+
+    /// A node at tick `tick` listing `ticks`, with `GetLockInfo` answering from `locks`. A tick
+    /// in `failing` answers 500.
+    #[derive(Clone, Default)]
+    struct Node {
+        tick: u64,
+        next_lock_id: u64,
+        ticks: Arc<Mutex<HashMap<u64, Vec<Value>>>>,
+        locks: Arc<Mutex<HashMap<u64, GetLockInfoOutput>>>,
+        failing: Option<u64>,
+    }
+
+    impl Node {
+        fn list(&self, tick: u64, transaction: Value) {
+            self.ticks.lock().unwrap().entry(tick).or_default().push(transaction);
+        }
+
+        fn record(&self, lock_id: u64, lock: GetLockInfoOutput) {
+            self.locks.lock().unwrap().insert(lock_id, lock);
+        }
+    }
+
+    async fn current_tick(State(node): State<Node>) -> Json<Value> {
+        Json(json!({ "tickInfo": { "tick": node.tick, "duration": 1, "epoch": 1, "initialTick": 1 } }))
+    }
+
+    async fn listing(State(node): State<Node>, Path(tick): Path<u64>) -> Result<Json<Value>, HttpStatus> {
+        if node.failing == Some(tick) {
+            return Err(HttpStatus::INTERNAL_SERVER_ERROR);
+        }
+        let ticks = node.ticks.lock().unwrap();
+        let transactions = ticks.get(&tick).ok_or(HttpStatus::NOT_FOUND)?;
+        Ok(Json(json!({ "transactions": transactions })))
+    }
+
+    async fn query(State(node): State<Node>, Json(request): Json<Value>) -> Json<Value> {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let input = engine.decode(request["requestData"].as_str().unwrap()).unwrap();
+        let output = match request["inputType"].as_u64().unwrap() as u16 {
+            FUNCTION_GET_BRIDGE_INFO => {
+                let mut info = GetBridgeInfoOutput::decode(&[0; GetBridgeInfoOutput::SIZE]).unwrap();
+                info.next_lock_id = node.next_lock_id;
+                info.encode()
+            }
+            _ => {
+                let lock_id = GetLockInfoInput::decode(&input).unwrap().lock_id;
+                let locks = node.locks.lock().unwrap();
+                locks.get(&lock_id).cloned().unwrap_or_else(not_found).encode()
+            }
+        };
+        Json(json!({ "responseData": engine.encode(output) }))
+    }
+
+    async fn monitor(node: &Node) -> QubicMonitor {
+        let url = serve(
+            Router::new()
+                .route("/v1/tick-info", get(current_tick))
+                .route("/v2/ticks/:tick/transactions", get(listing))
+                .route(QUERY_SMART_CONTRACT, post(query))
+                .with_state(node.clone()),
+        )
+        .await;
+        QubicMonitor::new(QubicEndpoints::new(&[url], None), Pubkey::new_from_array([4; 32]))
+    }
+
+    fn not_found() -> GetLockInfoOutput {
+        GetLockInfoOutput::decode(&[0; GetLockInfoOutput::SIZE]).unwrap()
     }
-}
\ No newline at end of file
+
+    /// A call of `input_type` on `destination` in `tick`, as the node lists it.
+    fn call(destination: [u8; 32], tick: u64, input_type: u16, input: &[u8], hash: u8, money_flew: bool) -> Value {
+        json!({
+            "transaction": {
+                "sourceId": identity(&LOCKER),
+                "destId": identity(&destination),
+                "amount": "500",
+                "tickNumber": tick,
+                "inputType": input_type,
+                "inputSize": input.len(),
+                "inputHex": hex::encode(input),
+                "signatureHex": hex::encode([0; 64]),
+                "txId": lowercase_identity(&[hash; 32]),
+            },
+            "timestamp": "0",
+            "moneyFlew": money_flew,
+        })
+    }
+
+    fn lock_input(amount: u64) -> LockAssetsInput {
+        LockAssetsInput { solana_destination: [3; 32], amount, memo: [0; 64] }
+    }
+
+    fn lock_call(tick: u64, input: &LockAssetsInput, hash: u8) -> Value {
+        call(contract_id(HM25_CONTRACT_INDEX), tick, PROCEDURE_LOCK_ASSETS, &input.encode(), hash, true)
+    }
+
+    /// What the contract records for `input` locked in `tick`, after a fee of 10.
+    fn recorded(tick: u64, input: &LockAssetsInput) -> GetLockInfoOutput {
+        GetLockInfoOutput {
+            found: true,
+            locker: LOCKER,
+            amount: input.amount,
+            actual_amount: input.amount - 10,
+            fee: 10,
+            timestamp: tick,
+            solana_destination: input.solana_destination,
+            memo: input.memo,
+            status: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn the_first_poll_starts_at_the_head() {
+        let node = Node { tick: 100, next_lock_id: 4, ..Node::default() };
+        node.list(100, lock_call(100, &lock_input(500), 1));
+        let monitor = monitor(&node).await;
+
+        assert!(monitor.get_pending_transactions().await.unwrap().is_empty());
+        assert_eq!(monitor.cursor(), Some(QubicCursor { latest_tick: 100, next_lock_id: 4 }));
+    }
+
+    #[tokio::test]
+    async fn accepted_locks_are_decoded_into_jobs() {
+        let node = Node { tick: 12, ..Node::default() };
+        let input = lock_input(500);
+        node.list(11, lock_call(11, &input, 0xaa));
+        node.record(1, recorded(11, &input));
+        let monitor = monitor(&node).await;
+        monitor.restore(QubicCursor { latest_tick: 10, next_lock_id: 1 });
+
+        let jobs = monitor.get_pending_transactions().await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        let job = &jobs[0];
+        assert_eq!((job.id, job.direction, job.amount), (1, BridgeDirection::QubicToSolana, 490));
+        assert_eq!(job.token_mint, Pubkey::new_from_array([4; 32]));
+        assert_eq!(job.solana_destination, Pubkey::new_from_array([3; 32]));
+        assert_eq!(job.qubic_tx_hash, Some([0xaa; 32]));
+        // Tick 12 was empty, and is still read past
+        assert_eq!(monitor.cursor(), Some(QubicCursor { latest_tick: 12, next_lock_id: 2 }));
+    }
+
+    #[tokio::test]
+    async fn calls_that_are_not_accepted_locks_are_skipped() {
+        let node = Node { tick: 11, ..Node::default() };
+        let hm25 = contract_id(HM25_CONTRACT_INDEX);
+        let input = lock_input(500);
+        node.list(11, call(contract_id(1), 11, PROCEDURE_LOCK_ASSETS, &input.encode(), 1, true));
+        node.list(11, call(hm25, 11, PROCEDURE_LOCK_ASSETS + 1, &input.encode(), 2, true));
+        node.list(11, call(hm25, 11, PROCEDURE_LOCK_ASSETS, &input.encode(), 3, false));
+        node.list(11, call(hm25, 11, PROCEDURE_LOCK_ASSETS, &input.encode()[..80], 4, true));
+        // Well-formed, but the contract has no lock made in tick 11 for it
+        node.list(11, lock_call(11, &input, 5));
+        node.record(1, recorded(9, &input));
+        let monitor = monitor(&node).await;
+        monitor.restore(QubicCursor { latest_tick: 10, next_lock_id: 1 });
+
+        assert!(monitor.get_pending_transactions().await.unwrap().is_empty());
+        assert_eq!(monitor.cursor(), Some(QubicCursor { latest_tick: 11, next_lock_id: 1 }));
+    }
+
+    #[tokio::test]
+    async fn a_failed_poll_leaves_the_cursor_and_a_poll_reads_a_bounded_range() {
+        let cursor = QubicCursor { latest_tick: 10, next_lock_id: 1 };
+        let node = Node { tick: 10 + 3 * MAX_TICKS_PER_POLL, failing: Some(12), ..Node::default() };
+        let input = lock_input(500);
+        node.list(11, lock_call(11, &input, 0xaa));
+        node.record(1, recorded(11, &input));
+        let failing = monitor(&node).await;
+        failing.restore(cursor);
+
+        // The lock in tick 11 comes again on the next poll rather than being lost
+        assert!(failing.get_pending_transactions().await.is_err());
+        assert_eq!(failing.cursor(), Some(cursor));
+
+        let recovered = monitor(&Node { failing: None, ..node }).await;
+        recovered.restore(cursor);
+        assert_eq!(recovered.get_pending_transactions().await.unwrap().len(), 1);
+        assert_eq!(recovered.cursor().unwrap().latest_tick, 10 + MAX_TICKS_PER_POLL);
+    }
+}
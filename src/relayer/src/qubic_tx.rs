@@ -0,0 +1,211 @@
+// src/relayer/src/qubic_tx.rs
+// Signed Qubic transactions on the wire. A transaction names the tick it must land in and is
+// dropped by the network once that tick passes without it, so the broadcaster aims a few ticks
+// ahead, waits for the target to be processed, checks inclusion and re-aims if it missed.
+use std::path::Path;
+
+use anyhow::Context;
+use base64::Engine;
+use log::{info, warn};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, Duration};
+
+use shared::qubic::{QubicKeypair, QubicTransaction};
+
+use crate::rpc_pool::QubicEndpoints;
+
+/// Ticks take a few seconds; checking more often only adds load.
+const TICK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QubicTxConfig {
+    /// File holding the 55-letter seed of the identity procedure calls are sent from.
+    #[serde(default)]
+    pub seed_path: Option<String>,
+    /// How far past the current tick to aim. Too close and the transaction arrives late.
+    #[serde(default = "default_tick_offset")]
+    pub tick_offset: u32,
+    /// Attempts at getting one procedure call included, each aimed at a fresh tick.
+    #[serde(default = "default_max_broadcasts")]
+    pub max_broadcasts: u32,
+}
+
+fn default_tick_offset() -> u32 {
+    5
+}
+
+fn default_max_broadcasts() -> u32 {
+    3
+}
+
+impl Default for QubicTxConfig {
+    fn default() -> Self {
+        QubicTxConfig { seed_path: None, tick_offset: default_tick_offset(), max_broadcasts: default_max_broadcasts() }
+    }
+}
+
+/// Read a seed file; surrounding whitespace is ignored.
+pub fn read_seed_file(path: impl AsRef<Path>) -> Result<QubicKeypair, anyhow::Error> {
+    let path = path.as_ref();
+    let seed = std::fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))?;
+    QubicKeypair::from_seed(&seed).with_context(|| format!("{} does not hold a Qubic seed", path.display()))
+}
+
+/// Where a broadcast transaction stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inclusion {
+    /// The target tick has not been processed yet.
+    Pending,
+    /// In its tick. For a contract call that only means the procedure was run; whether it
+    /// did anything is for the caller to check against the contract's state.
+    Included,
+    /// The target tick was processed without it; it can never land now.
+    Missed,
+}
+
+/// A transaction that was accepted for broadcast.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Broadcast {
+    pub id: String,
+    /// The signed digest `id` encodes; what the Solana lock records as its delivery.
+    pub digest: [u8; 32],
+    pub tick: u32,
+    /// Peers the node passed it on to.
+    pub peers: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TickInfoResponse {
+    tick_info: TickInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickInfo {
+    tick: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusResponse {
+    last_processed_tick: ProcessedTick,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProcessedTick {
+    tick_number: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BroadcastResponse {
+    peers_broadcasted: u32,
+    transaction_id: String,
+}
+
+pub struct QubicBroadcaster {
+    qubic: QubicEndpoints,
+    keypair: QubicKeypair,
+    config: QubicTxConfig,
+}
+
+impl QubicBroadcaster {
+    pub fn new(qubic: QubicEndpoints, keypair: QubicKeypair, config: QubicTxConfig) -> Self {
+        QubicBroadcaster { qubic, keypair, config }
+    }
+
+    /// The identity transactions are sent from.
+    pub fn identity(&self) -> String {
+        self.keypair.identity()
+    }
+
+    /// The tick the network is currently on.
+    pub async fn current_tick(&self) -> Result<u32, anyhow::Error> {
+        let info: TickInfoResponse = self.qubic.get("/v1/tick-info").await?.error_for_status()?.json().await?;
+        Ok(info.tick_info.tick)
+    }
+
+    /// The latest tick whose transactions are final and queryable.
+    pub async fn last_processed_tick(&self) -> Result<u32, anyhow::Error> {
+        let status: StatusResponse = self.qubic.get("/v1/status").await?.error_for_status()?.json().await?;
+        Ok(status.last_processed_tick.tick_number)
+    }
+
+    /// A transaction from this broadcaster's identity, signed for `tick`.
+    pub fn build(
+        &self,
+        destination: [u8; 32],
+        amount: i64,
+        tick: u32,
+        input_type: u16,
+        input: Vec<u8>,
+    ) -> Result<QubicTransaction, anyhow::Error> {
+        let mut transaction =
+            QubicTransaction::new(self.keypair.public_key(), destination, amount, tick, input_type, input)?;
+        transaction.sign(&self.keypair)?;
+        Ok(transaction)
+    }
+
+    pub async fn broadcast(&self, transaction: &QubicTransaction) -> Result<Broadcast, anyhow::Error> {
+        let body = serde_json::json!({
+            "encodedTransaction": base64::engine::general_purpose::STANDARD.encode(transaction.to_bytes()),
+        });
+        let response: BroadcastResponse =
+            self.qubic.post_json("/v1/broadcast-transaction", &body).await?.error_for_status()?.json().await?;
+
+        let id = transaction.id();
+        if response.transaction_id != id {
+            warn!("Qubic node reported transaction {} as {}", id, response.transaction_id);
+        }
+        Ok(Broadcast { id, digest: transaction.digest(), tick: transaction.tick, peers: response.peers_broadcasted })
+    }
+
+    /// Whether `broadcast` made it into its tick. Only known once that tick is processed.
+    pub async fn inclusion(&self, broadcast: &Broadcast) -> Result<Inclusion, anyhow::Error> {
+        if self.last_processed_tick().await? < broadcast.tick {
+            return Ok(Inclusion::Pending);
+        }
+        let response = self.qubic.get(&format!("/v2/transactions/{}", broadcast.id)).await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(Inclusion::Missed),
+            _ => {
+                response.error_for_status()?;
+                Ok(Inclusion::Included)
+            }
+        }
+    }
+
+    /// Build, broadcast and follow a transaction until it is included, aiming at a fresh tick
+    /// each time a target passes without it.
+    pub async fn submit(
+        &self,
+        destination: [u8; 32],
+        amount: i64,
+        input_type: u16,
+        input: &[u8],
+    ) -> Result<Broadcast, anyhow::Error> {
+        for attempt in 1..=self.config.max_broadcasts {
+            let tick = self.current_tick().await? + self.config.tick_offset;
+            let transaction = self.build(destination, amount, tick, input_type, input.to_vec())?;
+            let broadcast = self.broadcast(&transaction).await?;
+            info!("Broadcast Qubic transaction {} for tick {} to {} peers", broadcast.id, tick, broadcast.peers);
+
+            loop {
+                match self.inclusion(&broadcast).await? {
+                    Inclusion::Pending => sleep(TICK_POLL_INTERVAL).await,
+                    Inclusion::Included => return Ok(broadcast),
+                    Inclusion::Missed => break,
+                }
+            }
+            warn!(
+                "Qubic transaction {} missed tick {} (attempt {}/{})",
+                broadcast.id, tick, attempt, self.config.max_broadcasts
+            );
+        }
+
+        Err(anyhow::anyhow!("Qubic transaction not included after {} broadcasts", self.config.max_broadcasts))
+    }
+}
@@ -0,0 +1,303 @@
+// src/relayer/src/retry_scheduler.rs
+// Delivery retries with jittered exponential backoff. Jobs that fail fatally or
+// run out of attempts are parked in a dead-letter list until an operator requeues them.
+use std::collections::HashMap;
+
+use log::{error, warn};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_sdk::transaction::TransactionError;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tokio::time::Duration;
+
+use shared::types::{BridgeDirection, BridgeTransaction};
+
+/// The destination chain answered, but refused the delivery.
+#[derive(Debug, Error)]
+#[error("delivery rejected with HTTP {0}")]
+pub struct DeliveryRejected(pub u16);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorClass {
+    Retryable,
+    Fatal,
+}
+
+/// Decide whether retrying can possibly help. Transport failures, timeouts, rate limits
+/// and server errors are retryable; rejected requests and failed transactions are not.
+/// Anything unrecognised is retried, bounded by the policy's attempt limit.
+pub fn classify(err: &anyhow::Error) -> ErrorClass {
+    if let Some(DeliveryRejected(status)) = err.downcast_ref::<DeliveryRejected>() {
+        return classify_status(*status);
+    }
+
+    if let Some(e) = err.downcast_ref::<reqwest::Error>() {
+        return match e.status() {
+            Some(status) => classify_status(status.as_u16()),
+            None if e.is_builder() || e.is_decode() => ErrorClass::Fatal,
+            None => ErrorClass::Retryable,
+        };
+    }
+
+    if let Some(e) = err.downcast_ref::<ClientError>() {
+        // An expired blockhash only means the transaction has to be rebuilt
+        return match (e.get_transaction_error(), e.kind()) {
+            (Some(TransactionError::BlockhashNotFound), _) => ErrorClass::Retryable,
+            (Some(_), _) | (None, ClientErrorKind::SigningError(_)) => ErrorClass::Fatal,
+            _ => ErrorClass::Retryable,
+        };
+    }
+
+    ErrorClass::Retryable
+}
+
+fn classify_status(status: u16) -> ErrorClass {
+    match status {
+        408 | 425 | 429 => ErrorClass::Retryable,
+        400..=499 => ErrorClass::Fatal,
+        _ => ErrorClass::Retryable,
+    }
+}
+
+/// What happened to a job after a failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureOutcome {
+    Rescheduled(Duration),
+    DeadLettered(ErrorClass),
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        RetryPolicy {
+            max_attempts,
+            base_delay,
+            max_delay: base_delay.saturating_mul(32),
+        }
+    }
+
+    /// Delay before retry number `attempt` (1-based): `base * 2^(attempt-1)`, capped at
+    /// `max_delay`, with the upper half jittered so relayers don't retry in lockstep.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        half + half.mul_f64(rand::thread_rng().gen::<f64>())
+    }
+}
+
+struct Job {
+    tx: BridgeTransaction,
+    next_attempt_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub transaction: BridgeTransaction,
+    pub error: String,
+    pub class: ErrorClass,
+    pub failed_at: i64,
+}
+
+pub struct RetryScheduler {
+    policy: RetryPolicy,
+    jobs: Mutex<HashMap<(BridgeDirection, u64), Job>>,
+    dead_letters: Mutex<HashMap<(BridgeDirection, u64), DeadLetter>>,
+}
+
+impl RetryScheduler {
+    pub fn new(policy: RetryPolicy) -> Self {
+        RetryScheduler {
+            policy,
+            jobs: Mutex::new(HashMap::new()),
+            dead_letters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Queue a delivery for immediate attempt. Already queued or dead-lettered jobs are left alone.
+    pub async fn schedule(&self, tx: BridgeTransaction) {
+        if self.dead_letters.lock().await.contains_key(&tx.key()) {
+            return;
+        }
+
+        self.jobs.lock().await.entry(tx.key()).or_insert(Job {
+            tx,
+            next_attempt_at: now_millis(),
+        });
+    }
+
+    /// Remove and return every job whose backoff has elapsed.
+    pub async fn take_due(&self) -> Vec<BridgeTransaction> {
+        let now = now_millis();
+        let mut jobs = self.jobs.lock().await;
+        let due: Vec<(BridgeDirection, u64)> = jobs
+            .iter()
+            .filter(|(_, job)| job.next_attempt_at <= now)
+            .map(|(key, _)| *key)
+            .collect();
+
+        due.into_iter()
+            .filter_map(|key| jobs.remove(&key))
+            .map(|job| job.tx)
+            .collect()
+    }
+
+    /// Record a failed attempt: reschedule with backoff, or dead-letter the job if the
+    /// error is fatal or the attempt budget is spent.
+    pub async fn record_failure(&self, mut tx: BridgeTransaction, err: anyhow::Error) -> FailureOutcome {
+        let class = classify(&err);
+        tx.retry_count += 1;
+        tx.last_retry_at = Some(chrono::Utc::now().timestamp());
+
+        if class == ErrorClass::Fatal || tx.retry_count >= self.policy.max_attempts {
+            error!("Dead-lettering transaction {} after {} attempt(s): {}", tx.id, tx.retry_count, err);
+            self.dead_letters.lock().await.insert(tx.key(), DeadLetter {
+                transaction: tx,
+                error: err.to_string(),
+                class,
+                failed_at: chrono::Utc::now().timestamp(),
+            });
+            return FailureOutcome::DeadLettered(class);
+        }
+
+        let delay = self.policy.backoff(tx.retry_count);
+        warn!("Transaction {} attempt {} failed, retrying in {:?}: {}", tx.id, tx.retry_count, delay, err);
+        self.jobs.lock().await.insert(tx.key(), Job {
+            next_attempt_at: now_millis() + delay.as_millis() as i64,
+            tx,
+        });
+
+        FailureOutcome::Rescheduled(delay)
+    }
+
+    pub async fn dead_letters(&self) -> Vec<DeadLetter> {
+        let mut dead: Vec<_> = self.dead_letters.lock().await.values().cloned().collect();
+        dead.sort_by_key(|d| d.transaction.id);
+        dead
+    }
+
+    /// Move a dead-lettered job back into the queue with a fresh attempt budget.
+    pub async fn requeue(&self, direction: BridgeDirection, id: u64) -> bool {
+        let Some(dead) = self.dead_letters.lock().await.remove(&(direction, id)) else {
+            return false;
+        };
+
+        let mut tx = dead.transaction;
+        tx.retry_count = 0;
+        self.schedule(tx).await;
+        true
+    }
+
+    /// Put back a dead letter from a checkpoint.
+    pub async fn restore_dead_letter(&self, dead: DeadLetter) {
+        self.dead_letters.lock().await.insert(dead.transaction.key(), dead);
+    }
+
+    pub async fn queued(&self) -> usize {
+        self.jobs.lock().await.len()
+    }
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::transaction;
+
+    fn scheduler(max_attempts: u32) -> RetryScheduler {
+        RetryScheduler::new(RetryPolicy::new(max_attempts, Duration::from_millis(100)))
+    }
+
+    #[test]
+    fn backoff_stays_in_the_jittered_upper_half_and_is_capped() {
+        let policy = RetryPolicy::new(10, Duration::from_millis(100));
+        for attempt in 1..=40 {
+            let exponential = Duration::from_millis(100)
+                .saturating_mul(1u32 << (attempt - 1).min(16))
+                .min(policy.max_delay);
+            for _ in 0..50 {
+                let delay = policy.backoff(attempt);
+                assert!(delay >= exponential / 2 && delay <= exponential, "attempt {}: {:?}", attempt, delay);
+            }
+        }
+        assert_eq!(policy.max_delay, Duration::from_millis(3_200));
+    }
+
+    #[test]
+    fn client_errors_are_fatal_except_timeouts_and_rate_limits() {
+        assert_eq!(classify(&DeliveryRejected(400).into()), ErrorClass::Fatal);
+        assert_eq!(classify(&DeliveryRejected(404).into()), ErrorClass::Fatal);
+        assert_eq!(classify(&DeliveryRejected(408).into()), ErrorClass::Retryable);
+        assert_eq!(classify(&DeliveryRejected(429).into()), ErrorClass::Retryable);
+        assert_eq!(classify(&DeliveryRejected(503).into()), ErrorClass::Retryable);
+        assert_eq!(classify(&anyhow::anyhow!("connection reset")), ErrorClass::Retryable);
+    }
+
+    #[tokio::test]
+    async fn retryable_failures_back_off_until_the_attempt_budget_is_spent() {
+        let scheduler = scheduler(3);
+        let tx = transaction(1, BridgeDirection::SolanaToQubic);
+        scheduler.schedule(tx.clone()).await;
+        let tx = scheduler.take_due().await.pop().unwrap();
+
+        let outcome = scheduler.record_failure(tx, anyhow::anyhow!("timeout")).await;
+        assert!(matches!(outcome, FailureOutcome::Rescheduled(delay) if delay <= Duration::from_millis(100)));
+        assert_eq!(scheduler.queued().await, 1);
+        assert!(scheduler.take_due().await.is_empty(), "a rescheduled job waits out its backoff");
+
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        let tx = scheduler.take_due().await.pop().unwrap();
+        assert_eq!(tx.retry_count, 1);
+        let outcome = scheduler.record_failure(tx, anyhow::anyhow!("timeout")).await;
+        assert!(matches!(outcome, FailureOutcome::Rescheduled(_)));
+
+        tokio::time::sleep(Duration::from_millis(220)).await;
+        let tx = scheduler.take_due().await.pop().unwrap();
+        let outcome = scheduler.record_failure(tx, anyhow::anyhow!("timeout")).await;
+        assert_eq!(outcome, FailureOutcome::DeadLettered(ErrorClass::Retryable));
+        assert_eq!(scheduler.queued().await, 0);
+        assert_eq!(scheduler.dead_letters().await[0].transaction.retry_count, 3);
+    }
+
+    #[tokio::test]
+    async fn fatal_failures_dead_letter_at_once_and_requeue_resets_the_budget() {
+        let scheduler = scheduler(5);
+        let tx = transaction(7, BridgeDirection::QubicToSolana);
+
+        let outcome = scheduler.record_failure(tx.clone(), DeliveryRejected(400).into()).await;
+        assert_eq!(outcome, FailureOutcome::DeadLettered(ErrorClass::Fatal));
+
+        // Dead letters stay parked until an operator requeues them
+        scheduler.schedule(tx.clone()).await;
+        assert_eq!(scheduler.queued().await, 0);
+        assert!(!scheduler.requeue(BridgeDirection::SolanaToQubic, 7).await, "the key includes the direction");
+
+        assert!(scheduler.requeue(BridgeDirection::QubicToSolana, 7).await);
+        assert!(scheduler.dead_letters().await.is_empty());
+        let requeued = scheduler.take_due().await.pop().unwrap();
+        assert_eq!(requeued.key(), tx.key());
+        assert_eq!(requeued.retry_count, 0);
+    }
+
+    #[tokio::test]
+    async fn the_same_lock_id_is_scheduled_once_per_direction() {
+        let scheduler = scheduler(5);
+        scheduler.schedule(transaction(3, BridgeDirection::SolanaToQubic)).await;
+        scheduler.schedule(transaction(3, BridgeDirection::SolanaToQubic)).await;
+        scheduler.schedule(transaction(3, BridgeDirection::QubicToSolana)).await;
+        assert_eq!(scheduler.queued().await, 2);
+    }
+}
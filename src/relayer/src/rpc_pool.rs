@@ -0,0 +1,498 @@
+// src/relayer/src/rpc_pool.rs
+// Several RPC endpoints per chain behind one interface. Calls go to the healthiest endpoint
+// and fail over on transport errors; quorum reads ask every endpoint and compare answers.
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use log::warn;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::Serialize;
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::RpcClientConfig;
+use solana_client::rpc_custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY;
+use solana_client::rpc_request::{RpcError, RpcRequest};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+use tokio::time::{Duration, Instant};
+
+use crate::solana_rpc::rpc_client;
+
+/// An endpoint that just failed is tried last for this long.
+const FAILURE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Weight of the newest sample in an endpoint's latency average.
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+const QUBIC_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum QuorumError {
+    #[error("only {agreeing} of {endpoints} endpoints agree, {required} required")]
+    NotReached {
+        agreeing: usize,
+        required: usize,
+        endpoints: usize,
+    },
+}
+
+/// Health of one endpoint as reported on the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub url: String,
+    pub consecutive_failures: u32,
+    pub latency_ms: u64,
+    pub cooling_down: bool,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    latency_ms: f64,
+    last_failure: Option<Instant>,
+}
+
+impl Health {
+    fn record(&mut self, ok: bool, elapsed: Duration) {
+        let sample = elapsed.as_secs_f64() * 1000.0;
+        self.latency_ms = if self.latency_ms == 0.0 {
+            sample
+        } else {
+            self.latency_ms + LATENCY_SMOOTHING * (sample - self.latency_ms)
+        };
+
+        if ok {
+            self.consecutive_failures = 0;
+        } else {
+            self.consecutive_failures += 1;
+            self.last_failure = Some(Instant::now());
+        }
+    }
+
+    fn cooling_down(&self) -> bool {
+        self.consecutive_failures > 0 && self.last_failure.is_some_and(|at| at.elapsed() < FAILURE_COOLDOWN)
+    }
+}
+
+struct Endpoints<T> {
+    endpoints: Vec<(String, T)>,
+    health: Vec<Mutex<Health>>,
+}
+
+impl<T> Endpoints<T> {
+    fn new(endpoints: Vec<(String, T)>) -> Self {
+        assert!(!endpoints.is_empty(), "at least one RPC endpoint is required");
+        let health = endpoints.iter().map(|_| Mutex::new(Health::default())).collect();
+        Endpoints { endpoints, health }
+    }
+
+    /// Indices from best to worst: endpoints cooling down after a failure go last, then
+    /// more consecutive failures, then higher latency. Ties keep the configured order.
+    fn ordered(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by_key(|&index| {
+            let health = self.health[index].lock().unwrap();
+            (health.cooling_down(), health.consecutive_failures, health.latency_ms as u64)
+        });
+        order
+    }
+
+    fn record(&self, index: usize, ok: bool, elapsed: Duration) {
+        self.health[index].lock().unwrap().record(ok, elapsed);
+    }
+
+    fn status(&self) -> Vec<EndpointStatus> {
+        self.endpoints
+            .iter()
+            .zip(&self.health)
+            .map(|((url, _), health)| {
+                let health = health.lock().unwrap();
+                EndpointStatus {
+                    url: url.clone(),
+                    consecutive_failures: health.consecutive_failures,
+                    latency_ms: health.latency_ms as u64,
+                    cooling_down: health.cooling_down(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The answer at least `required` endpoints gave. Endpoints that errored count as abstaining.
+fn agreed<V: PartialEq>(answers: Vec<V>, required: usize, endpoints: usize) -> Result<V, QuorumError> {
+    let mut tally: Vec<(V, usize)> = Vec::new();
+    for answer in answers {
+        match tally.iter_mut().find(|(value, _)| *value == answer) {
+            Some((_, count)) => *count += 1,
+            None => tally.push((answer, 1)),
+        }
+    }
+
+    let (value, agreeing) = tally
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map_or((None, 0), |(value, count)| (Some(value), count));
+    match value {
+        Some(value) if agreeing >= required => Ok(value),
+        _ => Err(QuorumError::NotReached { agreeing, required, endpoints }),
+    }
+}
+
+/// `RpcSender` that tries each Solana endpoint in health order. Only transport failures
+/// and unhealthy nodes fail over; an RPC error the node answered with is returned as-is.
+struct FailoverSender {
+    endpoints: Arc<Endpoints<RpcClient>>,
+}
+
+#[async_trait]
+impl RpcSender for FailoverSender {
+    async fn send(&self, request: RpcRequest, params: serde_json::Value) -> ClientResult<serde_json::Value> {
+        let mut last_error = None;
+
+        for index in self.endpoints.ordered() {
+            let (url, client) = &self.endpoints.endpoints[index];
+            let started = Instant::now();
+            let result = client.send(request, params.clone()).await;
+
+            let failed = result.as_ref().err().is_some_and(is_endpoint_failure);
+            self.endpoints.record(index, !failed, started.elapsed());
+            match result {
+                Err(e) if failed => {
+                    warn!("Solana RPC {} failed {}: {}", url, request, e);
+                    last_error = Some(e);
+                }
+                result => return result,
+            }
+        }
+
+        Err(last_error.expect("at least one RPC endpoint is configured"))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        let mut total = RpcTransportStats::default();
+        for (_, client) in &self.endpoints.endpoints {
+            let stats = client.get_transport_stats();
+            total.request_count += stats.request_count;
+            total.elapsed_time += stats.elapsed_time;
+            total.rate_limited_time += stats.rate_limited_time;
+        }
+        total
+    }
+
+    fn url(&self) -> String {
+        let best = self.endpoints.ordered()[0];
+        self.endpoints.endpoints[best].0.clone()
+    }
+}
+
+fn is_endpoint_failure(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => true,
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+            *code == JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+        }
+        _ => false,
+    }
+}
+
+/// Solana endpoints: one failover client for everyday reads and writes, plus direct
+/// access to each endpoint for quorum reads.
+#[derive(Clone)]
+pub struct SolanaEndpoints {
+    endpoints: Arc<Endpoints<RpcClient>>,
+    client: Arc<RpcClient>,
+    quorum: Option<usize>,
+}
+
+impl SolanaEndpoints {
+    pub fn new(urls: &[String], timeout: Duration, quorum: Option<usize>) -> Self {
+        let endpoints = Arc::new(Endpoints::new(
+            urls.iter().map(|url| (url.clone(), rpc_client(url, timeout))).collect(),
+        ));
+        let client = Arc::new(RpcClient::new_sender(
+            FailoverSender { endpoints: endpoints.clone() },
+            RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+        ));
+
+        SolanaEndpoints { endpoints, client, quorum }
+    }
+
+    /// The failover client, shared by monitors, trackers and submissions.
+    pub fn client(&self) -> Arc<RpcClient> {
+        self.client.clone()
+    }
+
+    /// How many endpoints must agree before a lock is acted on, if quorum reads are enabled.
+    pub fn quorum(&self) -> Option<usize> {
+        self.quorum
+    }
+
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        self.endpoints.status()
+    }
+
+    /// Owner and data of `address` as at least `required` endpoints independently report it,
+    /// or `None` if they agree the account does not exist.
+    pub async fn quorum_account(&self, address: &Pubkey, required: usize) -> Result<Option<(Pubkey, Vec<u8>)>, QuorumError> {
+        let answers = join_all(
+            self.endpoints
+                .endpoints
+                .iter()
+                .map(|(_, client)| client.get_account_with_commitment(address, CommitmentConfig::confirmed())),
+        )
+        .await;
+
+        let answers = answers
+            .into_iter()
+            .filter_map(Result::ok)
+            .map(|response| response.value.map(|account| (account.owner, account.data)))
+            .collect();
+        agreed(answers, required, self.endpoints.endpoints.len())
+    }
+}
+
+/// Qubic endpoints behind the same health scoring. Connection errors, 429s and 5xx responses
+/// fail over; any other response is the answer.
+#[derive(Clone)]
+pub struct QubicEndpoints {
+    endpoints: Arc<Endpoints<String>>,
+    http_client: Client,
+    quorum: Option<usize>,
+}
+
+impl QubicEndpoints {
+    pub fn new(urls: &[String], quorum: Option<usize>) -> Self {
+        let http_client = Client::builder()
+            .timeout(QUBIC_REQUEST_TIMEOUT)
+            .build()
+            .expect("static reqwest client configuration");
+
+        QubicEndpoints {
+            endpoints: Arc::new(Endpoints::new(urls.iter().map(|url| (url.clone(), url.clone())).collect())),
+            http_client,
+            quorum,
+        }
+    }
+
+    pub fn quorum(&self) -> Option<usize> {
+        self.quorum
+    }
+
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        self.endpoints.status()
+    }
+
+    /// `GET {endpoint}{path}` on the healthiest endpoint that answers.
+    pub async fn get(&self, path: &str) -> Result<Response, anyhow::Error> {
+        self.request(|client, url| client.get(format!("{}{}", url, path))).await
+    }
+
+    pub async fn post_json<B: Serialize>(&self, path: &str, body: &B) -> Result<Response, anyhow::Error> {
+        self.request(|client, url| client.post(format!("{}{}", url, path)).json(body)).await
+    }
+
+    async fn request(&self, build: impl Fn(&Client, &str) -> RequestBuilder) -> Result<Response, anyhow::Error> {
+        let mut last_failure = None;
+
+        for index in self.endpoints.ordered() {
+            let (url, _) = &self.endpoints.endpoints[index];
+            let started = Instant::now();
+            let result = build(&self.http_client, url).send().await;
+
+            let failed = match &result {
+                Ok(response) => is_server_failure(response.status()),
+                Err(_) => true,
+            };
+            self.endpoints.record(index, !failed, started.elapsed());
+            if !failed {
+                return Ok(result?);
+            }
+
+            match &result {
+                Ok(response) => warn!("Qubic RPC {} answered {}", url, response.status()),
+                Err(e) => warn!("Qubic RPC {} failed: {}", url, e),
+            }
+            last_failure = Some(result);
+        }
+
+        // Every endpoint failed: hand back the last answer so callers see its status
+        Ok(last_failure.expect("at least one RPC endpoint is configured")?)
+    }
+
+    /// `POST {path}` with a JSON body to every endpoint, compared after `decode`. Endpoints
+    /// whose answer does not decode abstain, so a quorum is reached on contents, not bytes.
+    pub async fn quorum_post_json<B, V>(
+        &self,
+        path: &str,
+        body: &B,
+        required: usize,
+        decode: impl Fn(serde_json::Value) -> Result<V, anyhow::Error>,
+    ) -> Result<V, QuorumError>
+    where
+        B: Serialize,
+        V: PartialEq,
+    {
+        let decode = &decode;
+        self.quorum_read(required, |client, url| client.post(format!("{}{}", url, path)).json(body), |response| async move {
+            let body = response.error_for_status().ok()?.json::<serde_json::Value>().await.ok()?;
+            decode(body).ok()
+        })
+        .await
+    }
+
+    async fn quorum_read<V, Fut>(
+        &self,
+        required: usize,
+        build: impl Fn(&Client, &str) -> RequestBuilder,
+        read: impl Fn(Response) -> Fut,
+    ) -> Result<V, QuorumError>
+    where
+        V: PartialEq,
+        Fut: std::future::Future<Output = Option<V>>,
+    {
+        let answers = join_all(self.endpoints.endpoints.iter().map(|(url, _)| {
+            let request = build(&self.http_client, url).send();
+            let read = &read;
+            async move { read(request.await.ok()?).await }
+        }))
+        .await;
+
+        agreed(answers.into_iter().flatten().collect(), required, self.endpoints.endpoints.len())
+    }
+}
+
+fn is_server_failure(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode as HttpStatus;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+
+    use crate::test_support::{serve, unreachable_url};
+
+    fn endpoints(count: usize) -> Endpoints<()> {
+        Endpoints::new((0..count).map(|index| (format!("endpoint-{}", index), ())).collect())
+    }
+
+    #[test]
+    fn quorum_needs_enough_endpoints_giving_the_same_answer() {
+        assert_eq!(agreed(vec![1, 1, 2], 2, 3).unwrap(), 1);
+
+        let disagreement = agreed(vec![1, 2, 3], 2, 3).unwrap_err();
+        assert!(matches!(disagreement, QuorumError::NotReached { agreeing: 1, required: 2, endpoints: 3 }));
+
+        // Endpoints that errored abstain rather than count against the answer
+        let abstained = agreed(vec![Some(5)], 2, 3).unwrap_err();
+        assert!(matches!(abstained, QuorumError::NotReached { agreeing: 1, .. }));
+        assert!(matches!(agreed(Vec::<u8>::new(), 1, 3), Err(QuorumError::NotReached { agreeing: 0, .. })));
+    }
+
+    #[test]
+    fn latency_is_smoothed_and_failures_reset_on_success() {
+        let mut health = Health::default();
+        health.record(true, Duration::from_millis(100));
+        assert_eq!(health.latency_ms, 100.0);
+        health.record(true, Duration::from_millis(200));
+        assert!((health.latency_ms - 120.0).abs() < 1e-6);
+
+        health.record(false, Duration::from_millis(100));
+        health.record(false, Duration::from_millis(100));
+        assert_eq!(health.consecutive_failures, 2);
+        assert!(health.cooling_down());
+
+        health.record(true, Duration::from_millis(100));
+        assert_eq!(health.consecutive_failures, 0);
+        assert!(!health.cooling_down());
+    }
+
+    #[test]
+    fn failing_endpoints_go_last_then_slower_ones() {
+        let pool = endpoints(3);
+        assert_eq!(pool.ordered(), vec![0, 1, 2], "ties keep the configured order");
+
+        pool.record(0, false, Duration::from_millis(10));
+        pool.record(1, true, Duration::from_millis(300));
+        pool.record(2, true, Duration::from_millis(50));
+        assert_eq!(pool.ordered(), vec![2, 1, 0]);
+
+        let status = pool.status();
+        assert!(status[0].cooling_down);
+        assert_eq!(status[0].consecutive_failures, 1);
+        assert_eq!(status[1].latency_ms, 300);
+    }
+
+    #[tokio::test]
+    async fn qubic_requests_fail_over_on_transport_and_server_errors() {
+        let overloaded = serve(Router::new().route("/v", get(|| async { HttpStatus::SERVICE_UNAVAILABLE }))).await;
+        let healthy = serve(Router::new().route("/v", get(|| async { "ok" }))).await;
+        let qubic = QubicEndpoints::new(&[unreachable_url(), overloaded, healthy], None);
+
+        let response = qubic.get("/v").await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+
+        let status = qubic.status();
+        assert_eq!(status.iter().map(|s| s.consecutive_failures).collect::<Vec<_>>(), vec![1, 1, 0]);
+        assert_eq!(qubic.endpoints.ordered()[0], 2, "the endpoint that answered is tried first next time");
+    }
+
+    #[tokio::test]
+    async fn qubic_client_errors_are_answers_not_failures() {
+        let missing = serve(Router::new()).await;
+        let qubic = QubicEndpoints::new(&[missing, unreachable_url()], None);
+
+        assert_eq!(qubic.get("/v").await.unwrap().status(), StatusCode::NOT_FOUND);
+        assert_eq!(qubic.status()[0].consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn qubic_quorum_posts_compare_decoded_lock_info() {
+        use base64::Engine;
+        use shared::qubic::hm25::{GetLockInfoInput, GetLockInfoOutput, FUNCTION_GET_LOCK_INFO};
+        use shared::qubic::QpiStruct;
+
+        use crate::qubic_monitor::{query_output, query_request, QUERY_SMART_CONTRACT};
+
+        let reporting = |lock: Option<GetLockInfoOutput>| {
+            let body = match lock {
+                Some(lock) => serde_json::json!({ "responseData": base64::engine::general_purpose::STANDARD.encode(lock.encode()) }),
+                None => serde_json::json!({ "responseData": "not base64" }),
+            };
+            Router::new().route(QUERY_SMART_CONTRACT, post(move || async move { Json(body) }))
+        };
+        let honest = GetLockInfoOutput {
+            found: true,
+            locker: [9; 32],
+            amount: 110,
+            actual_amount: 100,
+            fee: 10,
+            timestamp: 50,
+            solana_destination: [3; 32],
+            memo: [0; 64],
+            status: 0,
+        };
+        let inflated = GetLockInfoOutput { actual_amount: 10_000, ..honest.clone() };
+        let a = serve(reporting(Some(honest.clone()))).await;
+        let b = serve(reporting(Some(honest.clone()))).await;
+        let c = serve(reporting(Some(inflated))).await;
+        let garbled = serve(reporting(None)).await;
+
+        let request = query_request(FUNCTION_GET_LOCK_INFO, &GetLockInfoInput { lock_id: 8 });
+        let qubic = QubicEndpoints::new(&[a.clone(), b, c.clone()], Some(2));
+        let agreed = qubic.quorum_post_json(QUERY_SMART_CONTRACT, &request, 2, query_output::<GetLockInfoOutput>).await;
+        assert_eq!(agreed.unwrap(), honest);
+
+        // Disagreeing locks, and an answer that does not decode, leave nothing corroborated
+        let split = QubicEndpoints::new(&[a, c, garbled], Some(2));
+        assert!(matches!(
+            split.quorum_post_json(QUERY_SMART_CONTRACT, &request, 2, query_output::<GetLockInfoOutput>).await,
+            Err(QuorumError::NotReached { agreeing: 1, required: 2, endpoints: 3 })
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+// src/relayer/src/signature_collector.rs
+// Coordinator side of signature aggregation: validators POST their signed
+// attestations here, and the relayer picks up threshold-many for submission.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use log::{info, warn};
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use tokio::sync::RwLock;
+
+use crate::attestation::{Attestation, AttestationDomain, SignedAttestation};
+use validator::state::{ValidatorConfig, ValidatorInfo};
+
+struct Collected {
+    attestation: Attestation,
+    signatures: BTreeMap<Pubkey, Signature>,
+}
+
+pub struct SignatureCollector {
+    /// Signatures are only accepted for attestations in this deployment.
+    domain: AttestationDomain,
+    /// Active validators, with the Qubic identity HM25 knows each by.
+    validators: RwLock<HashMap<Pubkey, [u8; 32]>>,
+    threshold: RwLock<usize>,
+    collected: RwLock<HashMap<[u8; 32], Collected>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollectionStatus {
+    pub digest: String,
+    pub lock_id: u64,
+    pub collected: usize,
+    pub threshold: usize,
+    pub validators: Vec<String>,
+}
+
+impl SignatureCollector {
+    pub fn new(domain: AttestationDomain, validators: impl IntoIterator<Item = (Pubkey, [u8; 32])>, threshold: usize) -> Self {
+        SignatureCollector {
+            domain,
+            validators: RwLock::new(validators.into_iter().collect()),
+            threshold: RwLock::new(threshold),
+            collected: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the validator set and threshold with what the validator program holds.
+    pub async fn refresh_from_chain(&self, client: &RpcClient) -> Result<(), anyhow::Error> {
+        let (validators, threshold) = fetch_validator_set(client).await?;
+        let mut current_validators = self.validators.write().await;
+        let mut current_threshold = self.threshold.write().await;
+        if *current_validators != validators || *current_threshold != threshold {
+            info!("Validator set refreshed: {} active, threshold {}", validators.len(), threshold);
+        }
+        *current_validators = validators;
+        *current_threshold = threshold;
+        Ok(())
+    }
+
+    /// Accept one validator's signature. Returns how many distinct validators
+    /// have signed this attestation so far.
+    pub async fn submit(&self, signed: &SignedAttestation) -> Result<usize, anyhow::Error> {
+        let (attestation, validator, signature) = signed.verify(&self.domain)?;
+        if !self.validators.read().await.contains_key(&validator) {
+            anyhow::bail!("{} is not an active validator", validator);
+        }
+
+        let mut collected = self.collected.write().await;
+        let entry = collected
+            .entry(attestation.digest())
+            .or_insert_with(|| Collected {
+                attestation,
+                signatures: BTreeMap::new(),
+            });
+        entry.signatures.insert(validator, signature);
+
+        Ok(entry.signatures.len())
+    }
+
+    /// Exactly `threshold` signatures for the attestation, once enough were collected.
+    pub async fn threshold_signatures(&self, attestation: &Attestation) -> Option<Vec<(Pubkey, Signature)>> {
+        let threshold = *self.threshold.read().await;
+        let validators = self.validators.read().await;
+        let collected = self.collected.read().await;
+        let entry = collected.get(&attestation.digest())?;
+
+        // Validators deactivated since they signed no longer count
+        let signatures: Vec<_> = entry
+            .signatures
+            .iter()
+            .filter(|(validator, _)| validators.contains_key(validator))
+            .take(threshold)
+            .map(|(validator, signature)| (*validator, *signature))
+            .collect();
+
+        (threshold > 0 && signatures.len() == threshold).then_some(signatures)
+    }
+
+    /// The Qubic identity `validator` registered, which HM25 checks signers against.
+    pub async fn qubic_validator_id(&self, validator: &Pubkey) -> Option<[u8; 32]> {
+        self.validators.read().await.get(validator).copied()
+    }
+
+    /// Drop signatures for an attestation once it has been relayed.
+    pub async fn forget(&self, attestation: &Attestation) {
+        self.collected.write().await.remove(&attestation.digest());
+    }
+
+    pub async fn status(&self, digest: &[u8; 32]) -> Option<CollectionStatus> {
+        let threshold = *self.threshold.read().await;
+        let collected = self.collected.read().await;
+        let entry = collected.get(digest)?;
+
+        Some(CollectionStatus {
+            digest: hex::encode(digest),
+            lock_id: entry.attestation.lock_id,
+            collected: entry.signatures.len(),
+            threshold,
+            validators: entry.signatures.keys().map(|v| v.to_string()).collect(),
+        })
+    }
+
+    /// HTTP collection endpoint:
+    /// `POST /attestations` with a `SignedAttestation`, `GET /attestations/:digest` for progress.
+    pub fn router(self: Arc<Self>) -> Router {
+        Router::new()
+            .route("/attestations", post(submit_attestation))
+            .route("/attestations/:digest", get(attestation_status))
+            .with_state(self)
+    }
+}
+
+async fn submit_attestation(
+    State(collector): State<Arc<SignatureCollector>>,
+    Json(signed): Json<SignedAttestation>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    match collector.submit(&signed).await {
+        Ok(collected) => Ok(Json(serde_json::json!({ "collected": collected }))),
+        Err(e) => {
+            warn!("Rejected attestation for lock {}: {}", signed.lock_id, e);
+            Err((StatusCode::BAD_REQUEST, e.to_string()))
+        }
+    }
+}
+
+async fn attestation_status(
+    State(collector): State<Arc<SignatureCollector>>,
+    Path(digest): Path<String>,
+) -> Result<Json<CollectionStatus>, StatusCode> {
+    let digest: [u8; 32] = hex::decode(&digest)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    collector.status(&digest).await.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Active validators with their Qubic identities, and the approval threshold, read from the
+/// validator program.
+pub async fn fetch_validator_set(client: &RpcClient) -> Result<(HashMap<Pubkey, [u8; 32]>, usize), anyhow::Error> {
+    use anchor_lang::{AccountDeserialize, Discriminator};
+
+    let config_address = validator::client::validator_config_address();
+    let config_data = client
+        .get_account_data(&config_address)
+        .await
+        .context("validator config account not found")?;
+    let config = ValidatorConfig::try_deserialize(&mut config_data.as_slice())?;
+
+    let filter = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &ValidatorInfo::DISCRIMINATOR));
+    let accounts = client.get_program_accounts_with_config(
+        &validator::ID,
+        RpcProgramAccountsConfig {
+            filters: Some(vec![filter]),
+            account_config: RpcAccountInfoConfig::default(),
+            ..RpcProgramAccountsConfig::default()
+        },
+    )
+    .await?;
+
+    let validators = accounts
+        .into_iter()
+        .filter_map(|(_, account)| ValidatorInfo::try_deserialize(&mut account.data.as_slice()).ok())
+        .filter(|info| info.is_active)
+        .map(|info| (info.validator_pubkey, info.qubic_validator_id))
+        .collect();
+
+    Ok((validators, config.threshold as usize))
+}
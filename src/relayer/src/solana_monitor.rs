@@ -1,55 +1,140 @@
 // src/relayer/src/solana_monitor.rs
+// Finds pending bridge locks on Solana, either by scanning program accounts or by
+// streaming account updates over a `programSubscribe` WebSocket subscription.
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
 
-use solana_client::rpc_client::RpcClient;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use futures::StreamExt;
+use log::{info, warn};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
-use std::time::Duration;
-use tokio::time::sleep;
-use anyhow;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Mutex;
+
+use bridge::state::BRIDGE_STATUS_PENDING;
+use shared::types::{BridgeDirection, BridgeStatus, BridgeTransaction};
+
+/// Offset of `BridgeTransaction::status`: discriminator, version, id, user, token_mint,
+/// amount, actual_amount, fee, qubic_destination and memo come first.
+const STATUS_OFFSET: usize = 8 + 1 + 8 + 32 + 32 + 8 + 8 + 8 + 32 + 64;
 
 pub struct SolanaMonitor {
-    client: RpcClient,
+    client: Arc<RpcClient>,
     pubkey: Pubkey,
+    /// Lock ids already handed out, shared by the stream and the backfill scan.
+    reported: Mutex<HashSet<u64>>,
 }
 
 impl SolanaMonitor {
-    pub fn new(rpc_url: &str, pubkey: Pubkey) -> Self {
+    pub fn new(client: Arc<RpcClient>, pubkey: Pubkey) -> Self {
         SolanaMonitor {
-            client: RpcClient::new(rpc_url.to_string()),
+            client,
             pubkey,
+            reported: Mutex::new(HashSet::new()),
         }
     }
 
-    pub async fn monitor(&self) {
-        loop {
-            match self.check_for_events().await {
-                Ok(_) => {
-                    // Handle events
-                }
-                Err(e) => {
-                    eprintln!("Error checking for events: {:?}", e);
-                }
+    /// Scan the bridge program for pending locks not reported yet. Polling mode runs this
+    /// every round; subscription mode runs it as the backfill.
+    pub async fn get_pending_transactions(&self) -> Result<Vec<BridgeTransaction>, anyhow::Error> {
+        let accounts = self
+            .client
+            .get_program_accounts_with_config(&self.pubkey, pending_locks_config())
+            .await?;
+
+        let mut transactions = Vec::new();
+        for (address, account) in accounts {
+            if let Some(tx) = self.report(&address, &account.data).await? {
+                transactions.push(tx);
             }
-            sleep(Duration::from_secs(10)).await; // Adjust the interval as needed
         }
+        Ok(transactions)
     }
 
-    async fn check_for_events(&self) -> Result<(), anyhow::Error> {
-        // Implement logic to check for relevant events on the Solana blockchain
-        // For example, querying account state or transaction history
+    /// Stream pending locks into `sender` until the subscription drops. Returns `Ok` when the
+    /// node closes the stream; either way the caller is expected to backfill and resubscribe.
+    pub async fn subscribe(&self, ws_url: &str, sender: UnboundedSender<BridgeTransaction>) -> Result<(), anyhow::Error> {
+        let pubsub = PubsubClient::new(ws_url).await?;
+        let (mut updates, unsubscribe) = pubsub.program_subscribe(&self.pubkey, Some(pending_locks_config())).await?;
+        info!("Subscribed to bridge program accounts at {}", ws_url);
+
+        while let Some(update) = updates.next().await {
+            let Some(account) = update.value.account.decode::<Account>() else {
+                warn!("Could not decode streamed account {}", update.value.pubkey);
+                continue;
+            };
+            let address = Pubkey::from_str(&update.value.pubkey)?;
+            if let Some(tx) = self.report(&address, &account.data).await? {
+                sender.send(tx)?;
+            }
+        }
+
+        unsubscribe().await;
         Ok(())
     }
-    
-    pub async fn get_pending_transactions(&self) -> Result<Vec<shared::types::BridgeTransaction>, anyhow::Error> {
-        // Implement logic to get pending transactions from Solana
-        // This would query the bridge program state
-        Ok(vec![])
+
+    /// The slot the monitor reads at (its client's commitment) and the `processed` tip.
+    pub async fn chain_heights(&self) -> Result<(u64, u64), anyhow::Error> {
+        let seen = self.client.get_slot().await?;
+        let head = self.client.get_slot_with_commitment(CommitmentConfig::processed()).await?;
+        Ok((seen, head))
+    }
+
+    /// Decode a lock account the first time it is seen. A lock only counts as reported once
+    /// its signature is known, so a failed lookup is retried by the next scan.
+    async fn report(&self, address: &Pubkey, data: &[u8]) -> Result<Option<BridgeTransaction>, anyhow::Error> {
+        let lock = bridge::state::BridgeTransaction::try_deserialize(&mut &data[..])?;
+        if lock.status != BRIDGE_STATUS_PENDING || self.reported.lock().await.contains(&lock.id) {
+            return Ok(None);
+        }
+
+        let signature = self.lock_signature(address).await?;
+        if !self.reported.lock().await.insert(lock.id) {
+            // The other path got there while the signature was being fetched
+            return Ok(None);
+        }
+
+        let mut tx = BridgeTransaction::new(
+            lock.id,
+            lock.user,
+            lock.token_mint,
+            lock.actual_amount,
+            BridgeDirection::SolanaToQubic,
+        );
+        tx.qubic_destination = lock.qubic_destination;
+        tx.status = BridgeStatus::Pending;
+        tx.created_at = lock.created_at;
+        tx.solana_tx_signature = signature;
+        Ok(Some(tx))
+    }
+
+    /// The transaction that created the lock account: the oldest signature touching it.
+    async fn lock_signature(&self, address: &Pubkey) -> Result<Option<String>, anyhow::Error> {
+        let signatures = self.client.get_signatures_for_address(address).await?;
+        Ok(signatures.last().map(|status| status.signature.clone()))
     }
 }
 
-pub async fn monitor() {
-    // Monitor function for standalone usage
-    println!("Monitoring Solana...");
-    loop {
-        tokio::time::sleep(Duration::from_secs(10)).await;
+/// `BridgeTransaction` accounts whose status is still pending.
+fn pending_locks_config() -> RpcProgramAccountsConfig {
+    RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &bridge::state::BridgeTransaction::DISCRIMINATOR)),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(STATUS_OFFSET, &[BRIDGE_STATUS_PENDING])),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            // Lock accounts are too large for the default base58 encoding
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,160 @@
+// src/relayer/src/solana_rpc.rs
+// Shared nonblocking Solana RPC access over the endpoint pool: request timeouts, a short-lived
+// blockhash cache and optional priority fees on everything the relayer submits.
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+use crate::rpc_pool::SolanaEndpoints;
+
+/// Blockhashes stay valid for ~150 slots (about a minute); refresh well before that.
+const BLOCKHASH_TTL: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolanaRpcConfig {
+    /// Per-request timeout; a slow node fails the call instead of stalling a loop.
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+    /// Compute unit price attached to submitted transactions, if any.
+    #[serde(default)]
+    pub priority_fee_micro_lamports: Option<u64>,
+    #[serde(default)]
+    pub compute_unit_limit: Option<u32>,
+}
+
+fn default_timeout_seconds() -> u64 {
+    30
+}
+
+impl Default for SolanaRpcConfig {
+    fn default() -> Self {
+        SolanaRpcConfig {
+            timeout_seconds: default_timeout_seconds(),
+            priority_fee_micro_lamports: None,
+            compute_unit_limit: None,
+        }
+    }
+}
+
+impl SolanaRpcConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds)
+    }
+}
+
+/// A nonblocking client reading at `confirmed`; `processed` data is never used.
+pub fn rpc_client(url: &str, timeout: Duration) -> RpcClient {
+    RpcClient::new_with_timeout_and_commitment(url.to_string(), timeout, CommitmentConfig::confirmed())
+}
+
+pub struct SolanaRpc {
+    endpoints: SolanaEndpoints,
+    client: Arc<RpcClient>,
+    config: SolanaRpcConfig,
+    blockhash: Mutex<Option<(Hash, Instant)>>,
+}
+
+impl SolanaRpc {
+    pub fn new(endpoints: SolanaEndpoints, config: SolanaRpcConfig) -> Self {
+        SolanaRpc {
+            client: endpoints.client(),
+            endpoints,
+            config,
+            blockhash: Mutex::new(None),
+        }
+    }
+
+    /// The underlying client, shared with monitors and trackers.
+    pub fn client(&self) -> Arc<RpcClient> {
+        self.client.clone()
+    }
+
+    pub fn endpoints(&self) -> &SolanaEndpoints {
+        &self.endpoints
+    }
+
+    /// Recent blockhash, fetched at most once per `BLOCKHASH_TTL` across all submissions.
+    pub async fn latest_blockhash(&self) -> Result<Hash, anyhow::Error> {
+        let mut cached = self.blockhash.lock().await;
+        if let Some((hash, fetched_at)) = *cached {
+            if fetched_at.elapsed() < BLOCKHASH_TTL {
+                return Ok(hash);
+            }
+        }
+
+        let hash = self.client.get_latest_blockhash().await?;
+        *cached = Some((hash, Instant::now()));
+        Ok(hash)
+    }
+
+    /// Sign `instructions` with `payer`, prefixed with any configured compute budget,
+    /// and wait for confirmation without blocking the runtime.
+    pub async fn send_instructions(&self, mut instructions: Vec<Instruction>, payer: &Keypair) -> Result<Signature, anyhow::Error> {
+        let mut budget = Vec::new();
+        if let Some(units) = self.config.compute_unit_limit {
+            budget.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(price) = self.config.priority_fee_micro_lamports {
+            budget.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        budget.append(&mut instructions);
+
+        let blockhash = self.latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(&budget, Some(&payer.pubkey()), &[payer], blockhash);
+
+        match self.client.send_and_confirm_transaction(&transaction).await {
+            Ok(signature) => Ok(signature),
+            Err(e) => {
+                // An expired blockhash must not be handed to the retry either
+                if e.get_transaction_error() == Some(TransactionError::BlockhashNotFound) {
+                    *self.blockhash.lock().await = None;
+                }
+                Err(e.into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockSolana;
+
+    #[test]
+    fn config_fields_are_optional() {
+        let config: SolanaRpcConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.timeout(), Duration::from_secs(30));
+        assert_eq!(config.priority_fee_micro_lamports, None);
+
+        let config: SolanaRpcConfig =
+            serde_json::from_str(r#"{ "timeoutSeconds": 5, "priorityFeeMicroLamports": 1000, "computeUnitLimit": 200000 }"#).unwrap();
+        assert_eq!(config.timeout(), Duration::from_secs(5));
+        assert_eq!(config.priority_fee_micro_lamports, Some(1_000));
+        assert_eq!(config.compute_unit_limit, Some(200_000));
+    }
+
+    #[tokio::test]
+    async fn the_blockhash_is_fetched_once_per_ttl() {
+        let mock = MockSolana::default();
+        let url = mock.serve().await;
+        let rpc = SolanaRpc::new(SolanaEndpoints::new(&[url], Duration::from_secs(5), None), SolanaRpcConfig::default());
+
+        let first = rpc.latest_blockhash().await.unwrap();
+        assert_eq!(rpc.latest_blockhash().await.unwrap(), first);
+        assert_eq!(mock.calls("getLatestBlockhash"), 1);
+
+        *rpc.blockhash.lock().await = Some((first, Instant::now() - BLOCKHASH_TTL));
+        assert_ne!(rpc.latest_blockhash().await.unwrap(), first);
+        assert_eq!(mock.calls("getLatestBlockhash"), 2);
+    }
+}
@@ -0,0 +1,114 @@
+// src/relayer/src/supervisor.rs
+// Keeps long-running relayer tasks alive: a task that fails or returns early is restarted
+// with backoff, and every task is handed the shutdown signal so it can stop at a safe point.
+use std::future::Future;
+
+use log::{error, info, warn};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration, Instant};
+
+const INITIAL_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+/// A task that ran at least this long before failing starts over from the initial delay.
+const HEALTHY_RUN: Duration = Duration::from_secs(60);
+
+pub type Shutdown = watch::Receiver<bool>;
+
+/// Resolves once shutdown has been requested (or the sender is gone).
+pub async fn shutdown_requested(shutdown: &mut Shutdown) {
+    let _ = shutdown.wait_for(|stop| *stop).await;
+}
+
+pub fn is_shutting_down(shutdown: &Shutdown) -> bool {
+    *shutdown.borrow()
+}
+
+/// Run `task` until shutdown, restarting it with exponential backoff whenever it fails
+/// or exits on its own. Tasks are expected to return promptly once `shutdown` fires.
+pub fn spawn_supervised<F, Fut>(name: &'static str, shutdown: Shutdown, task: F) -> JoinHandle<()>
+where
+    F: Fn(Shutdown) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), anyhow::Error>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut delay = INITIAL_RESTART_DELAY;
+
+        loop {
+            let started = Instant::now();
+            let result = task(shutdown.clone()).await;
+
+            if is_shutting_down(&shutdown) {
+                if let Err(e) = result {
+                    warn!("Task {} failed while shutting down: {}", name, e);
+                }
+                info!("Task {} stopped", name);
+                return;
+            }
+
+            if started.elapsed() >= HEALTHY_RUN {
+                delay = INITIAL_RESTART_DELAY;
+            }
+            match result {
+                Ok(()) => warn!("Task {} exited unexpectedly, restarting in {:?}", name, delay),
+                Err(e) => error!("Task {} failed: {}, restarting in {:?}", name, e, delay),
+            }
+
+            let mut shutdown_wait = shutdown.clone();
+            tokio::select! {
+                _ = sleep(delay) => {}
+                _ = shutdown_requested(&mut shutdown_wait) => {
+                    info!("Task {} stopped", name);
+                    return;
+                }
+            }
+            delay = (delay * 2).min(MAX_RESTART_DELAY);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn a_failed_task_is_restarted_until_shutdown() {
+        let (stop, shutdown) = watch::channel(false);
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let counter = runs.clone();
+        let handle = spawn_supervised("flaky", shutdown, move |mut shutdown| {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    anyhow::bail!("first run fails");
+                }
+                shutdown_requested(&mut shutdown).await;
+                Ok(())
+            }
+        });
+
+        // Restarted after the initial delay, then parked until shutdown
+        sleep(INITIAL_RESTART_DELAY + Duration::from_millis(300)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert!(!handle.is_finished());
+
+        stop.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), handle).await.unwrap().unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2, "a task stopped by shutdown is not restarted");
+    }
+
+    #[tokio::test]
+    async fn shutdown_interrupts_the_restart_delay() {
+        let (stop, shutdown) = watch::channel(false);
+        let handle = spawn_supervised("failing", shutdown.clone(), |_| async { anyhow::bail!("always fails") });
+
+        sleep(Duration::from_millis(50)).await;
+        assert!(!is_shutting_down(&shutdown));
+        stop.send(true).unwrap();
+        assert!(is_shutting_down(&shutdown));
+        tokio::time::timeout(Duration::from_millis(500), handle).await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+// src/relayer/src/test_support.rs
+// Fixtures for the relayer's unit tests: jobs, throwaway HTTP servers standing in for
+// Qubic and Solana RPC endpoints, and a relayer wired to them.
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anchor_lang::{AccountSerialize, AnchorDeserialize};
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{write_keypair_file, Keypair};
+
+use bridge::state::BridgeState;
+use validator::state::ValidatorConfig;
+use shared::types::{BridgeDirection, BridgeTransaction};
+
+use crate::anomaly::AnomalyConfig;
+use crate::bridge_relayer::{BridgeConfig, BridgeRelayer};
+use crate::finality::FinalityConfig;
+use crate::qubic_tx::QubicTxConfig;
+use crate::solana_rpc::SolanaRpcConfig;
+
+pub fn transaction(id: u64, direction: BridgeDirection) -> BridgeTransaction {
+    BridgeTransaction::new(id, Pubkey::new_unique(), Pubkey::new_unique(), 1_000, direction)
+}
+
+/// Serve `router` on an ephemeral local port and return its base URL.
+pub async fn serve(router: Router) -> String {
+    let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(router.into_make_service());
+    let url = format!("http://{}", server.local_addr());
+    tokio::spawn(server);
+    url
+}
+
+/// A URL nothing listens on.
+pub fn unreachable_url() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+/// A Solana JSON-RPC endpoint that knows a freshly initialized bridge (on the cluster with the
+/// default genesis hash) and validator registry, no validators or transactions, and hands out a
+/// new blockhash per call. Counts calls per method.
+#[derive(Clone, Default)]
+pub struct MockSolana {
+    pub calls: Arc<Mutex<HashMap<String, usize>>>,
+    /// Leave the validator program uninitialized.
+    pub without_validator_config: bool,
+}
+
+impl MockSolana {
+    pub async fn serve(&self) -> String {
+        serve(Router::new().route("/", post(solana_rpc)).with_state(self.clone())).await
+    }
+
+    pub fn calls(&self, method: &str) -> usize {
+        self.calls.lock().unwrap().get(method).copied().unwrap_or(0)
+    }
+}
+
+async fn solana_rpc(State(mock): State<MockSolana>, Json(request): Json<Value>) -> Json<Value> {
+    let method = request["method"].as_str().unwrap_or_default().to_string();
+    *mock.calls.lock().unwrap().entry(method.clone()).or_insert(0) += 1;
+
+    let result = match method.as_str() {
+        "getAccountInfo" if request["params"][0] == json!(bridge::client::bridge_state_address().to_string()) => {
+            let state = BridgeState::deserialize(&mut &[0u8; BridgeState::INIT_SPACE][..]).unwrap();
+            let mut data = Vec::new();
+            state.try_serialize(&mut data).unwrap();
+            json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "data": [base64::engine::general_purpose::STANDARD.encode(&data), "base64"],
+                    "executable": false,
+                    "lamports": 1_000_000,
+                    "owner": bridge::ID.to_string(),
+                    "rentEpoch": 0,
+                    "space": data.len(),
+                },
+            })
+        }
+        "getAccountInfo"
+            if !mock.without_validator_config
+                && request["params"][0] == json!(validator::client::validator_config_address().to_string()) =>
+        {
+            let config = ValidatorConfig::deserialize(&mut &[0u8; ValidatorConfig::INIT_SPACE][..]).unwrap();
+            let mut data = Vec::new();
+            config.try_serialize(&mut data).unwrap();
+            json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "data": [base64::engine::general_purpose::STANDARD.encode(&data), "base64"],
+                    "executable": false,
+                    "lamports": 1_000_000,
+                    "owner": validator::ID.to_string(),
+                    "rentEpoch": 0,
+                    "space": data.len(),
+                },
+            })
+        }
+        "getAccountInfo" => json!({ "context": { "slot": 1 }, "value": null }),
+        "getProgramAccounts" => json!([]),
+        "getSignatureStatuses" => json!({ "context": { "slot": 1 }, "value": [null] }),
+        "getVersion" => json!({ "solana-core": "1.18.26", "feature-set": 0 }),
+        "getGenesisHash" => json!(Hash::default().to_string()),
+        "getLatestBlockhash" => json!({
+            "context": { "slot": 1 },
+            "value": { "blockhash": Hash::new_unique().to_string(), "lastValidBlockHeight": 150 },
+        }),
+        _ => {
+            return Json(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32601, "message": "Method not found" },
+                "id": request["id"],
+            }))
+        }
+    };
+    Json(json!({ "jsonrpc": "2.0", "result": result, "id": request["id"] }))
+}
+
+/// A path under the system temp directory, unique to this test process and `name`.
+pub fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("relayer-test-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// A relayer against `MockSolana` and an unreachable Qubic node, checkpointing to `checkpoint_path`.
+/// Its keypair is written next to the checkpoint, as `{checkpoint_path}.keypair`.
+pub async fn relayer(solana_url: &str, checkpoint_path: &str) -> BridgeRelayer {
+    BridgeRelayer::new(config(solana_url, checkpoint_path)).await.unwrap()
+}
+
+/// The configuration `relayer` starts with; writes the keypair.
+pub fn config(solana_url: &str, checkpoint_path: &str) -> BridgeConfig {
+    let keypair_path = format!("{}.keypair", checkpoint_path);
+    write_keypair_file(&Keypair::new(), &keypair_path).unwrap();
+
+    BridgeConfig {
+        solana_rpc_urls: vec![solana_url.to_string()],
+        solana_quorum: None,
+        solana_ws_url: None,
+        solana_backfill_interval_seconds: 60,
+        qubic_rpc_urls: vec![unreachable_url()],
+        qubic_quorum: None,
+        qubic_mint: Pubkey::new_unique(),
+        qubic_tx: QubicTxConfig::default(),
+        bridge_program_id: bridge::ID,
+        keypair_path,
+        finality: FinalityConfig::default(),
+        anomaly: AnomalyConfig::default(),
+        solana_rpc: SolanaRpcConfig::default(),
+        max_retry_attempts: 3,
+        max_concurrent_submissions: 1,
+        retry_delay_seconds: 1,
+        collector_listen_addr: "127.0.0.1:0".to_string(),
+        admin_listen_addr: "127.0.0.1:0".to_string(),
+        checkpoint_path: checkpoint_path.to_string(),
+    }
+}
@@ -0,0 +1,273 @@
+// src/relayer/src/validator_daemon.rs
+// Validator mode: one operator, one key. The daemon observes locks on both chains on its own,
+// signs the canonical attestation and publishes it to the coordinator's collector. Locks are
+// also approved on-chain: quantum_link only unlocks a Qubic lock, or records a Solana lock as
+// delivered, once the validator program approved it.
+use std::collections::{HashMap, HashSet};
+
+use log::{error, info};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use tokio::sync::Mutex;
+use tokio::time::Duration;
+
+use shared::types::{BridgeDirection, BridgeTransaction};
+
+use crate::attestation::{attestation_for, fetch_domain, Attestation, AttestationDomain, SignedAttestation};
+use crate::finality::{Finality, FinalityConfig, FinalityTracker};
+use crate::qubic_monitor::{QubicCursor, QubicMonitor};
+use crate::rpc_pool::{QubicEndpoints, SolanaEndpoints};
+use crate::solana_monitor::SolanaMonitor;
+use crate::solana_rpc::{SolanaRpc, SolanaRpcConfig};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What the daemon must not forget across a restart: where the Qubic monitor is, the locks it
+/// saw but has not attested yet and the ones it already signed. Without it, Qubic locks made
+/// while the daemon was down would never be signed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DaemonCheckpoint {
+    qubic_cursor: Option<QubicCursor>,
+    observed: Vec<BridgeTransaction>,
+    signed_locks: Vec<(BridgeDirection, u64)>,
+}
+
+pub struct ValidatorDaemon {
+    keypair: Keypair,
+    collector_url: String,
+    solana: SolanaEndpoints,
+    solana_rpc: SolanaRpc,
+    solana_monitor: SolanaMonitor,
+    qubic_monitor: QubicMonitor,
+    finality_tracker: FinalityTracker,
+    http_client: Client,
+    /// Locks seen on either chain and not attested yet. The monitors report each lock once,
+    /// so one that is not final yet waits here for a later round.
+    observed: Mutex<HashMap<(BridgeDirection, u64), BridgeTransaction>>,
+    signed_locks: Mutex<HashSet<(BridgeDirection, u64)>>,
+    /// Rewritten after every round.
+    checkpoint_path: String,
+}
+
+impl ValidatorDaemon {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        keypair: Keypair,
+        collector_url: String,
+        solana: SolanaEndpoints,
+        qubic: QubicEndpoints,
+        bridge_program_id: Pubkey,
+        qubic_mint: Pubkey,
+        finality: FinalityConfig,
+        checkpoint_path: String,
+    ) -> Self {
+        ValidatorDaemon {
+            keypair,
+            collector_url,
+            solana_rpc: SolanaRpc::new(solana.clone(), SolanaRpcConfig::default()),
+            solana_monitor: SolanaMonitor::new(solana.client(), bridge_program_id),
+            qubic_monitor: QubicMonitor::new(qubic.clone(), qubic_mint),
+            finality_tracker: FinalityTracker::new(finality, solana.clone(), qubic),
+            solana,
+            http_client: Client::new(),
+            observed: Mutex::new(HashMap::new()),
+            signed_locks: Mutex::new(HashSet::new()),
+            checkpoint_path,
+        }
+    }
+
+    pub async fn run(&self) -> Result<(), anyhow::Error> {
+        // Only ever sign for the deployment on the cluster this daemon is connected to
+        let domain = fetch_domain(&self.solana.client()).await?;
+        info!("Validator daemon started for {} on bridge {}", self.keypair.pubkey(), domain.bridge_id);
+        self.restore_checkpoint().await?;
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match self.solana_monitor.get_pending_transactions().await {
+                Ok(transactions) => self.observe(transactions).await,
+                Err(e) => error!("Error observing Solana locks: {}", e),
+            }
+            match self.qubic_monitor.get_pending_transactions().await {
+                Ok(transactions) => self.observe(transactions).await,
+                Err(e) => error!("Error observing Qubic locks: {}", e),
+            }
+
+            let observed: Vec<BridgeTransaction> = self.observed.lock().await.values().cloned().collect();
+            for tx in observed {
+                if self.attest(&tx, &domain).await {
+                    self.observed.lock().await.remove(&tx.key());
+                }
+            }
+
+            if let Err(e) = self.write_checkpoint().await {
+                error!("Could not write checkpoint to {}: {}", self.checkpoint_path, e);
+            }
+        }
+    }
+
+    async fn write_checkpoint(&self) -> Result<(), anyhow::Error> {
+        let checkpoint = DaemonCheckpoint {
+            qubic_cursor: self.qubic_monitor.cursor(),
+            observed: self.observed.lock().await.values().cloned().collect(),
+            signed_locks: self.signed_locks.lock().await.iter().copied().collect(),
+        };
+
+        // Write then rename, so a kill mid-write never leaves a truncated checkpoint
+        let temp_path = format!("{}.tmp", self.checkpoint_path);
+        std::fs::write(&temp_path, serde_json::to_vec(&checkpoint)?)?;
+        std::fs::rename(&temp_path, &self.checkpoint_path)?;
+        Ok(())
+    }
+
+    async fn restore_checkpoint(&self) -> Result<(), anyhow::Error> {
+        let contents = match std::fs::read(&self.checkpoint_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let checkpoint: DaemonCheckpoint = serde_json::from_slice(&contents)?;
+
+        if let Some(cursor) = checkpoint.qubic_cursor {
+            self.qubic_monitor.restore(cursor);
+        }
+        self.signed_locks.lock().await.extend(checkpoint.signed_locks);
+        self.observe(checkpoint.observed).await;
+        info!(
+            "Resuming from {}: {} lock(s) signed, {} awaiting finality",
+            self.checkpoint_path,
+            self.signed_locks.lock().await.len(),
+            self.observed.lock().await.len()
+        );
+        Ok(())
+    }
+
+    async fn observe(&self, transactions: Vec<BridgeTransaction>) {
+        let signed = self.signed_locks.lock().await;
+        let mut observed = self.observed.lock().await;
+        for tx in transactions {
+            if !signed.contains(&tx.key()) {
+                observed.entry(tx.key()).or_insert(tx);
+            }
+        }
+    }
+
+    /// Sign and approve a lock once it is final. Returns false when the lock should be tried
+    /// again next round.
+    async fn attest(&self, tx: &BridgeTransaction, domain: &AttestationDomain) -> bool {
+        if self.signed_locks.lock().await.contains(&tx.key()) {
+            return true;
+        }
+
+        // Only attest to locks that can no longer be rolled back
+        match self.finality_tracker.check(tx).await {
+            Ok(Finality::Final(_)) => {}
+            Ok(_) => return false,
+            Err(e) => {
+                error!("Could not check finality of {:?} lock {}: {}", tx.direction, tx.id, e);
+                return false;
+            }
+        }
+
+        // Never sign or vote twice for a lock: two different attestations for the same
+        // lock are slashable equivocation, off-chain signatures and on-chain votes alike
+        let attestation = attestation_for(tx, domain);
+        if let Err(e) = self.vote(&attestation).await {
+            error!("Failed to approve {:?} lock {} on-chain: {}", tx.direction, tx.id, e);
+            return false;
+        }
+        let signed = SignedAttestation::sign(&self.keypair, &attestation);
+        match self.publish(&signed).await {
+            Ok(()) => {
+                self.signed_locks.lock().await.insert(tx.key());
+                true
+            }
+            Err(e) => {
+                error!("Failed to publish attestation for {:?} lock {}: {}", tx.direction, tx.id, e);
+                false
+            }
+        }
+    }
+
+    /// Approve `attestation` in the validator program, unless this validator already has.
+    async fn vote(&self, attestation: &Attestation) -> Result<(), anyhow::Error> {
+        let validator = self.keypair.pubkey();
+        let vote = validator::client::validation_vote_address(&attestation.digest(), &validator);
+        let existing = self.solana_rpc
+            .client()
+            .get_account_with_commitment(&vote, self.solana_rpc.client().commitment())
+            .await?
+            .value;
+        if existing.is_some() {
+            return Ok(());
+        }
+
+        let instruction = validator::client::validate_transaction(&validator, *attestation, true);
+        let signature = self.solana_rpc.send_instructions(vec![instruction], &self.keypair).await?;
+        info!("Approved lock {} on-chain: {}", attestation.lock_id, signature);
+        Ok(())
+    }
+
+    async fn publish(&self, signed: &SignedAttestation) -> Result<(), anyhow::Error> {
+        let response = self.http_client
+            .post(format!("{}/attestations", self.collector_url))
+            .json(signed)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("collector returned {}", response.status());
+        }
+
+        info!("Published attestation for lock {}", signed.lock_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{temp_path, transaction, unreachable_url};
+
+    fn daemon(checkpoint_path: &str) -> ValidatorDaemon {
+        ValidatorDaemon::new(
+            Keypair::new(),
+            unreachable_url(),
+            SolanaEndpoints::new(&[unreachable_url()], Duration::from_secs(1), None),
+            QubicEndpoints::new(&[unreachable_url()], None),
+            bridge::ID,
+            Pubkey::new_unique(),
+            FinalityConfig::default(),
+            checkpoint_path.to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn a_restarted_daemon_resumes_its_qubic_cursor_and_signed_locks() {
+        let checkpoint_path = temp_path("validator-checkpoint.json");
+        let before = daemon(&checkpoint_path);
+        let cursor = QubicCursor { latest_tick: 900, next_lock_id: 12 };
+        before.qubic_monitor.restore(cursor);
+        let signed = transaction(10, BridgeDirection::QubicToSolana);
+        let pending = transaction(11, BridgeDirection::QubicToSolana);
+        before.signed_locks.lock().await.insert(signed.key());
+        before.observe(vec![pending.clone()]).await;
+        before.write_checkpoint().await.unwrap();
+
+        let after = daemon(&checkpoint_path);
+        after.restore_checkpoint().await.unwrap();
+        std::fs::remove_file(&checkpoint_path).unwrap();
+
+        assert_eq!(after.qubic_monitor.cursor(), Some(cursor));
+        assert!(after.signed_locks.lock().await.contains(&signed.key()));
+        assert!(after.observed.lock().await.contains_key(&pending.key()));
+
+        // A lock signed before the restart is not picked up again
+        after.observe(vec![signed.clone()]).await;
+        assert!(!after.observed.lock().await.contains_key(&signed.key()));
+    }
+}
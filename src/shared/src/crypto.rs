@@ -1,23 +1,21 @@
 // This file provides cryptographic utilities shared between Solana and Qubic components.
 
-pub mod crypto {
-    use sha2::{Sha256, Digest};
+use sha2::{Sha256, Digest};
 
-    pub fn hash(data: &[u8]) -> Vec<u8> {
-        let mut hasher = Sha256::new();
-        hasher.update(data);
-        hasher.finalize().to_vec()
-    }
+pub fn hash(data: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
 
-    pub fn verify_signature(_data: &[u8], _signature: &[u8], _public_key: &[u8]) -> bool {
-        // Implement signature verification logic here
-        // This is a placeholder for actual implementation
-        true
-    }
+pub fn verify_signature(_data: &[u8], _signature: &[u8], _public_key: &[u8]) -> bool {
+    // Implement signature verification logic here
+    // This is a placeholder for actual implementation
+    true
+}
 
-    pub fn generate_keypair() -> (Vec<u8>, Vec<u8>) {
-        // Implement keypair generation logic here
-        // This is a placeholder for actual implementation
-        (vec![0; 32], vec![0; 32])
-    }
-}
\ No newline at end of file
+pub fn generate_keypair() -> (Vec<u8>, Vec<u8>) {
+    // Implement keypair generation logic here
+    // This is a placeholder for actual implementation
+    (vec![0; 32], vec![0; 32])
+}
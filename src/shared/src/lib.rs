@@ -1,4 +1,5 @@
 // This file defines shared types and utilities used across the project.
 
 pub mod types;
-pub mod crypto;
\ No newline at end of file
+pub mod crypto;
+pub mod qubic;
\ No newline at end of file
@@ -0,0 +1,189 @@
+// Contract inputs and outputs travel as the raw bytes of their C++ structs. These helpers lay
+// fields out the way the compiler does: each at the next multiple of its alignment, the struct
+// padded to its largest. `id` is an `m256i` and 32-byte aligned, so it moves everything after it.
+use super::QubicError;
+
+/// A Qubic identity (public key) or any other 256-bit value, as `id`/`m256i`.
+pub type Id = [u8; 32];
+
+/// `NULL_ID`, which fills unused `Array<id, N>` slots.
+pub const NULL_ID: Id = [0; 32];
+
+pub const ID_ALIGN: usize = 32;
+pub const U64_ALIGN: usize = 8;
+
+/// A contract input or output struct with a fixed C++ layout.
+pub trait QpiStruct: Sized {
+    /// `sizeof` the C++ struct; what `inputSize` says and what an output query returns.
+    const SIZE: usize;
+
+    fn encode(&self) -> Vec<u8>;
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError>;
+}
+
+/// `BitArray<64 * WORDS>`: bits packed into 64-bit words, bit `i` at `1 << (i % 64)` of word
+/// `i / 64`. `bit_4096` is `BitArray<64>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitArray<const WORDS: usize>(pub [u64; WORDS]);
+
+pub type Bit4096 = BitArray<64>;
+
+impl<const WORDS: usize> BitArray<WORDS> {
+    pub const BITS: usize = WORDS * 64;
+
+    pub fn new() -> Self {
+        BitArray([0; WORDS])
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        self.0[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        let mask = 1u64 << (index % 64);
+        if value {
+            self.0[index / 64] |= mask;
+        } else {
+            self.0[index / 64] &= !mask;
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for BitArray<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Appends fields at their aligned offsets.
+#[derive(Debug, Default)]
+pub struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Writer::default()
+    }
+
+    fn align(&mut self, alignment: usize) {
+        let padded = self.bytes.len().next_multiple_of(alignment);
+        self.bytes.resize(padded, 0);
+    }
+
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.bytes.push(value);
+        self
+    }
+
+    /// `bit`, a one-byte bool.
+    pub fn bit(&mut self, value: bool) -> &mut Self {
+        self.u8(value as u8)
+    }
+
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.align(U64_ALIGN);
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn id(&mut self, value: &Id) -> &mut Self {
+        self.align(ID_ALIGN);
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    /// `Array<uint8, N>`.
+    pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    /// `Array<id, N>`.
+    pub fn ids(&mut self, values: &[Id]) -> &mut Self {
+        self.align(ID_ALIGN);
+        for value in values {
+            self.bytes.extend_from_slice(value);
+        }
+        self
+    }
+
+    pub fn bit_array<const WORDS: usize>(&mut self, value: &BitArray<WORDS>) -> &mut Self {
+        self.align(U64_ALIGN);
+        for word in value.0 {
+            self.bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        self
+    }
+
+    /// The struct's bytes, padded to `size`.
+    pub fn finish(&mut self, size: usize) -> Vec<u8> {
+        debug_assert!(self.bytes.len() <= size, "fields overrun the struct size");
+        let mut bytes = std::mem::take(&mut self.bytes);
+        bytes.resize(size, 0);
+        bytes
+    }
+}
+
+/// Reads fields back from their aligned offsets.
+#[derive(Debug)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Fails unless `bytes` is exactly one struct of `size` bytes.
+    pub fn new(bytes: &'a [u8], size: usize) -> Result<Self, QubicError> {
+        if bytes.len() != size {
+            return Err(QubicError::StructSize { expected: size, actual: bytes.len() });
+        }
+        Ok(Reader { bytes, offset: 0 })
+    }
+
+    fn take(&mut self, alignment: usize, len: usize) -> &'a [u8] {
+        self.offset = self.offset.next_multiple_of(alignment);
+        let field = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        field
+    }
+
+    pub fn u8(&mut self) -> u8 {
+        self.take(1, 1)[0]
+    }
+
+    pub fn bit(&mut self) -> bool {
+        self.u8() != 0
+    }
+
+    pub fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.take(U64_ALIGN, 8).try_into().expect("8 bytes"))
+    }
+
+    pub fn id(&mut self) -> Id {
+        self.take(ID_ALIGN, 32).try_into().expect("32 bytes")
+    }
+
+    pub fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        self.take(1, N).try_into().expect("N bytes")
+    }
+
+    pub fn ids<const N: usize>(&mut self) -> [Id; N] {
+        let mut values = [NULL_ID; N];
+        let field = self.take(ID_ALIGN, 32 * N);
+        for (value, chunk) in values.iter_mut().zip(field.chunks_exact(32)) {
+            value.copy_from_slice(chunk);
+        }
+        values
+    }
+
+    pub fn bit_array<const WORDS: usize>(&mut self) -> BitArray<WORDS> {
+        let mut words = [0u64; WORDS];
+        let field = self.take(U64_ALIGN, 8 * WORDS);
+        for (word, chunk) in words.iter_mut().zip(field.chunks_exact(8)) {
+            *word = u64::from_le_bytes(chunk.try_into().expect("8 bytes"));
+        }
+        BitArray(words)
+    }
+}
@@ -0,0 +1,352 @@
+// FourQ and the SchnorrQ signatures Qubic signs transactions with, following FourQlib and
+// Qubic core's `sign`/`verify`. The curve is the twisted Edwards curve -x² + y² = 1 + d·x²·y²
+// over GF(p²), p = 2^127 - 1. Scalar multiplication adds on every bit, so its timing does not
+// depend on the key; nothing here is tuned for speed.
+use super::k12::kangaroo_twelve;
+
+const P: u128 = (1 << 127) - 1;
+
+/// Curve parameter d.
+const D: F2 = F2 { re: 0x0000_0000_0000_00E4_0000_0000_0000_0142, im: 0x5E47_2F84_6657_E0FC_B382_1488_F1FC_0C8D };
+
+/// Affine coordinates of the generator of the prime-order subgroup.
+const GENERATOR_X: F2 = F2 { re: 0x1A34_7223_7C2F_B305_2865_92AD_7B38_33AA, im: 0x1E1F_553F_2878_AA9C_9686_9FB3_60AC_77F6 };
+const GENERATOR_Y: F2 = F2 { re: 0x0E3F_EE9B_A120_785A_B924_A246_2BCB_B287, im: 0x6E1C_4AF8_630E_0242_49A7_C344_844C_8B5C };
+
+/// Order N of the generator, little-endian 64-bit limbs.
+const ORDER: Scalar = [0x2FB2_540E_C776_8CE7, 0xDFBD_004D_FE0F_7999, 0xF053_9782_9CBC_14E5, 0x0029_CBC1_4E5E_0A72];
+
+// GF(p), elements fully reduced to [0, p)
+
+fn fp_reduce(x: u128) -> u128 {
+    let x = (x & P) + (x >> 127);
+    if x >= P {
+        x - P
+    } else {
+        x
+    }
+}
+
+fn fp_add(a: u128, b: u128) -> u128 {
+    fp_reduce(a + b)
+}
+
+fn fp_sub(a: u128, b: u128) -> u128 {
+    if a >= b {
+        a - b
+    } else {
+        a + P - b
+    }
+}
+
+fn fp_mul(a: u128, b: u128) -> u128 {
+    let (a0, a1) = (a as u64 as u128, a >> 64);
+    let (b0, b1) = (b as u64 as u128, b >> 64);
+    let mid = a0 * b1 + a1 * b0;
+    let (low, carry) = (a0 * b0).overflowing_add(mid << 64);
+    let high = a1 * b1 + (mid >> 64) + carry as u128;
+    // 2^128 = 2 (mod p), and high < 2^126 since both factors are below 2^127
+    fp_reduce((low & P) + (low >> 127) + 2 * high)
+}
+
+fn fp_pow(a: u128, exponent: u128) -> u128 {
+    let mut result = 1;
+    for bit in (0..128).rev() {
+        result = fp_mul(result, result);
+        if (exponent >> bit) & 1 == 1 {
+            result = fp_mul(result, a);
+        }
+    }
+    result
+}
+
+fn fp_inv(a: u128) -> u128 {
+    fp_pow(a, P - 2)
+}
+
+/// A square root of `a`, if it has one. p = 3 (mod 4), so it is a^((p + 1) / 4).
+fn fp_sqrt(a: u128) -> Option<u128> {
+    let root = fp_pow(a, 1 << 125);
+    (fp_mul(root, root) == a).then_some(root)
+}
+
+/// GF(p²) = GF(p)[i] / (i² + 1).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct F2 {
+    re: u128,
+    im: u128,
+}
+
+impl F2 {
+    const ZERO: F2 = F2 { re: 0, im: 0 };
+    const ONE: F2 = F2 { re: 1, im: 0 };
+
+    fn add(self, other: F2) -> F2 {
+        F2 { re: fp_add(self.re, other.re), im: fp_add(self.im, other.im) }
+    }
+
+    fn sub(self, other: F2) -> F2 {
+        F2 { re: fp_sub(self.re, other.re), im: fp_sub(self.im, other.im) }
+    }
+
+    fn neg(self) -> F2 {
+        F2::ZERO.sub(self)
+    }
+
+    fn mul(self, other: F2) -> F2 {
+        F2 {
+            re: fp_sub(fp_mul(self.re, other.re), fp_mul(self.im, other.im)),
+            im: fp_add(fp_mul(self.re, other.im), fp_mul(self.im, other.re)),
+        }
+    }
+
+    fn inv(self) -> F2 {
+        let norm = fp_inv(fp_add(fp_mul(self.re, self.re), fp_mul(self.im, self.im)));
+        F2 { re: fp_mul(self.re, norm), im: fp_sub(0, fp_mul(self.im, norm)) }
+    }
+
+    fn sqrt(self) -> Option<F2> {
+        if self.im == 0 {
+            return match fp_sqrt(self.re) {
+                Some(root) => Some(F2 { re: root, im: 0 }),
+                // (r·i)² = -r²
+                None => fp_sqrt(fp_sub(0, self.re)).map(|root| F2 { re: 0, im: root }),
+            };
+        }
+        let norm = fp_sqrt(fp_add(fp_mul(self.re, self.re), fp_mul(self.im, self.im)))?;
+        let half = 1 << 126;
+        let re = fp_sqrt(fp_mul(fp_add(self.re, norm), half))
+            .or_else(|| fp_sqrt(fp_mul(fp_sub(self.re, norm), half)))?;
+        let root = F2 { re, im: fp_mul(self.im, fp_inv(fp_add(re, re))) };
+        (root.mul(root) == self).then_some(root)
+    }
+
+    /// Bit 126 of the real part, or of the imaginary part when the real part is zero;
+    /// negation flips it.
+    fn sign(self) -> u8 {
+        let part = if self.re == 0 { self.im } else { self.re };
+        ((part >> 126) & 1) as u8
+    }
+
+    fn select(self, other: F2, mask: u128) -> F2 {
+        F2 { re: self.re ^ ((self.re ^ other.re) & mask), im: self.im ^ ((self.im ^ other.im) & mask) }
+    }
+}
+
+/// Extended twisted Edwards coordinates (X : Y : Z : T) with T = X·Y / Z.
+#[derive(Clone, Copy, Debug)]
+struct Point {
+    x: F2,
+    y: F2,
+    z: F2,
+    t: F2,
+}
+
+impl Point {
+    const IDENTITY: Point = Point { x: F2::ZERO, y: F2::ONE, z: F2::ONE, t: F2::ZERO };
+
+    fn from_affine(x: F2, y: F2) -> Point {
+        Point { x, y, z: F2::ONE, t: x.mul(y) }
+    }
+
+    fn generator() -> Point {
+        Point::from_affine(GENERATOR_X, GENERATOR_Y)
+    }
+
+    /// Unified addition for a = -1 (Hisil–Wong–Carter–Dawson); also doubles.
+    fn add(&self, other: &Point) -> Point {
+        let a = self.y.sub(self.x).mul(other.y.sub(other.x));
+        let b = self.y.add(self.x).mul(other.y.add(other.x));
+        let c = self.t.mul(D.add(D)).mul(other.t);
+        let d = self.z.mul(other.z.add(other.z));
+        let (e, f, g, h) = (b.sub(a), d.sub(c), d.add(c), b.add(a));
+        Point { x: e.mul(f), y: g.mul(h), z: f.mul(g), t: e.mul(h) }
+    }
+
+    fn select(&self, other: &Point, choose_other: bool) -> Point {
+        let mask = 0u128.wrapping_sub(choose_other as u128);
+        Point {
+            x: self.x.select(other.x, mask),
+            y: self.y.select(other.y, mask),
+            z: self.z.select(other.z, mask),
+            t: self.t.select(other.t, mask),
+        }
+    }
+
+    /// `scalar · self` for a 256-bit little-endian scalar.
+    fn mul(&self, scalar: &[u8; 32]) -> Point {
+        let mut result = Point::IDENTITY;
+        for bit in (0..256).rev() {
+            result = result.add(&result);
+            let sum = result.add(self);
+            result = result.select(&sum, (scalar[bit / 8] >> (bit % 8)) & 1 == 1);
+        }
+        result
+    }
+
+    /// The y coordinate, with the sign of x in the top bit.
+    fn encode(&self) -> [u8; 32] {
+        let z = self.z.inv();
+        let (x, y) = (self.x.mul(z), self.y.mul(z));
+        let mut encoded = [0u8; 32];
+        encoded[..16].copy_from_slice(&y.re.to_le_bytes());
+        encoded[16..].copy_from_slice(&y.im.to_le_bytes());
+        encoded[31] |= x.sign() << 7;
+        encoded
+    }
+
+    fn decode(encoded: &[u8; 32]) -> Option<Point> {
+        let re = u128::from_le_bytes(encoded[..16].try_into().expect("16 bytes"));
+        let im = u128::from_le_bytes(encoded[16..].try_into().expect("16 bytes")) & P;
+        if re >= P || im >= P {
+            return None;
+        }
+        let y = F2 { re, im };
+        let yy = y.mul(y);
+        // x² = (y² - 1) / (d·y² + 1)
+        let mut x = yy.sub(F2::ONE).mul(D.mul(yy).add(F2::ONE).inv()).sqrt()?;
+        let sign = encoded[31] >> 7;
+        if x.sign() != sign {
+            x = x.neg();
+        }
+        (x.sign() == sign).then(|| Point::from_affine(x, y))
+    }
+}
+
+/// Integers modulo the group order, little-endian 64-bit limbs.
+type Scalar = [u64; 4];
+
+fn scalar_from_bytes(bytes: &[u8]) -> Scalar {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("8 bytes"));
+    }
+    limbs
+}
+
+fn scalar_to_bytes(scalar: &Scalar) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(scalar) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    bytes
+}
+
+fn limbs_add(a: &Scalar, b: &Scalar) -> Scalar {
+    let mut sum = [0u64; 4];
+    let mut carry = false;
+    for i in 0..4 {
+        let (partial, first) = a[i].overflowing_add(b[i]);
+        let (partial, second) = partial.overflowing_add(carry as u64);
+        sum[i] = partial;
+        carry = first || second;
+    }
+    sum
+}
+
+fn limbs_sub(a: &Scalar, b: &Scalar) -> (Scalar, bool) {
+    let mut difference = [0u64; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (partial, first) = a[i].overflowing_sub(b[i]);
+        let (partial, second) = partial.overflowing_sub(borrow as u64);
+        difference[i] = partial;
+        borrow = first || second;
+    }
+    (difference, borrow)
+}
+
+/// `a + b` for reduced `a` and `b`. Both are below 2^246, so the sum cannot overflow.
+fn scalar_add(a: &Scalar, b: &Scalar) -> Scalar {
+    let sum = limbs_add(a, b);
+    match limbs_sub(&sum, &ORDER) {
+        (reduced, false) => reduced,
+        (_, true) => sum,
+    }
+}
+
+fn scalar_sub(a: &Scalar, b: &Scalar) -> Scalar {
+    match limbs_sub(a, b) {
+        (difference, false) => difference,
+        (difference, true) => limbs_add(&difference, &ORDER),
+    }
+}
+
+/// Any 256-bit value, reduced modulo the order.
+fn scalar_reduce(value: &Scalar) -> Scalar {
+    let mut reduced = [0u64; 4];
+    for bit in (0..256).rev() {
+        reduced = scalar_add(&reduced, &reduced);
+        if (value[bit / 64] >> (bit % 64)) & 1 == 1 {
+            reduced = scalar_add(&reduced, &[1, 0, 0, 0]);
+        }
+    }
+    reduced
+}
+
+fn scalar_mul(a: &Scalar, b: &Scalar) -> Scalar {
+    let mut product = [0u64; 4];
+    for bit in (0..256).rev() {
+        product = scalar_add(&product, &product);
+        if (b[bit / 64] >> (bit % 64)) & 1 == 1 {
+            product = scalar_add(&product, a);
+        }
+    }
+    product
+}
+
+/// Encoded `private_key · G`, as Qubic core's `getPublicKey`.
+pub fn public_key(private_key: &[u8; 32]) -> [u8; 32] {
+    Point::generator().mul(private_key).encode()
+}
+
+/// SchnorrQ signature of `digest` as Qubic core's `sign`: the private key is the first half
+/// of K12(`subseed`), the nonce comes from the second half and the digest.
+pub fn sign(subseed: &[u8; 32], public_key: &[u8; 32], digest: &[u8; 32]) -> [u8; 64] {
+    let mut k = [0u8; 64];
+    kangaroo_twelve(subseed, &mut k);
+
+    let mut nonce_input = [0u8; 64];
+    nonce_input[..32].copy_from_slice(&k[32..]);
+    nonce_input[32..].copy_from_slice(digest);
+    let mut r = [0u8; 64];
+    kangaroo_twelve(&nonce_input, &mut r);
+    let r: [u8; 32] = r[..32].try_into().expect("32 bytes");
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&Point::generator().mul(&r).encode());
+
+    let h = challenge(signature[..32].try_into().expect("32 bytes"), public_key, digest);
+    let private_key = scalar_reduce(&scalar_from_bytes(&k[..32]));
+    let s = scalar_sub(&scalar_reduce(&scalar_from_bytes(&r)), &scalar_mul(&h, &private_key));
+    signature[32..].copy_from_slice(&scalar_to_bytes(&s));
+    signature
+}
+
+/// Whether `signature` over `digest` was made by the holder of `public_key`, as Qubic
+/// core's `verify`.
+pub fn verify(public_key: &[u8; 32], digest: &[u8; 32], signature: &[u8; 64]) -> bool {
+    // Non-canonical encodings and an s of 246 bits or more are rejected outright
+    if public_key[15] & 0x80 != 0 || signature[15] & 0x80 != 0 || signature[62] & 0xC0 != 0 || signature[63] != 0 {
+        return false;
+    }
+    let Some(a) = Point::decode(public_key) else {
+        return false;
+    };
+    let r: [u8; 32] = signature[..32].try_into().expect("32 bytes");
+    let s: [u8; 32] = signature[32..].try_into().expect("32 bytes");
+    let h = scalar_to_bytes(&challenge(&r, public_key, digest));
+
+    Point::generator().mul(&s).add(&a.mul(&h)).encode() == r
+}
+
+/// h = K12(R || A || digest), reduced modulo the order.
+fn challenge(r: &[u8; 32], public_key: &[u8; 32], digest: &[u8; 32]) -> Scalar {
+    let mut input = [0u8; 96];
+    input[..32].copy_from_slice(r);
+    input[32..64].copy_from_slice(public_key);
+    input[64..].copy_from_slice(digest);
+    let mut h = [0u8; 64];
+    kangaroo_twelve(&input, &mut h);
+    scalar_reduce(&scalar_from_bytes(&h[..32]))
+}
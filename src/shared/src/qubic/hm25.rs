@@ -0,0 +1,356 @@
+// The HM25 bridge contract's inputs and outputs in their on-chain byte layout. Field order and
+// types follow the contract; offsets are noted where alignment pads. Procedure outputs carry
+// `success`, which the contract clears instead of failing the call.
+use super::abi::{Id, QpiStruct, Reader, Writer, NULL_ID};
+use super::QubicError;
+
+// Registration indices (`REGISTER_USER_PROCEDURE` / `REGISTER_USER_FUNCTION`)
+pub const PROCEDURE_LOCK_ASSETS: u16 = 1;
+pub const PROCEDURE_UNLOCK_ASSETS: u16 = 2;
+pub const PROCEDURE_ADD_VALIDATOR: u16 = 3;
+pub const PROCEDURE_REMOVE_VALIDATOR: u16 = 4;
+pub const PROCEDURE_UPDATE_CONFIG: u16 = 5;
+pub const FUNCTION_GET_BRIDGE_INFO: u16 = 1;
+pub const FUNCTION_GET_LOCK_INFO: u16 = 2;
+
+/// Slots in `validatorSignatures` (`Array<id, 16>`).
+pub const MAX_VALIDATORS: usize = 16;
+
+/// `LockAssets_input`. The locked amount is also sent as the invocation reward.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockAssetsInput {
+    /// `Array<uint8, 32>`
+    pub solana_destination: [u8; 32],
+    pub amount: u64,
+    /// `Array<uint8, 64>`
+    pub memo: [u8; 64],
+}
+
+impl QpiStruct for LockAssetsInput {
+    const SIZE: usize = 104;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new().bytes(&self.solana_destination).u64(self.amount).bytes(&self.memo).finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        let mut reader = Reader::new(bytes, Self::SIZE)?;
+        Ok(LockAssetsInput { solana_destination: reader.bytes(), amount: reader.u64(), memo: reader.bytes() })
+    }
+}
+
+/// `LockAssets_output`: `lockId` at 8, after `success` is padded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockAssetsOutput {
+    pub success: bool,
+    pub lock_id: u64,
+    pub actual_amount: u64,
+    pub fee: u64,
+}
+
+impl QpiStruct for LockAssetsOutput {
+    const SIZE: usize = 32;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new()
+            .bit(self.success)
+            .u64(self.lock_id)
+            .u64(self.actual_amount)
+            .u64(self.fee)
+            .finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        let mut reader = Reader::new(bytes, Self::SIZE)?;
+        Ok(LockAssetsOutput {
+            success: reader.bit(),
+            lock_id: reader.u64(),
+            actual_amount: reader.u64(),
+            fee: reader.u64(),
+        })
+    }
+}
+
+/// `UnlockAssets_input`: `recipient` at 32, `amount` at 64 and `validatorSignatures` at 96.
+/// The signatures name the validators that signed off; unused slots are `NULL_ID`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlockAssetsInput {
+    pub lock_id: u64,
+    pub recipient: Id,
+    pub amount: u64,
+    pub validator_signatures: [Id; MAX_VALIDATORS],
+}
+
+impl UnlockAssetsInput {
+    pub fn new(lock_id: u64, recipient: Id, amount: u64, signers: &[Id]) -> Result<Self, QubicError> {
+        if signers.len() > MAX_VALIDATORS {
+            return Err(QubicError::TooManySigners(signers.len()));
+        }
+        let mut validator_signatures = [NULL_ID; MAX_VALIDATORS];
+        validator_signatures[..signers.len()].copy_from_slice(signers);
+        Ok(UnlockAssetsInput { lock_id, recipient, amount, validator_signatures })
+    }
+
+    /// The filled signature slots.
+    pub fn signers(&self) -> impl Iterator<Item = &Id> {
+        self.validator_signatures.iter().filter(|id| **id != NULL_ID)
+    }
+}
+
+impl QpiStruct for UnlockAssetsInput {
+    const SIZE: usize = 608;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new()
+            .u64(self.lock_id)
+            .id(&self.recipient)
+            .u64(self.amount)
+            .ids(&self.validator_signatures)
+            .finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        let mut reader = Reader::new(bytes, Self::SIZE)?;
+        Ok(UnlockAssetsInput {
+            lock_id: reader.u64(),
+            recipient: reader.id(),
+            amount: reader.u64(),
+            validator_signatures: reader.ids(),
+        })
+    }
+}
+
+/// `UnlockAssets_output`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlockAssetsOutput {
+    pub success: bool,
+    /// Distinct registered validators among the signatures.
+    pub valid_signatures: u8,
+}
+
+impl QpiStruct for UnlockAssetsOutput {
+    const SIZE: usize = 2;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new().bit(self.success).u8(self.valid_signatures).finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        let mut reader = Reader::new(bytes, Self::SIZE)?;
+        Ok(UnlockAssetsOutput { success: reader.bit(), valid_signatures: reader.u8() })
+    }
+}
+
+/// `AddValidator_input` and `RemoveValidator_input`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorInput {
+    pub validator: Id,
+}
+
+impl QpiStruct for ValidatorInput {
+    const SIZE: usize = 32;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new().id(&self.validator).finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        Ok(ValidatorInput { validator: Reader::new(bytes, Self::SIZE)?.id() })
+    }
+}
+
+/// `AddValidator_output`, `RemoveValidator_output` and `UpdateConfig_output`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuccessOutput {
+    pub success: bool,
+}
+
+impl QpiStruct for SuccessOutput {
+    const SIZE: usize = 1;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new().bit(self.success).finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        Ok(SuccessOutput { success: Reader::new(bytes, Self::SIZE)?.bit() })
+    }
+}
+
+/// `UpdateConfig_input`, the same parameters as `update_config` on Solana.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateConfigInput {
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub required_signatures: u8,
+    pub is_active: bool,
+}
+
+impl QpiStruct for UpdateConfigInput {
+    const SIZE: usize = 32;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new()
+            .u64(self.min_lock_amount)
+            .u64(self.max_lock_amount)
+            .u64(self.bridge_fee)
+            .u8(self.required_signatures)
+            .bit(self.is_active)
+            .finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        let mut reader = Reader::new(bytes, Self::SIZE)?;
+        Ok(UpdateConfigInput {
+            min_lock_amount: reader.u64(),
+            max_lock_amount: reader.u64(),
+            bridge_fee: reader.u64(),
+            required_signatures: reader.u8(),
+            is_active: reader.bit(),
+        })
+    }
+}
+
+/// `GetBridgeInfo_input` is empty and is sent as zero bytes, as qubic-cli does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GetBridgeInfoInput;
+
+impl QpiStruct for GetBridgeInfoInput {
+    const SIZE: usize = 0;
+
+    fn encode(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        Reader::new(bytes, Self::SIZE)?;
+        Ok(GetBridgeInfoInput)
+    }
+}
+
+/// `GetBridgeInfo_output`: `isActive` at 56, the counters from 64, padded to 128.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetBridgeInfoOutput {
+    pub admin: Id,
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub is_active: bool,
+    pub validator_count: u64,
+    pub required_signatures: u64,
+    pub total_locked: u64,
+    pub total_unlocked: u64,
+    pub total_transfers: u64,
+    pub total_validator_actions: u64,
+    pub next_lock_id: u64,
+}
+
+impl QpiStruct for GetBridgeInfoOutput {
+    const SIZE: usize = 128;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new()
+            .id(&self.admin)
+            .u64(self.min_lock_amount)
+            .u64(self.max_lock_amount)
+            .u64(self.bridge_fee)
+            .bit(self.is_active)
+            .u64(self.validator_count)
+            .u64(self.required_signatures)
+            .u64(self.total_locked)
+            .u64(self.total_unlocked)
+            .u64(self.total_transfers)
+            .u64(self.total_validator_actions)
+            .u64(self.next_lock_id)
+            .finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        let mut reader = Reader::new(bytes, Self::SIZE)?;
+        Ok(GetBridgeInfoOutput {
+            admin: reader.id(),
+            min_lock_amount: reader.u64(),
+            max_lock_amount: reader.u64(),
+            bridge_fee: reader.u64(),
+            is_active: reader.bit(),
+            validator_count: reader.u64(),
+            required_signatures: reader.u64(),
+            total_locked: reader.u64(),
+            total_unlocked: reader.u64(),
+            total_transfers: reader.u64(),
+            total_validator_actions: reader.u64(),
+            next_lock_id: reader.u64(),
+        })
+    }
+}
+
+/// `GetLockInfo_input`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetLockInfoInput {
+    pub lock_id: u64,
+}
+
+impl QpiStruct for GetLockInfoInput {
+    const SIZE: usize = 8;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new().u64(self.lock_id).finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        Ok(GetLockInfoInput { lock_id: Reader::new(bytes, Self::SIZE)?.u64() })
+    }
+}
+
+/// `GetLockInfo_output`: `locker` at 32, the amounts from 64, `solanaDestination` at 96,
+/// `memo` at 128 and `status` at 192, padded to 224. An unknown lock id reads as
+/// `found: false` with every other field zeroed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetLockInfoOutput {
+    pub found: bool,
+    pub locker: Id,
+    /// Amount sent with the call, before the fee.
+    pub amount: u64,
+    /// Amount to release on Solana (`amount - fee`).
+    pub actual_amount: u64,
+    pub fee: u64,
+    /// Tick the lock was made in.
+    pub timestamp: u64,
+    pub solana_destination: [u8; 32],
+    pub memo: [u8; 64],
+    pub status: u8,
+}
+
+impl QpiStruct for GetLockInfoOutput {
+    const SIZE: usize = 224;
+
+    fn encode(&self) -> Vec<u8> {
+        Writer::new()
+            .bit(self.found)
+            .id(&self.locker)
+            .u64(self.amount)
+            .u64(self.actual_amount)
+            .u64(self.fee)
+            .u64(self.timestamp)
+            .bytes(&self.solana_destination)
+            .bytes(&self.memo)
+            .u8(self.status)
+            .finish(Self::SIZE)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, QubicError> {
+        let mut reader = Reader::new(bytes, Self::SIZE)?;
+        Ok(GetLockInfoOutput {
+            found: reader.bit(),
+            locker: reader.id(),
+            amount: reader.u64(),
+            actual_amount: reader.u64(),
+            fee: reader.u64(),
+            timestamp: reader.u64(),
+            solana_destination: reader.bytes(),
+            memo: reader.bytes(),
+            status: reader.u8(),
+        })
+    }
+}
@@ -0,0 +1,122 @@
+// Qubic seeds, keys and identities. A seed is 55 lowercase letters; the subseed, private key
+// and public key follow from it by K12 and FourQ. An identity is the public key written as
+// 56 letters plus a 4-letter checksum; transaction ids are digests written the same way in
+// lowercase.
+use std::fmt;
+
+use super::fourq;
+use super::k12::{k12_digest, kangaroo_twelve};
+use super::QubicError;
+
+pub const SEED_LENGTH: usize = 55;
+pub const IDENTITY_LENGTH: usize = 60;
+
+/// Letters per 64-bit fragment of the key.
+const FRAGMENT_LETTERS: usize = 14;
+
+/// A Qubic signing key. The seed itself is not kept.
+#[derive(Clone)]
+pub struct QubicKeypair {
+    subseed: [u8; 32],
+    public_key: [u8; 32],
+}
+
+impl QubicKeypair {
+    pub fn from_seed(seed: &str) -> Result<Self, QubicError> {
+        let seed = seed.trim();
+        if seed.len() != SEED_LENGTH || !seed.bytes().all(|c| c.is_ascii_lowercase()) {
+            return Err(QubicError::InvalidSeed);
+        }
+        let letters: Vec<u8> = seed.bytes().map(|c| c - b'a').collect();
+        let subseed = k12_digest(&letters);
+        let public_key = fourq::public_key(&k12_digest(&subseed));
+        Ok(QubicKeypair { subseed, public_key })
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+
+    pub fn identity(&self) -> String {
+        identity(&self.public_key)
+    }
+
+    /// Signature of a 32-byte digest, e.g. a transaction's.
+    pub fn sign(&self, digest: &[u8; 32]) -> [u8; 64] {
+        fourq::sign(&self.subseed, &self.public_key, digest)
+    }
+}
+
+impl fmt::Debug for QubicKeypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QubicKeypair").field("identity", &self.identity()).finish_non_exhaustive()
+    }
+}
+
+/// The uppercase identity of `public_key`.
+pub fn identity(public_key: &[u8; 32]) -> String {
+    encode(public_key, b'A')
+}
+
+/// A digest (such as a transaction id) in Qubic's lowercase identity form.
+pub fn lowercase_identity(digest: &[u8; 32]) -> String {
+    encode(digest, b'a')
+}
+
+/// The public key an uppercase identity names, after checking its checksum.
+pub fn public_key_from_identity(identity: &str) -> Result<[u8; 32], QubicError> {
+    decode(identity, b'A')
+}
+
+/// The digest a lowercase identity (such as a transaction id) names.
+pub fn digest_from_lowercase_identity(identity: &str) -> Result<[u8; 32], QubicError> {
+    decode(identity, b'a')
+}
+
+fn encode(key: &[u8; 32], alphabet: u8) -> String {
+    let mut letters = Vec::with_capacity(IDENTITY_LENGTH);
+    for fragment in key.chunks_exact(8) {
+        let mut value = u64::from_le_bytes(fragment.try_into().expect("8 bytes"));
+        for _ in 0..FRAGMENT_LETTERS {
+            letters.push(alphabet + (value % 26) as u8);
+            value /= 26;
+        }
+    }
+    let mut checksum = checksum(key);
+    for _ in 0..4 {
+        letters.push(alphabet + (checksum % 26) as u8);
+        checksum /= 26;
+    }
+    String::from_utf8(letters).expect("ASCII letters")
+}
+
+fn decode(identity: &str, alphabet: u8) -> Result<[u8; 32], QubicError> {
+    let letters = identity.as_bytes();
+    if letters.len() != IDENTITY_LENGTH || !letters.iter().all(|&c| (alphabet..alphabet + 26).contains(&c)) {
+        return Err(QubicError::InvalidIdentity(identity.to_string()));
+    }
+
+    let mut key = [0u8; 32];
+    for (fragment, chunk) in key.chunks_exact_mut(8).zip(letters.chunks_exact(FRAGMENT_LETTERS)) {
+        let mut value: u64 = 0;
+        for &letter in chunk.iter().rev() {
+            value = value
+                .checked_mul(26)
+                .and_then(|value| value.checked_add((letter - alphabet) as u64))
+                .ok_or_else(|| QubicError::InvalidIdentity(identity.to_string()))?;
+        }
+        fragment.copy_from_slice(&value.to_le_bytes());
+    }
+
+    if encode(&key, alphabet) != identity {
+        return Err(QubicError::InvalidIdentity(identity.to_string()));
+    }
+    Ok(key)
+}
+
+/// The low 18 bits of the first three bytes of K12(`key`).
+fn checksum(key: &[u8; 32]) -> u32 {
+    let mut bytes = [0u8; 4];
+    kangaroo_twelve(key, &mut bytes[..3]);
+    u32::from_le_bytes(bytes) & 0x3FFFF
+}
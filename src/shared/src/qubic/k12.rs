@@ -0,0 +1,92 @@
+// KangarooTwelve, the hash Qubic uses for seeds, identities, transaction digests and
+// signatures. Built on TurboSHAKE128 (Keccak-p[1600] with 12 rounds); long inputs use the
+// tree mode with 8 KiB chunks. Only the empty customization string is supported.
+use keccak::p1600;
+
+const RATE: usize = 168;
+const ROUNDS: usize = 12;
+const CHUNK_SIZE: usize = 8192;
+
+// Domain separation bytes
+const SINGLE_NODE: u8 = 0x07;
+const FINAL_NODE: u8 = 0x06;
+const LEAF: u8 = 0x0B;
+
+/// Fill `output` with KangarooTwelve(`input`), as Qubic's `KangarooTwelve(input, len, output, outputLen)`.
+pub fn kangaroo_twelve(input: &[u8], output: &mut [u8]) {
+    // S = M || C || length_encode(|C|), with C empty
+    let mut message = Vec::with_capacity(input.len() + 1);
+    message.extend_from_slice(input);
+    message.push(0x00);
+
+    if message.len() <= CHUNK_SIZE {
+        return turbo_shake(&message, SINGLE_NODE, output);
+    }
+
+    let mut chunks = message.chunks(CHUNK_SIZE);
+    let mut node = Vec::with_capacity(CHUNK_SIZE + 8 + 32 * (message.len() / CHUNK_SIZE) + 12);
+    node.extend_from_slice(chunks.next().expect("message is longer than one chunk"));
+    node.extend_from_slice(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
+
+    let mut leaves = 0u64;
+    for chunk in chunks {
+        let mut chaining_value = [0u8; 32];
+        turbo_shake(chunk, LEAF, &mut chaining_value);
+        node.extend_from_slice(&chaining_value);
+        leaves += 1;
+    }
+    node.extend_from_slice(&length_encode(leaves));
+    node.extend_from_slice(&[0xFF, 0xFF]);
+
+    turbo_shake(&node, FINAL_NODE, output);
+}
+
+/// The 32-byte KangarooTwelve digest.
+pub fn k12_digest(input: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    kangaroo_twelve(input, &mut digest);
+    digest
+}
+
+fn turbo_shake(message: &[u8], separator: u8, output: &mut [u8]) {
+    let mut state = [0u64; 25];
+
+    let mut blocks = message.chunks_exact(RATE);
+    for block in &mut blocks {
+        absorb(&mut state, block);
+        p1600(&mut state, ROUNDS);
+    }
+    let rest = blocks.remainder();
+    let mut last = [0u8; RATE];
+    last[..rest.len()].copy_from_slice(rest);
+    last[rest.len()] ^= separator;
+    last[RATE - 1] ^= 0x80;
+    absorb(&mut state, &last);
+    p1600(&mut state, ROUNDS);
+
+    for (index, block) in output.chunks_mut(RATE).enumerate() {
+        if index > 0 {
+            p1600(&mut state, ROUNDS);
+        }
+        let mut bytes = [0u8; RATE];
+        for (lane, word) in bytes.chunks_exact_mut(8).zip(state.iter()) {
+            lane.copy_from_slice(&word.to_le_bytes());
+        }
+        block.copy_from_slice(&bytes[..block.len()]);
+    }
+}
+
+fn absorb(state: &mut [u64; 25], block: &[u8]) {
+    for (word, lane) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *word ^= u64::from_le_bytes(lane.try_into().expect("8-byte lane"));
+    }
+}
+
+/// Big-endian `value` without leading zeros, followed by its byte count.
+fn length_encode(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&byte| byte == 0).count();
+    let mut encoded = bytes[skip..].to_vec();
+    encoded.push(encoded.len() as u8);
+    encoded
+}
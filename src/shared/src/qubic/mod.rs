@@ -0,0 +1,35 @@
+// Native Qubic primitives: the K12 hash, FourQ signatures, identities, the binary
+// transaction format nodes accept on `broadcast-transaction` and the HM25 contract's
+// inputs and outputs in their C++ layout.
+use thiserror::Error;
+
+pub mod abi;
+pub mod fourq;
+pub mod hm25;
+pub mod identity;
+pub mod k12;
+pub mod transaction;
+
+pub use abi::{Id, QpiStruct, NULL_ID};
+pub use identity::{identity, public_key_from_identity, QubicKeypair};
+pub use transaction::{contract_id, QubicTransaction, HM25_CONTRACT_INDEX};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QubicError {
+    #[error("a seed is 55 lowercase letters")]
+    InvalidSeed,
+    #[error("{0:?} is not a valid identity")]
+    InvalidIdentity(String),
+    #[error("amount {0} is negative")]
+    InvalidAmount(i64),
+    #[error("input of {0} bytes is over the {} byte limit", transaction::MAX_INPUT_SIZE)]
+    InputTooLarge(usize),
+    #[error("{0} bytes is not a whole transaction")]
+    Truncated(usize),
+    #[error("the keypair is not the transaction's source")]
+    WrongSigner,
+    #[error("expected a {expected} byte struct, got {actual} bytes")]
+    StructSize { expected: usize, actual: usize },
+    #[error("{0} validator signatures do not fit in {} slots", hm25::MAX_VALIDATORS)]
+    TooManySigners(usize),
+}
@@ -0,0 +1,129 @@
+// Qubic transactions: an 80-byte header, the input payload and a SchnorrQ signature over
+// K12 of everything before it. Procedure calls go to a contract's id with the procedure's
+// index as the input type; the amount is the invocation reward.
+use super::identity::{lowercase_identity, QubicKeypair};
+use super::k12::k12_digest;
+use super::{fourq, QubicError};
+
+/// Source, destination, amount, tick, input type and input size.
+pub const HEADER_SIZE: usize = 32 + 32 + 8 + 4 + 2 + 2;
+pub const SIGNATURE_SIZE: usize = 64;
+/// Largest input a node accepts (`MAX_INPUT_SIZE`).
+pub const MAX_INPUT_SIZE: usize = 1024;
+
+/// Contract index of the HM25 bridge contract.
+pub const HM25_CONTRACT_INDEX: u32 = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QubicTransaction {
+    pub source: [u8; 32],
+    pub destination: [u8; 32],
+    pub amount: i64,
+    /// The tick the transaction must be included in; it is dropped after that.
+    pub tick: u32,
+    pub input_type: u16,
+    pub input: Vec<u8>,
+    pub signature: [u8; 64],
+}
+
+impl QubicTransaction {
+    /// An unsigned transaction from `source`.
+    pub fn new(
+        source: [u8; 32],
+        destination: [u8; 32],
+        amount: i64,
+        tick: u32,
+        input_type: u16,
+        input: Vec<u8>,
+    ) -> Result<Self, QubicError> {
+        if amount < 0 {
+            return Err(QubicError::InvalidAmount(amount));
+        }
+        if input.len() > MAX_INPUT_SIZE {
+            return Err(QubicError::InputTooLarge(input.len()));
+        }
+        Ok(QubicTransaction { source, destination, amount, tick, input_type, input, signature: [0; 64] })
+    }
+
+    /// A call of procedure `input_type` on contract `contract_index`.
+    pub fn procedure_call(
+        source: [u8; 32],
+        contract_index: u32,
+        invocation_reward: i64,
+        tick: u32,
+        input_type: u16,
+        input: Vec<u8>,
+    ) -> Result<Self, QubicError> {
+        Self::new(source, contract_id(contract_index), invocation_reward, tick, input_type, input)
+    }
+
+    /// Everything the signature covers.
+    pub fn unsigned_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.input.len() + SIGNATURE_SIZE);
+        bytes.extend_from_slice(&self.source);
+        bytes.extend_from_slice(&self.destination);
+        bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(&self.tick.to_le_bytes());
+        bytes.extend_from_slice(&self.input_type.to_le_bytes());
+        bytes.extend_from_slice(&(self.input.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&self.input);
+        bytes
+    }
+
+    /// K12 of the unsigned bytes; what the source signs.
+    pub fn digest(&self) -> [u8; 32] {
+        k12_digest(&self.unsigned_bytes())
+    }
+
+    pub fn sign(&mut self, keypair: &QubicKeypair) -> Result<(), QubicError> {
+        if keypair.public_key() != self.source {
+            return Err(QubicError::WrongSigner);
+        }
+        self.signature = keypair.sign(&self.digest());
+        Ok(())
+    }
+
+    pub fn verify(&self) -> bool {
+        fourq::verify(&self.source, &self.digest(), &self.signature)
+    }
+
+    /// The signed transaction as broadcast.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.unsigned_bytes();
+        bytes.extend_from_slice(&self.signature);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QubicError> {
+        if bytes.len() < HEADER_SIZE + SIGNATURE_SIZE {
+            return Err(QubicError::Truncated(bytes.len()));
+        }
+        let input_size = u16::from_le_bytes([bytes[78], bytes[79]]) as usize;
+        if bytes.len() != HEADER_SIZE + input_size + SIGNATURE_SIZE {
+            return Err(QubicError::Truncated(bytes.len()));
+        }
+        let array = |range: std::ops::Range<usize>| bytes[range].to_vec();
+        let mut transaction = QubicTransaction::new(
+            array(0..32).try_into().expect("32 bytes"),
+            array(32..64).try_into().expect("32 bytes"),
+            i64::from_le_bytes(array(64..72).try_into().expect("8 bytes")),
+            u32::from_le_bytes(array(72..76).try_into().expect("4 bytes")),
+            u16::from_le_bytes([bytes[76], bytes[77]]),
+            array(HEADER_SIZE..HEADER_SIZE + input_size),
+        )?;
+        transaction.signature = array(HEADER_SIZE + input_size..bytes.len()).try_into().expect("64 bytes");
+        Ok(transaction)
+    }
+
+    /// The transaction id: K12 of the signed bytes, in lowercase identity form.
+    pub fn id(&self) -> String {
+        lowercase_identity(&k12_digest(&self.to_bytes()))
+    }
+}
+
+/// The id procedure calls to contract `index` are sent to: the index in the first 8 bytes.
+pub fn contract_id(index: u32) -> [u8; 32] {
+    let mut id = [0u8; 32];
+    id[..8].copy_from_slice(&(index as u64).to_le_bytes());
+    id
+}
@@ -32,7 +32,7 @@ pub enum BridgeStatus {
     Expired,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum BridgeDirection {
     SolanaToQubic,
     QubicToSolana,
@@ -187,6 +187,11 @@ impl BridgeTransaction {
         }
     }
 
+    /// Lock ids are only unique per source chain, so transfers are keyed by both.
+    pub fn key(&self) -> (BridgeDirection, u64) {
+        (self.direction, self.id)
+    }
+
     pub fn is_expired(&self, timeout_seconds: u64) -> bool {
         let now = chrono::Utc::now().timestamp();
         (now - self.created_at) as u64 > timeout_seconds
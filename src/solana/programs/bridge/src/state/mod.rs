@@ -1,7 +0,0 @@
-// This file defines the state management for the bridge program.
-
-pub mod bridge_state;
-pub mod validator_set;
-
-pub use bridge_state::*;
-pub use validator_set::*;
\ No newline at end of file
@@ -1,30 +0,0 @@
-// src/solana/programs/validator/src/instructions/validate.rs
-use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey, program_error::ProgramError};
-
-pub fn process_validate(
-    _program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {
-    msg!("Processing validate instruction");
-    validate_transaction(accounts, instruction_data)
-}
-
-pub fn validate_transaction(
-    accounts: &[AccountInfo],
-    _transaction_data: &[u8],
-) -> ProgramResult {
-    // Implement transaction validation logic here
-    msg!("Validating transaction...");
-
-    // Example validation logic
-    if accounts.is_empty() {
-        msg!("No accounts provided for validation.");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    // Further validation logic can be added here
-
-    msg!("Transaction validated successfully.");
-    Ok(())
-}
\ No newline at end of file
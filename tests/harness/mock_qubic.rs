@@ -0,0 +1,375 @@
+// tests/harness/mock_qubic.rs
+// In-process stand-in for a Qubic node running the HM25 bridge contract, serving the same
+// endpoints as `rpc.qubic.org`: tick info, per-tick transaction listings, archived
+// transactions, contract function queries and signed binary broadcasts. Ticks advance on a
+// timer so finality depth can be reached.
+use std::collections::{HashMap, HashSet};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::qubic::hm25::{
+    GetBridgeInfoOutput, GetLockInfoInput, GetLockInfoOutput, LockAssetsInput, UnlockAssetsInput,
+    FUNCTION_GET_BRIDGE_INFO, FUNCTION_GET_LOCK_INFO, PROCEDURE_LOCK_ASSETS, PROCEDURE_UNLOCK_ASSETS,
+};
+use shared::qubic::{contract_id, identity, QpiStruct, QubicTransaction, HM25_CONTRACT_INDEX, NULL_ID};
+
+const EPOCH: u32 = 1;
+
+/// A transaction as the node archives it, input still in binary.
+#[derive(Debug, Clone)]
+struct Recorded {
+    id: String,
+    source: [u8; 32],
+    destination: [u8; 32],
+    amount: i64,
+    tick: u64,
+    input_type: u16,
+    input: Vec<u8>,
+    signature: [u8; 64],
+}
+
+impl Recorded {
+    fn new(transaction: &QubicTransaction) -> Recorded {
+        Recorded {
+            id: transaction.id(),
+            source: transaction.source,
+            destination: transaction.destination,
+            amount: transaction.amount,
+            tick: transaction.tick as u64,
+            input_type: transaction.input_type,
+            input: transaction.input.clone(),
+            signature: transaction.signature,
+        }
+    }
+
+    /// The archive's JSON for this transaction, as listed per tick and by id.
+    fn to_json(&self) -> Value {
+        json!({
+            "transaction": {
+                "sourceId": identity(&self.source),
+                "destId": identity(&self.destination),
+                "amount": self.amount.to_string(),
+                "tickNumber": self.tick,
+                "inputType": self.input_type,
+                "inputSize": self.input.len(),
+                "inputHex": hex::encode(&self.input),
+                "signatureHex": hex::encode(self.signature),
+                "txId": self.id,
+            },
+            "timestamp": "0",
+            "moneyFlew": self.amount > 0,
+        })
+    }
+}
+
+/// A lock as HM25 stores it. The mock charges no fee, so the whole amount is released.
+#[derive(Debug, Clone)]
+struct Lock {
+    locker: [u8; 32],
+    amount: u64,
+    tick: u64,
+    solana_destination: [u8; 32],
+}
+
+/// HM25 `Bridge` contract state: lock ids start at 1 and `UnlockAssets` counts the signers
+/// that are registered validators against `requiredSignatures`, like the contract does. HM25
+/// bridges QU, so balances are per identity.
+#[derive(Debug)]
+struct Contract {
+    tick: u64,
+    required_signatures: usize,
+    /// Qubic identities of the registered validators.
+    validators: HashSet<[u8; 32]>,
+    /// Lock `id` is `locks[id - 1]`.
+    locks: Vec<Lock>,
+    /// Identity -> QU balance.
+    balances: HashMap<[u8; 32], u64>,
+    total_locked: u64,
+    total_unlocked: u64,
+    unlocked: HashSet<u64>,
+    transactions: Vec<Recorded>,
+}
+
+impl Contract {
+    fn lock_assets(&mut self, invocator: [u8; 32], tick: u64, input: &LockAssetsInput) -> Result<u64, String> {
+        let balance = self.balances.entry(invocator).or_default();
+        if input.amount == 0 || *balance < input.amount {
+            return Err(format!("{} holds {} QU, cannot lock {}", identity(&invocator), balance, input.amount));
+        }
+        *balance -= input.amount;
+        self.total_locked += input.amount;
+
+        self.locks.push(Lock {
+            locker: invocator,
+            amount: input.amount,
+            tick,
+            solana_destination: input.solana_destination,
+        });
+        Ok(self.locks.len() as u64)
+    }
+
+    fn unlock_assets(&mut self, input: &UnlockAssetsInput) -> Result<(), String> {
+        let signatures = input.signers().filter(|signer| self.validators.contains(*signer)).count();
+        if signatures < self.required_signatures {
+            return Err(format!("{} signatures, {} required", signatures, self.required_signatures));
+        }
+        if !self.unlocked.insert(input.lock_id) {
+            return Err(format!("lock id {} already unlocked", input.lock_id));
+        }
+        *self.balances.entry(input.recipient).or_default() += input.amount;
+        self.total_unlocked += input.amount;
+        Ok(())
+    }
+
+    /// Run a signed transaction. It executes in its target tick, so one aimed at a tick that
+    /// has already passed is dropped, as a real node would. A call the contract turns down is
+    /// still included, it just changes nothing, as HM25 clears `success` instead of failing.
+    fn execute_transaction(&mut self, transaction: &QubicTransaction) -> Result<(), String> {
+        if !transaction.verify() {
+            return Err("bad signature".to_string());
+        }
+        if transaction.tick as u64 <= self.tick {
+            return Ok(());
+        }
+        if transaction.destination != contract_id(HM25_CONTRACT_INDEX) || transaction.input_type != PROCEDURE_UNLOCK_ASSETS {
+            return Err(format!("unexpected call of procedure {}", transaction.input_type));
+        }
+
+        let input = UnlockAssetsInput::decode(&transaction.input).map_err(|e| e.to_string())?;
+        let _ = self.unlock_assets(&input);
+        self.transactions.push(Recorded::new(transaction));
+        Ok(())
+    }
+
+    fn lock_info(&self, lock_id: u64) -> GetLockInfoOutput {
+        let lock = lock_id.checked_sub(1).and_then(|index| self.locks.get(index as usize));
+        GetLockInfoOutput {
+            found: lock.is_some(),
+            locker: lock.map_or(NULL_ID, |lock| lock.locker),
+            amount: lock.map_or(0, |lock| lock.amount),
+            actual_amount: lock.map_or(0, |lock| lock.amount),
+            fee: 0,
+            timestamp: lock.map_or(0, |lock| lock.tick),
+            solana_destination: lock.map_or([0; 32], |lock| lock.solana_destination),
+            memo: [0; 64],
+            status: 0,
+        }
+    }
+
+    fn bridge_info(&self) -> GetBridgeInfoOutput {
+        GetBridgeInfoOutput {
+            admin: NULL_ID,
+            min_lock_amount: 1,
+            max_lock_amount: u64::MAX,
+            bridge_fee: 0,
+            is_active: true,
+            validator_count: self.validators.len() as u64,
+            required_signatures: self.required_signatures as u64,
+            total_locked: self.total_locked,
+            total_unlocked: self.total_unlocked,
+            total_transfers: (self.locks.len() + self.unlocked.len()) as u64,
+            total_validator_actions: 0,
+            next_lock_id: self.locks.len() as u64 + 1,
+        }
+    }
+}
+
+pub struct MockQubic {
+    pub url: String,
+    contract: Arc<Mutex<Contract>>,
+}
+
+impl MockQubic {
+    /// Serve on an ephemeral port, advancing one tick every `tick_interval`.
+    pub async fn start(required_signatures: usize, tick_interval: Duration) -> MockQubic {
+        let contract = Arc::new(Mutex::new(Contract {
+            tick: 1,
+            required_signatures,
+            validators: HashSet::new(),
+            locks: Vec::new(),
+            balances: HashMap::new(),
+            total_locked: 0,
+            total_unlocked: 0,
+            unlocked: HashSet::new(),
+            transactions: Vec::new(),
+        }));
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock Qubic node");
+        listener.set_nonblocking(true).expect("nonblocking listener");
+        let url = format!("http://{}", listener.local_addr().expect("local address"));
+
+        let router = Router::new()
+            .route("/v1/tick-info", get(tick_info))
+            .route("/v1/status", get(status))
+            .route("/v1/broadcast-transaction", post(broadcast_transaction))
+            .route("/v1/querySmartContract", post(query_smart_contract))
+            .route("/v2/ticks/:tick/transactions", get(tick_transactions))
+            .route("/v2/transactions/:id", get(archived_transaction))
+            .with_state(contract.clone());
+        let server = axum::Server::from_tcp(listener).expect("mock Qubic server");
+        tokio::spawn(server.serve(router.into_make_service()));
+
+        let ticker = contract.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick_interval);
+            loop {
+                interval.tick().await;
+                ticker.lock().unwrap().tick += 1;
+            }
+        });
+
+        MockQubic { url, contract }
+    }
+
+    /// Add a validator to HM25 by its Qubic identity, as the contract admin would.
+    pub fn register_validator(&self, identity: [u8; 32]) {
+        self.contract.lock().unwrap().validators.insert(identity);
+    }
+
+    pub fn fund(&self, identity: &[u8; 32], amount: u64) {
+        *self.contract.lock().unwrap().balances.entry(*identity).or_default() += amount;
+    }
+
+    pub fn balance(&self, identity: &[u8; 32]) -> u64 {
+        self.contract.lock().unwrap().balances.get(identity).copied().unwrap_or(0)
+    }
+
+    /// `LockAssets` sent by `identity`, as a wallet would broadcast it: scheduled for the next
+    /// tick, so a monitor that already read the current tick still sees it. Returns the lock id.
+    pub fn lock_assets(&self, identity: &[u8; 32], amount: u64, solana_destination: [u8; 32]) -> u64 {
+        let input = LockAssetsInput { solana_destination, amount, memo: [0; 64] };
+        let mut contract = self.contract.lock().unwrap();
+        let tick = contract.tick + 1;
+        let transaction = QubicTransaction::procedure_call(
+            *identity,
+            HM25_CONTRACT_INDEX,
+            amount as i64,
+            tick as u32,
+            PROCEDURE_LOCK_ASSETS,
+            input.encode(),
+        )
+        .expect("LockAssets transaction");
+        let lock_id = contract.lock_assets(*identity, tick, &input).expect("LockAssets rejected");
+        contract.transactions.push(Recorded::new(&transaction));
+        lock_id
+    }
+
+    pub fn is_unlocked(&self, lock_id: u64) -> bool {
+        self.contract.lock().unwrap().unlocked.contains(&lock_id)
+    }
+
+    pub fn total_locked(&self) -> u64 {
+        self.contract.lock().unwrap().total_locked
+    }
+}
+
+type SharedContract = Arc<Mutex<Contract>>;
+
+async fn tick_info(State(contract): State<SharedContract>) -> Json<Value> {
+    let tick = contract.lock().unwrap().tick;
+    Json(json!({ "tickInfo": { "tick": tick, "duration": 1, "epoch": EPOCH, "initialTick": 1 } }))
+}
+
+async fn status(State(contract): State<SharedContract>) -> Json<Value> {
+    let tick = contract.lock().unwrap().tick;
+    Json(json!({ "lastProcessedTick": { "tickNumber": tick, "epoch": EPOCH } }))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncodedTransaction {
+    encoded_transaction: String,
+}
+
+/// Only malformed or badly signed transactions are refused; contract calls are accepted
+/// whatever the contract makes of them.
+async fn broadcast_transaction(
+    State(contract): State<SharedContract>,
+    Json(body): Json<EncodedTransaction>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(&body.encoded_transaction)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let transaction = QubicTransaction::from_bytes(&bytes).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    contract
+        .lock()
+        .unwrap()
+        .execute_transaction(&transaction)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    Ok(Json(json!({
+        "peersBroadcasted": 1,
+        "encodedTransaction": body.encoded_transaction,
+        "transactionId": transaction.id(),
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryRequest {
+    contract_index: u32,
+    input_type: u16,
+    request_data: String,
+}
+
+/// HM25's read-only functions, binary in and out like the node's.
+async fn query_smart_contract(
+    State(contract): State<SharedContract>,
+    Json(request): Json<QueryRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    if request.contract_index != HM25_CONTRACT_INDEX {
+        return Err((StatusCode::BAD_REQUEST, format!("no contract {}", request.contract_index)));
+    }
+    let input = base64::engine::general_purpose::STANDARD
+        .decode(&request.request_data)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let contract = contract.lock().unwrap();
+    let output = match request.input_type {
+        FUNCTION_GET_BRIDGE_INFO => contract.bridge_info().encode(),
+        FUNCTION_GET_LOCK_INFO => {
+            let input = GetLockInfoInput::decode(&input).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+            contract.lock_info(input.lock_id).encode()
+        }
+        other => return Err((StatusCode::BAD_REQUEST, format!("no function {}", other))),
+    };
+    Ok(Json(json!({ "responseData": base64::engine::general_purpose::STANDARD.encode(output) })))
+}
+
+/// Ticks are only listed once processed; empty ones aren't archived.
+async fn tick_transactions(
+    State(contract): State<SharedContract>,
+    Path(tick): Path<u64>,
+) -> Result<Json<Value>, StatusCode> {
+    let contract = contract.lock().unwrap();
+    let listed: Vec<Value> = contract
+        .transactions
+        .iter()
+        .filter(|t| t.tick == tick && t.tick <= contract.tick)
+        .map(Recorded::to_json)
+        .collect();
+    if listed.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(json!({ "transactions": listed })))
+}
+
+/// Transactions are only archived once their tick is processed.
+async fn archived_transaction(
+    State(contract): State<SharedContract>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    let contract = contract.lock().unwrap();
+    contract
+        .transactions
+        .iter()
+        .find(|t| t.id == id && t.tick <= contract.tick)
+        .map(|t| Json(t.to_json()))
+        .ok_or(StatusCode::NOT_FOUND)
+}
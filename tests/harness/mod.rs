@@ -0,0 +1,177 @@
+// tests/harness/mod.rs
+// Local end-to-end bridge: solana-test-validator with both programs, a mock Qubic node,
+// validator daemons and the real relayer, all in this process. Program-test and other
+// bank-only runtimes are not enough here because the relayer talks JSON-RPC and WebSocket.
+#![allow(dead_code)]
+
+pub mod mock_qubic;
+pub mod solana;
+
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use relayer::anomaly::AnomalyConfig;
+use relayer::bridge_relayer::{BridgeConfig, BridgeRelayer};
+use relayer::finality::FinalityConfig;
+use relayer::qubic_tx::QubicTxConfig;
+use relayer::rpc_pool::{QubicEndpoints, SolanaEndpoints};
+use relayer::solana_rpc::SolanaRpcConfig;
+use relayer::validator_daemon::ValidatorDaemon;
+
+use self::mock_qubic::MockQubic;
+use self::solana::SolanaHarness;
+
+/// Validator approvals required on both chains.
+pub const THRESHOLD: u8 = 2;
+
+/// Long enough for a few relayer poll rounds plus retries.
+const E2E_TIMEOUT: Duration = Duration::from_secs(120);
+
+const QUBIC_TICK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The identity the relayer sends `UnlockAssets` from.
+const RELAYER_QUBIC_SEED: &str = "relayerrelayerrelayerrelayerrelayerrelayerrelayerrelaye";
+
+pub struct LocalBridge {
+    pub solana: SolanaHarness,
+    pub qubic: MockQubic,
+    pub validators: Vec<Keypair>,
+    pub relayer: Arc<BridgeRelayer>,
+    pub collector_url: String,
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl LocalBridge {
+    /// Bring up both chains, register `THRESHOLD` validators plus the relayer's submitting
+    /// key, then start the relayer and one daemon per validator.
+    pub async fn start() -> LocalBridge {
+        let solana = SolanaHarness::start(THRESHOLD).await;
+        let qubic = MockQubic::start(THRESHOLD as usize, QUBIC_TICK_INTERVAL).await;
+
+        let validators: Vec<Keypair> = (0..THRESHOLD).map(|_| Keypair::new()).collect();
+        let relayer_key = Keypair::new();
+        // The relayer refuses to start without a validator set, so register everyone first
+        for keypair in validators.iter().chain([&relayer_key]) {
+            let qubic_id = qubic_validator_id(keypair);
+            solana.register_validator(keypair, qubic_id).await;
+            qubic.register_validator(qubic_id);
+        }
+
+        let dir = std::env::temp_dir().join(format!("bridge-e2e-{}", relayer_key.pubkey()));
+        std::fs::create_dir_all(&dir).expect("harness directory");
+        let keypair_path = dir.join("relayer.json");
+        write_keypair_file(&relayer_key, &keypair_path).expect("write relayer keypair");
+        let seed_path = dir.join("relayer-qubic.seed");
+        std::fs::write(&seed_path, RELAYER_QUBIC_SEED).expect("write relayer Qubic seed");
+
+        let finality = FinalityConfig {
+            solana_slot_depth: Some(1),
+            qubic_tick_depth: 2,
+        };
+        let solana_urls = vec![solana.rpc_url.clone()];
+        let qubic_urls = vec![qubic.url.clone()];
+        let collector_listen_addr = free_local_addr();
+        let collector_url = format!("http://{}", collector_listen_addr);
+        let config = BridgeConfig {
+            solana_rpc_urls: solana_urls.clone(),
+            solana_quorum: None,
+            solana_ws_url: Some(solana.ws_url.clone()),
+            solana_backfill_interval_seconds: 5,
+            qubic_rpc_urls: qubic_urls.clone(),
+            qubic_quorum: None,
+            qubic_mint: solana.mint,
+            // Mock ticks are quick; aiming two ahead leaves ample time to broadcast
+            qubic_tx: QubicTxConfig {
+                seed_path: Some(seed_path.to_string_lossy().into_owned()),
+                tick_offset: 2,
+                max_broadcasts: 3,
+            },
+            bridge_program_id: bridge::ID,
+            keypair_path: keypair_path.to_string_lossy().into_owned(),
+            finality: finality.clone(),
+            // Test transfers are a large share of a near-empty vault
+            anomaly: AnomalyConfig { max_unlock_bps: 0, pause_on_anomaly: false, ..AnomalyConfig::default() },
+            solana_rpc: SolanaRpcConfig::default(),
+            max_retry_attempts: 20,
+            max_concurrent_submissions: 4,
+            retry_delay_seconds: 1,
+            collector_listen_addr,
+            admin_listen_addr: free_local_addr(),
+            checkpoint_path: dir.join("checkpoint.json").to_string_lossy().into_owned(),
+        };
+
+        let relayer = Arc::new(BridgeRelayer::new(config).await.expect("relayer"));
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let mut tasks = vec![tokio::spawn({
+            let relayer = relayer.clone();
+            async move { relayer.start(shutdown_rx).await.expect("relayer stopped with an error") }
+        })];
+
+        for validator in &validators {
+            let checkpoint_path = dir.join(format!("validator-{}.json", validator.pubkey()));
+            let daemon = ValidatorDaemon::new(
+                validator.insecure_clone(),
+                collector_url.clone(),
+                SolanaEndpoints::new(&solana_urls, Duration::from_secs(30), None),
+                QubicEndpoints::new(&qubic_urls, None),
+                bridge::ID,
+                solana.mint,
+                finality.clone(),
+                checkpoint_path.to_string_lossy().into_owned(),
+            );
+            tasks.push(tokio::spawn(async move {
+                daemon.run().await.expect("validator daemon stopped with an error");
+            }));
+        }
+
+        LocalBridge {
+            solana,
+            qubic,
+            validators,
+            relayer,
+            collector_url,
+            shutdown,
+            tasks,
+        }
+    }
+}
+
+impl Drop for LocalBridge {
+    fn drop(&mut self) {
+        let _ = self.shutdown.send(true);
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Poll `check` until it holds, failing the test after `E2E_TIMEOUT`.
+pub async fn wait_until<F, Fut>(what: &str, mut check: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    let deadline = Instant::now() + E2E_TIMEOUT;
+    while !check().await {
+        assert!(Instant::now() < deadline, "timed out waiting for {}", what);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+fn free_local_addr() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("free local port");
+    listener.local_addr().expect("local address").to_string()
+}
+
+/// The Qubic identity `validator` registers, kept apart from its Solana key so an unlock that
+/// names signers by the wrong key is refused.
+fn qubic_validator_id(validator: &Keypair) -> [u8; 32] {
+    shared::qubic::k12::k12_digest(&validator.pubkey().to_bytes())
+}
@@ -0,0 +1,265 @@
+// tests/harness/solana.rs
+// A local validator with the bridge and validator programs loaded, initialized and
+// holding one test mint with its bridge vault.
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::{system_instruction, system_program};
+use solana_test_validator::{TestValidator, TestValidatorGenesis};
+
+use validator::state::AttestationDomain;
+
+const MINT_DECIMALS: u8 = 9;
+const ACCOUNT_LAMPORTS: u64 = 10_000_000_000;
+
+/// Deployment identity the local bridge is initialized with.
+pub const QUBIC_NETWORK_ID: u64 = 1;
+pub const BRIDGE_ID: u64 = 1;
+
+pub struct SolanaHarness {
+    _validator: TestValidator,
+    pub rpc_url: String,
+    pub ws_url: String,
+    pub client: RpcClient,
+    /// Bridge and validator-config admin, mint authority and fee payer.
+    pub admin: Keypair,
+    pub mint: Pubkey,
+    /// The bridge state's token account for `mint`.
+    pub vault: Pubkey,
+}
+
+impl SolanaHarness {
+    /// Start a validator and initialize both programs with `threshold` approvals required.
+    /// Programs load from `anchor build` output unless `SBF_OUT_DIR` points elsewhere.
+    pub async fn start(threshold: u8) -> SolanaHarness {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var("SBF_OUT_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy"));
+        }
+
+        let (test_validator, admin) = TestValidatorGenesis::default()
+            .add_program("bridge", bridge::ID)
+            .add_program("validator", validator::ID)
+            .start_async()
+            .await;
+        let rpc_url = test_validator.rpc_url();
+        let ws_url = test_validator.rpc_pubsub_url();
+        let client = RpcClient::new_with_commitment(rpc_url.clone(), CommitmentConfig::confirmed());
+
+        let mint = Keypair::new();
+        let vault = bridge::client::vault_address(&mint.pubkey());
+        let harness = SolanaHarness {
+            _validator: test_validator,
+            rpc_url,
+            ws_url,
+            client,
+            admin,
+            mint: mint.pubkey(),
+            vault,
+        };
+
+        harness.initialize(threshold).await;
+        harness.create_mint(&mint).await;
+        harness
+    }
+
+    async fn initialize(&self, threshold: u8) {
+        let admin = self.admin.pubkey();
+        let genesis_hash = self.client.get_genesis_hash().await.expect("genesis hash").to_bytes();
+        let initialize_bridge = Instruction {
+            program_id: bridge::ID,
+            accounts: bridge::accounts::InitializeBridge {
+                bridge_state: bridge::client::bridge_state_address(),
+                admin,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: bridge::instruction::InitializeBridge {
+                admin,
+                validator_threshold: threshold,
+                genesis_hash,
+                qubic_chain_id: QUBIC_NETWORK_ID,
+                bridge_id: BRIDGE_ID,
+            }
+            .data(),
+        };
+        let initialize_config = Instruction {
+            program_id: validator::ID,
+            accounts: validator::accounts::InitializeConfig {
+                admin,
+                validator_config: validator::client::validator_config_address(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: validator::instruction::InitializeConfig {
+                threshold,
+                domain: AttestationDomain {
+                    genesis_hash,
+                    bridge_program: bridge::ID,
+                    qubic_network_id: QUBIC_NETWORK_ID,
+                    bridge_id: BRIDGE_ID,
+                },
+            }
+            .data(),
+        };
+        self.send(vec![initialize_bridge, initialize_config], &[]).await;
+    }
+
+    async fn create_mint(&self, mint: &Keypair) {
+        let rent = self
+            .client
+            .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+            .await
+            .expect("rent for mint");
+        let instructions = vec![
+            system_instruction::create_account(
+                &self.admin.pubkey(),
+                &mint.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &self.admin.pubkey(),
+                None,
+                MINT_DECIMALS,
+            )
+            .expect("initialize mint"),
+            create_token_account(&self.admin.pubkey(), &bridge::client::bridge_state_address(), &mint.pubkey()),
+        ];
+        self.send(instructions, &[mint]).await;
+    }
+
+    /// Register `keypair` as an active validator known on Qubic as `qubic_validator_id`, and
+    /// fund it for fees.
+    pub async fn register_validator(&self, keypair: &Keypair, qubic_validator_id: [u8; 32]) {
+        let register = Instruction {
+            program_id: validator::ID,
+            accounts: validator::accounts::RegisterValidator {
+                admin: self.admin.pubkey(),
+                validator_config: validator::client::validator_config_address(),
+                validator_info: validator::client::validator_info_address(&keypair.pubkey()),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: validator::instruction::RegisterValidator {
+                validator_pubkey: keypair.pubkey(),
+                qubic_validator_id,
+            }
+            .data(),
+        };
+        let fund = system_instruction::transfer(&self.admin.pubkey(), &keypair.pubkey(), ACCOUNT_LAMPORTS);
+        self.send(vec![fund, register], &[]).await;
+    }
+
+    /// A funded user holding `amount` of the test mint.
+    pub async fn fund_user(&self, amount: u64) -> Keypair {
+        let user = Keypair::new();
+        let token_account = get_associated_token_address(&user.pubkey(), &self.mint);
+        let instructions = vec![
+            system_instruction::transfer(&self.admin.pubkey(), &user.pubkey(), ACCOUNT_LAMPORTS),
+            create_token_account(&self.admin.pubkey(), &user.pubkey(), &self.mint),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &self.mint,
+                &token_account,
+                &self.admin.pubkey(),
+                &[],
+                amount,
+            )
+            .expect("mint to user"),
+        ];
+        self.send(instructions, &[]).await;
+        user
+    }
+
+    /// Lock `amount` from `user`'s token account for `qubic_destination`. Returns the lock id.
+    pub async fn lock(&self, user: &Keypair, amount: u64, qubic_destination: [u8; 32]) -> u64 {
+        let lock_id = self.bridge_state().await.next_lock_id;
+        let lock = bridge::client::lock_assets(
+            &user.pubkey(),
+            lock_id,
+            &self.mint,
+            &get_associated_token_address(&user.pubkey(), &self.mint),
+            &self.vault,
+            amount,
+            qubic_destination,
+            [0; 64],
+        );
+        self.send_as(vec![lock], user).await;
+        lock_id
+    }
+
+    pub async fn bridge_state(&self) -> bridge::state::BridgeState {
+        let data = self
+            .client
+            .get_account_data(&bridge::client::bridge_state_address())
+            .await
+            .expect("bridge state account");
+        bridge::state::BridgeState::try_deserialize(&mut &data[..]).expect("bridge state")
+    }
+
+    /// The deployment attestations for this bridge are bound to.
+    pub async fn domain(&self) -> AttestationDomain {
+        self.bridge_state().await.attestation_domain()
+    }
+
+    pub async fn lock_account(&self, lock_id: u64) -> bridge::state::BridgeTransaction {
+        let data = self
+            .client
+            .get_account_data(&bridge::client::bridge_transaction_address(lock_id))
+            .await
+            .expect("lock account");
+        bridge::state::BridgeTransaction::try_deserialize(&mut &data[..]).expect("lock")
+    }
+
+    pub async fn token_balance(&self, owner: &Pubkey) -> u64 {
+        let balance = self
+            .client
+            .get_token_account_balance(&get_associated_token_address(owner, &self.mint))
+            .await
+            .expect("token account balance");
+        balance.amount.parse().expect("token amount")
+    }
+
+    /// Send with the admin paying, plus any extra signers.
+    async fn send(&self, instructions: Vec<Instruction>, signers: &[&Keypair]) {
+        let mut all: Vec<&Keypair> = vec![&self.admin];
+        all.extend_from_slice(signers);
+        self.send_signed(instructions, &self.admin.pubkey(), &all).await;
+    }
+
+    async fn send_as(&self, instructions: Vec<Instruction>, signer: &Keypair) {
+        self.send_signed(instructions, &signer.pubkey(), &[signer]).await;
+    }
+
+    async fn send_signed(&self, instructions: Vec<Instruction>, payer: &Pubkey, signers: &[&Keypair]) {
+        let blockhash = self.client.get_latest_blockhash().await.expect("blockhash");
+        let transaction = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &instructions,
+            Some(payer),
+            signers,
+            blockhash,
+        );
+        self.client
+            .send_and_confirm_transaction(&transaction)
+            .await
+            .expect("transaction failed");
+    }
+}
+
+fn create_token_account(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        owner,
+        mint,
+        &spl_token::ID,
+    )
+}
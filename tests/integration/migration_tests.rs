@@ -0,0 +1,426 @@
+// Accounts written in every earlier layout are injected under solana-program-test and
+// upgraded in place by the `migrate_*` instructions.
+use anchor_lang::{AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+
+use bridge::state::{
+    BridgeState, BridgeStateV0, BridgeStateV1, BridgeStateV2, BridgeTransaction, BridgeTransactionV0, MintLedger,
+    MintLedgerV0, PAUSE_REASON_MANUAL, PAUSE_REASON_NONE,
+};
+use validator::state::{AttestationDomain, ValidatorInfo, ValidatorInfoV0, ValidatorInfoV1, ValidatorInfoV2};
+
+const GENESIS_HASH: [u8; 32] = [9; 32];
+
+struct Chain {
+    context: ProgramTestContext,
+    admin: Keypair,
+    nonce: u32,
+}
+
+impl Chain {
+    /// Programs load from `anchor build` output unless `SBF_OUT_DIR` points elsewhere.
+    async fn start(admin: Keypair, accounts: Vec<(Pubkey, Account)>) -> Chain {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var("SBF_OUT_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy"));
+        }
+
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        program_test.add_program("bridge", bridge::ID, None);
+        program_test.add_program("validator", validator::ID, None);
+        program_test.add_account(admin.pubkey(), Account::new(10_000_000_000, 0, &system_program::ID));
+        for (address, account) in accounts {
+            program_test.add_account(address, account);
+        }
+        Chain { context: program_test.start_with_context().await, admin, nonce: 0 }
+    }
+
+    /// Whether the transaction landed. `signer` pays.
+    async fn send(&mut self, instruction: Instruction, signer: &Keypair) -> bool {
+        // Distinct compute limits keep retried instructions from being deduplicated
+        self.nonce += 1;
+        let instructions = [ComputeBudgetInstruction::set_compute_unit_limit(400_000 + self.nonce), instruction];
+        let blockhash = self.context.banks_client.get_latest_blockhash().await.expect("blockhash");
+        let transaction =
+            Transaction::new_signed_with_payer(&instructions, Some(&signer.pubkey()), &[signer], blockhash);
+        self.context.banks_client.process_transaction(transaction).await.is_ok()
+    }
+
+    async fn send_as_admin(&mut self, instruction: Instruction) -> bool {
+        let admin = self.admin.insecure_clone();
+        self.send(instruction, &admin).await
+    }
+
+    async fn raw(&mut self, address: Pubkey) -> Account {
+        self.context.banks_client.get_account(address).await.expect("banks").expect("account")
+    }
+
+    async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.raw(address).await;
+        let rent = self.context.banks_client.get_rent().await.expect("rent");
+        assert!(rent.is_exempt(account.lamports, account.data.len()), "migrated account must stay rent exempt");
+        T::try_deserialize(&mut &account.data[..]).expect("account data")
+    }
+}
+
+/// An account as an older program version left it: discriminator, then the legacy struct
+/// zero-padded to the length that version allocated.
+fn legacy_account(discriminator: [u8; 8], value: &impl AnchorSerialize, len: usize, owner: Pubkey) -> Account {
+    let mut data = discriminator.to_vec();
+    value.serialize(&mut data).expect("serialize");
+    assert!(data.len() <= 8 + len, "legacy value overflows its layout");
+    data.resize(8 + len, 0);
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn bridge_state_v0(admin: Pubkey) -> BridgeStateV0 {
+    BridgeStateV0 {
+        admin,
+        is_paused: true,
+        bridge_id: 4,
+        min_lock_amount: 1_000,
+        max_lock_amount: 5_000_000,
+        bridge_fee: 25,
+        is_active: true,
+        validator_threshold: 3,
+        active_validators: 5,
+        qubic_chain_id: 2,
+        total_locked_tokens: 700,
+        total_unlocked_tokens: 300,
+        total_bridge_transactions: 12,
+        total_validator_actions: 40,
+        next_lock_id: 13,
+        last_qubic_block: 99,
+        emergency_pause_timestamp: 1_700_000_000,
+        bump: bridge_state_bump(),
+    }
+}
+
+fn bridge_state_bump() -> u8 {
+    Pubkey::find_program_address(&[b"bridge_state"], &bridge::ID).1
+}
+
+fn bridge_state_account(value: &impl AnchorSerialize, len: usize) -> (Pubkey, Account) {
+    (
+        bridge::client::bridge_state_address(),
+        legacy_account(BridgeState::DISCRIMINATOR, value, len, bridge::ID),
+    )
+}
+
+/// Every field carried over from `expected`, with the genesis hash the admin supplied.
+fn assert_bridge_state(state: &BridgeState, expected: &BridgeStateV0) {
+    assert_eq!(state.version, BridgeState::VERSION);
+    assert_eq!(state.admin, expected.admin);
+    assert_eq!(state.is_paused, expected.is_paused);
+    assert_eq!(state.bridge_id, expected.bridge_id);
+    assert_eq!(state.min_lock_amount, expected.min_lock_amount);
+    assert_eq!(state.max_lock_amount, expected.max_lock_amount);
+    assert_eq!(state.bridge_fee, expected.bridge_fee);
+    assert_eq!(state.is_active, expected.is_active);
+    assert_eq!(state.validator_threshold, expected.validator_threshold);
+    assert_eq!(state.genesis_hash, GENESIS_HASH);
+    assert_eq!(state.qubic_chain_id, expected.qubic_chain_id);
+    assert_eq!(state.total_locked_tokens, expected.total_locked_tokens);
+    assert_eq!(state.total_unlocked_tokens, expected.total_unlocked_tokens);
+    assert_eq!(state.total_bridge_transactions, expected.total_bridge_transactions);
+    assert_eq!(state.total_validator_actions, expected.total_validator_actions);
+    assert_eq!(state.next_lock_id, expected.next_lock_id);
+    assert_eq!(state.last_qubic_block, expected.last_qubic_block);
+    assert_eq!(state.emergency_pause_timestamp, expected.emergency_pause_timestamp);
+    assert_eq!(state.bump, expected.bump);
+    assert_eq!(state.guardian, Pubkey::default());
+    assert_eq!(state.pause_flags, 0);
+    let reason = if expected.is_paused { PAUSE_REASON_MANUAL } else { PAUSE_REASON_NONE };
+    assert_eq!(state.pause_reason, reason);
+    assert_eq!((state.max_unlock_bps, state.max_unlocks_per_window, state.unlock_window_slots), (0, 0, 0));
+    assert_eq!((state.window_start_slot, state.window_unlocks), (0, 0));
+    assert_eq!(state.reserved, [0; BridgeState::RESERVED]);
+}
+
+async fn migrate_bridge_state(legacy: (Pubkey, Account), admin: Keypair, expected: &BridgeStateV0) {
+    let mut chain = Chain::start(admin, vec![legacy]).await;
+    let admin = chain.admin.pubkey();
+
+    assert!(chain.send_as_admin(bridge::client::migrate_bridge_state(&admin, GENESIS_HASH)).await, "migrate");
+    let state: BridgeState = chain.account(bridge::client::bridge_state_address()).await;
+    assert_bridge_state(&state, expected);
+
+    // Running it again is a no-op
+    assert!(chain.send_as_admin(bridge::client::migrate_bridge_state(&admin, [1; 32])).await, "migrate again");
+    let again: BridgeState = chain.account(bridge::client::bridge_state_address()).await;
+    assert_eq!(again.genesis_hash, GENESIS_HASH);
+}
+
+#[tokio::test]
+async fn bridge_state_upgrades_from_layout_0() {
+    let admin = Keypair::new();
+    let legacy = bridge_state_v0(admin.pubkey());
+    let account = bridge_state_account(&legacy, BridgeStateV0::LEN);
+    migrate_bridge_state(account, admin, &legacy).await;
+}
+
+#[tokio::test]
+async fn bridge_state_upgrades_from_layout_1() {
+    let admin = Keypair::new();
+    let legacy = bridge_state_v0(admin.pubkey());
+    let account = bridge_state_account(&legacy.clone().upgrade(), BridgeStateV1::LEN);
+    migrate_bridge_state(account, admin, &legacy).await;
+}
+
+#[tokio::test]
+async fn bridge_state_upgrades_from_layout_2() {
+    let admin = Keypair::new();
+    let legacy = bridge_state_v0(admin.pubkey());
+    let v2 = legacy.clone().upgrade().upgrade(GENESIS_HASH).expect("genesis hash");
+    let account = bridge_state_account(&v2, BridgeStateV2::LEN);
+    migrate_bridge_state(account, admin, &legacy).await;
+}
+
+#[tokio::test]
+async fn bridge_state_migration_is_admin_only() {
+    let admin = Keypair::new();
+    let legacy = bridge_state_v0(admin.pubkey());
+    let mut chain = Chain::start(admin, vec![bridge_state_account(&legacy, BridgeStateV0::LEN)]).await;
+    let before = chain.raw(bridge::client::bridge_state_address()).await;
+
+    let intruder = chain.context.payer.insecure_clone();
+    let instruction = bridge::client::migrate_bridge_state(&intruder.pubkey(), GENESIS_HASH);
+    assert!(!chain.send(instruction, &intruder).await, "non-admin migrated the bridge state");
+
+    // Layouts before the deployment identity also need a real genesis hash
+    let admin = chain.admin.pubkey();
+    assert!(!chain.send_as_admin(bridge::client::migrate_bridge_state(&admin, [0; 32])).await, "zero genesis hash");
+
+    assert_eq!(chain.raw(bridge::client::bridge_state_address()).await.data, before.data);
+}
+
+#[tokio::test]
+async fn bridge_state_of_unknown_length_is_rejected() {
+    let admin = Keypair::new();
+    let legacy = bridge_state_v0(admin.pubkey());
+    let mut chain = Chain::start(admin, vec![bridge_state_account(&legacy, BridgeStateV0::LEN + 3)]).await;
+    let admin = chain.admin.pubkey();
+    assert!(!chain.send_as_admin(bridge::client::migrate_bridge_state(&admin, GENESIS_HASH)).await);
+}
+
+#[tokio::test]
+async fn bridge_transaction_upgrades_from_layout_0() {
+    let admin = Keypair::new();
+    let state = bridge_state_v0(admin.pubkey()).upgrade().upgrade(GENESIS_HASH).expect("genesis hash");
+    let lock_id = 7;
+    let legacy = BridgeTransactionV0 {
+        id: lock_id,
+        user: Pubkey::new_unique(),
+        token_mint: Pubkey::new_unique(),
+        amount: 1_000_000,
+        actual_amount: 997_500,
+        fee: 2_500,
+        qubic_destination: [3; 32],
+        memo: [4; 64],
+        status: 1,
+        confirmations: 2,
+        created_at: 1_700_000_000,
+        updated_at: 1_700_000_100,
+        completed_at: 0,
+        qubic_tx_hash: [5; 32],
+        validator_signatures: vec![[6; 64], [8; 64]],
+        is_processed: false,
+        bump: Pubkey::find_program_address(&[b"bridge_tx", lock_id.to_le_bytes().as_ref()], &bridge::ID).1,
+    };
+    let accounts = vec![
+        bridge_state_account(&state, BridgeStateV2::LEN),
+        (
+            bridge::client::bridge_transaction_address(lock_id),
+            legacy_account(BridgeTransaction::DISCRIMINATOR, &legacy, BridgeTransactionV0::LEN, bridge::ID),
+        ),
+    ];
+    let mut chain = Chain::start(admin, accounts).await;
+    let admin = chain.admin.pubkey();
+
+    // Lock records can only be migrated once the bridge state is
+    assert!(!chain.send_as_admin(bridge::client::migrate_bridge_transaction(&admin, lock_id)).await);
+    assert!(chain.send_as_admin(bridge::client::migrate_bridge_state(&admin, GENESIS_HASH)).await);
+
+    let intruder = chain.context.payer.insecure_clone();
+    let instruction = bridge::client::migrate_bridge_transaction(&intruder.pubkey(), lock_id);
+    assert!(!chain.send(instruction, &intruder).await, "non-admin migrated a lock");
+
+    assert!(chain.send_as_admin(bridge::client::migrate_bridge_transaction(&admin, lock_id)).await);
+    let lock: BridgeTransaction = chain.account(bridge::client::bridge_transaction_address(lock_id)).await;
+    assert_eq!(lock.version, BridgeTransaction::VERSION);
+    assert_eq!(lock.id, legacy.id);
+    assert_eq!(lock.user, legacy.user);
+    assert_eq!(lock.token_mint, legacy.token_mint);
+    assert_eq!(lock.amount, legacy.amount);
+    assert_eq!(lock.actual_amount, legacy.actual_amount);
+    assert_eq!(lock.fee, legacy.fee);
+    assert_eq!(lock.qubic_destination, legacy.qubic_destination);
+    assert_eq!(lock.memo, legacy.memo);
+    assert_eq!(lock.status, legacy.status);
+    assert_eq!(lock.confirmations, legacy.confirmations);
+    assert_eq!(lock.created_at, legacy.created_at);
+    assert_eq!(lock.updated_at, legacy.updated_at);
+    assert_eq!(lock.completed_at, legacy.completed_at);
+    assert_eq!(lock.qubic_tx_hash, legacy.qubic_tx_hash);
+    assert_eq!(lock.validator_signatures, legacy.validator_signatures);
+    assert_eq!(lock.is_processed, legacy.is_processed);
+    assert_eq!(lock.bump, legacy.bump);
+    assert_eq!(lock.reserved, [0; BridgeTransaction::RESERVED]);
+}
+
+#[tokio::test]
+async fn mint_ledger_upgrades_from_layout_0() {
+    let admin = Keypair::new();
+    let state = bridge_state_v0(admin.pubkey()).upgrade().upgrade(GENESIS_HASH).expect("genesis hash").upgrade();
+    let mint = Pubkey::new_unique();
+    let legacy = MintLedgerV0 {
+        mint,
+        total_locked: 9_000,
+        total_unlocked: 4_000,
+        total_fees: 90,
+        last_audit_at: 1_700_000_200,
+        bump: Pubkey::find_program_address(&[b"mint_ledger", mint.as_ref()], &bridge::ID).1,
+    };
+    let accounts = vec![
+        bridge_state_account(&state, BridgeState::INIT_SPACE),
+        (
+            bridge::client::mint_ledger_address(&mint),
+            legacy_account(MintLedger::DISCRIMINATOR, &legacy, MintLedgerV0::LEN, bridge::ID),
+        ),
+    ];
+    let mut chain = Chain::start(admin, accounts).await;
+    let admin = chain.admin.pubkey();
+
+    let intruder = chain.context.payer.insecure_clone();
+    let instruction = bridge::client::migrate_mint_ledger(&intruder.pubkey(), mint);
+    assert!(!chain.send(instruction, &intruder).await, "non-admin migrated a mint ledger");
+
+    assert!(chain.send_as_admin(bridge::client::migrate_mint_ledger(&admin, mint)).await, "migrate");
+    let ledger: MintLedger = chain.account(bridge::client::mint_ledger_address(&mint)).await;
+    assert_eq!(ledger.version, MintLedger::VERSION);
+    assert_eq!(ledger.mint, legacy.mint);
+    assert_eq!(ledger.total_locked, legacy.total_locked);
+    assert_eq!(ledger.total_unlocked, legacy.total_unlocked);
+    assert_eq!(ledger.total_fees, legacy.total_fees);
+    assert_eq!(ledger.last_audit_at, legacy.last_audit_at);
+    assert!(!ledger.is_paused);
+    assert_eq!(ledger.bump, legacy.bump);
+    assert_eq!(ledger.reserved, [0; MintLedger::RESERVED]);
+}
+
+fn validator_info_v1(validator: Pubkey) -> ValidatorInfoV1 {
+    ValidatorInfoV1 {
+        validator_pubkey: validator,
+        qubic_validator_id: [0x51; 32],
+        is_active: true,
+        registered_at: 1_690_000_000,
+        stake: 0,
+        unbonding_amount: 0,
+        unbonding_available_at: 0,
+        slashed_amount: 0,
+        bump: Pubkey::find_program_address(&[b"validator", validator.as_ref()], &validator::ID).1,
+    }
+}
+
+fn validator_info_account(validator: Pubkey, value: &impl AnchorSerialize, len: usize) -> (Pubkey, Account) {
+    (
+        validator::client::validator_info_address(&validator),
+        legacy_account(ValidatorInfo::DISCRIMINATOR, value, len, validator::ID),
+    )
+}
+
+#[tokio::test]
+async fn validator_info_upgrades_from_every_layout() {
+    let admin = Keypair::new();
+    let [v0, v1, v2] = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let v0_legacy = ValidatorInfoV0 {
+        validator_pubkey: v0,
+        qubic_validator_id: [0x50; 32],
+        is_active: false,
+        registered_at: 1_680_000_000,
+        bump: Pubkey::find_program_address(&[b"validator", v0.as_ref()], &validator::ID).1,
+    };
+    let v1_legacy = ValidatorInfoV1 {
+        stake: 5_000,
+        unbonding_amount: 1_000,
+        unbonding_available_at: 1_700_000_000,
+        slashed_amount: 250,
+        ..validator_info_v1(v1)
+    };
+    let mut v2_legacy = validator_info_v1(v2).upgrade();
+    v2_legacy.stake = 8_000;
+    v2_legacy.attestations_signed = 40;
+    v2_legacy.missed_deadlines = 2;
+    v2_legacy.late_signatures = 3;
+    v2_legacy.last_seen_slot = 12_345;
+
+    let accounts = vec![
+        validator_info_account(v0, &v0_legacy, ValidatorInfoV0::LEN),
+        validator_info_account(v1, &v1_legacy, ValidatorInfoV1::LEN),
+        validator_info_account(v2, &v2_legacy, ValidatorInfoV2::LEN),
+    ];
+    let mut chain = Chain::start(admin, accounts).await;
+    let admin = chain.admin.pubkey();
+
+    let initialize = Instruction {
+        program_id: validator::ID,
+        accounts: validator::accounts::InitializeConfig {
+            admin,
+            validator_config: validator::client::validator_config_address(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: validator::instruction::InitializeConfig {
+            threshold: 2,
+            domain: AttestationDomain {
+                genesis_hash: GENESIS_HASH,
+                bridge_program: bridge::ID,
+                qubic_network_id: 1,
+                bridge_id: 1,
+            },
+        }
+        .data(),
+    };
+    assert!(chain.send_as_admin(initialize).await, "initialize config");
+
+    let intruder = chain.context.payer.insecure_clone();
+    let instruction = validator::client::migrate_validator_info(&intruder.pubkey(), v0);
+    assert!(!chain.send(instruction, &intruder).await, "non-admin migrated a validator");
+
+    let expected = [v0_legacy.upgrade().upgrade().upgrade(), v1_legacy.upgrade().upgrade(), v2_legacy.upgrade()];
+    for (validator, expected) in [v0, v1, v2].into_iter().zip(expected) {
+        assert!(chain.send_as_admin(validator::client::migrate_validator_info(&admin, validator)).await, "migrate");
+        let info: ValidatorInfo = chain.account(validator::client::validator_info_address(&validator)).await;
+        assert_eq!(info.version, ValidatorInfo::VERSION);
+        assert_eq!(info.validator_pubkey, validator);
+        assert_eq!(info.qubic_validator_id, expected.qubic_validator_id);
+        assert_eq!(info.is_active, expected.is_active);
+        assert_eq!(info.registered_at, expected.registered_at);
+        assert_eq!(info.stake, expected.stake);
+        assert_eq!(info.unbonding_amount, expected.unbonding_amount);
+        assert_eq!(info.unbonding_available_at, expected.unbonding_available_at);
+        assert_eq!(info.slashed_amount, expected.slashed_amount);
+        assert_eq!(info.attestations_signed, expected.attestations_signed);
+        assert_eq!(info.missed_deadlines, expected.missed_deadlines);
+        assert_eq!(info.late_signatures, expected.late_signatures);
+        assert_eq!(info.last_seen_slot, expected.last_seen_slot);
+        assert_eq!(info.bump, expected.bump);
+        assert_eq!(info.relays_landed, 0);
+        assert_eq!(info.reserved, [0; ValidatorInfo::RESERVED]);
+    }
+}
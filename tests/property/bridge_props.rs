@@ -0,0 +1,52 @@
+// Random operation sequences against the bridge and validator programs under
+// solana-program-test. Needs the program binaries from `anchor build`.
+mod model;
+mod vm;
+
+use proptest::prelude::*;
+
+use model::Op;
+
+fn op() -> impl Strategy<Value = Op> {
+    // Amounts straddle the default lock limits and the test balances
+    let amount = prop_oneof![0u64..2_000_000, 1_000_000u64..20_000_000_000, any::<u64>()];
+    prop_oneof![
+        6 => (any::<u8>(), amount).prop_map(|(user, amount)| Op::Lock { user, amount }),
+        6 => any::<u8>().prop_map(|lock| Op::Unlock { lock }),
+        1 => Just(Op::Pause),
+        2 => Just(Op::Unpause),
+        // Mostly valid PAUSE_* combinations, sometimes stray bits
+        2 => prop_oneof![1u8..16, any::<u8>()].prop_map(|flags| Op::PauseOperations { flags }),
+        3 => prop_oneof![1u8..16, any::<u8>()].prop_map(|flags| Op::UnpauseOperations { flags }),
+        2 => any::<bool>().prop_map(|is_paused| Op::SetMintPaused { is_paused }),
+        2 => any::<u8>().prop_map(|lock| Op::Refund { lock }),
+        // Limits tight enough to trip within a sequence, sometimes out of range
+        1 => (prop_oneof![0u16..3_000, any::<u16>()], 0u16..4).prop_map(|(max_unlock_bps, max_unlocks_per_window)| {
+            Op::ConfigureCircuitBreaker { max_unlock_bps, max_unlocks_per_window }
+        }),
+        1 => (any::<u8>(), prop::bool::weighted(0.7)).prop_map(|(lock, phantom)| Op::ReportLock { lock, phantom }),
+        2 => (0u64..5_000_000, 0u64..20_000_000_000, 0u64..1_200, 0u8..12, prop::bool::weighted(0.8)).prop_map(
+            |(min_lock_amount, max_lock_amount, bridge_fee, required_signatures, is_active)| Op::UpdateConfig {
+                min_lock_amount,
+                max_lock_amount,
+                bridge_fee,
+                required_signatures,
+                is_active,
+            }
+        ),
+        1 => Just(Op::AddValidator),
+        2 => (any::<u8>(), any::<bool>()).prop_map(|(validator, is_active)| Op::SetValidatorActive { validator, is_active }),
+    ]
+}
+
+proptest! {
+    // Every case boots a fresh bank, so keep the count modest
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn bridge_accounting_holds(required_signatures in 1u8..4, ops in prop::collection::vec(op(), 1..40)) {
+        tokio::runtime::Runtime::new()
+            .expect("tokio runtime")
+            .block_on(vm::check_sequence(required_signatures, &ops));
+    }
+}
@@ -0,0 +1,201 @@
+// Operations the property and fuzz suites drive against the bridge program, and a model of
+// which of them must succeed. The program is checked against the model after every step.
+use std::collections::{HashMap, HashSet};
+
+use arbitrary::Arbitrary;
+use bridge::state::{
+    PAUSE_FLAGS_ALL, PAUSE_LOCK, PAUSE_MAINTENANCE, PAUSE_REASON_LARGE_UNLOCK, PAUSE_REASON_NONE,
+    PAUSE_REASON_UNLOCK_RATE, PAUSE_UNLOCK,
+};
+
+/// Users holding test tokens, and validators registered before the first operation.
+pub const USERS: usize = 3;
+pub const INITIAL_VALIDATORS: usize = 3;
+pub const MAX_VALIDATORS: usize = 8;
+
+/// Approvals the validator program requires; fixed at initialization.
+pub const APPROVAL_THRESHOLD: usize = 2;
+
+/// Each user's starting token balance.
+pub const USER_BALANCE: u64 = 10_000_000_000;
+
+/// Circuit breaker window; longer than any sequence runs, so every unlock lands in one.
+pub const UNLOCK_WINDOW_SLOTS: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Arbitrary)]
+pub enum Op {
+    Lock { user: u8, amount: u64 },
+    /// Release the Qubic-side twin of one of the locks made so far, picked by index: a Qubic
+    /// lock with the same id, user and amount, coming back to Solana.
+    Unlock { lock: u8 },
+    Pause,
+    Unpause,
+    /// Pause the `PAUSE_*` bits in `flags` as the guardian.
+    PauseOperations { flags: u8 },
+    /// Try to resume `flags` as the guardian (always refused), then as the admin.
+    UnpauseOperations { flags: u8 },
+    /// Pause the test mint as the guardian, or resume it as the admin.
+    SetMintPaused { is_paused: bool },
+    /// Admin refund of one of the locks made so far, picked by index.
+    Refund { lock: u8 },
+    /// Set the unlock size and rate tripwires (zero turns one off).
+    ConfigureCircuitBreaker { max_unlock_bps: u16, max_unlocks_per_window: u16 },
+    /// Have the active validators approve a lock id that was never made (or, without
+    /// `phantom`, a fresh attestation for an existing lock) and report it.
+    ReportLock { lock: u8, phantom: bool },
+    UpdateConfig {
+        min_lock_amount: u64,
+        max_lock_amount: u64,
+        bridge_fee: u64,
+        required_signatures: u8,
+        is_active: bool,
+    },
+    AddValidator,
+    SetValidatorActive { validator: u8, is_active: bool },
+}
+
+#[derive(Debug, Clone)]
+pub struct ModelLock {
+    pub id: u64,
+    pub user: usize,
+    pub actual_amount: u64,
+    pub fee: u64,
+    /// Refunded. Inbound unlocks are tracked in `Model::unlocked` instead.
+    pub processed: bool,
+}
+
+/// What the program's state should be, as far as the invariants care.
+#[derive(Debug, Clone)]
+pub struct Model {
+    pub is_active: bool,
+    pub is_paused: bool,
+    pub pause_flags: u8,
+    pub mint_paused: bool,
+    pub pause_reason: u8,
+    pub max_unlock_bps: u16,
+    pub max_unlocks_per_window: u16,
+    /// Unlocks counted towards the rate tripwire since it was configured.
+    pub window_unlocks: u16,
+    pub min_lock_amount: u64,
+    pub max_lock_amount: u64,
+    pub bridge_fee: u64,
+    pub required_signatures: u8,
+    pub next_lock_id: u64,
+    pub locks: Vec<ModelLock>,
+    /// Qubic lock ids already released on Solana.
+    pub unlocked: HashSet<u64>,
+    pub balances: [u64; USERS],
+    pub validators_active: Vec<bool>,
+    /// Validators that already voted for each lock's attestation.
+    pub votes: HashMap<u64, HashSet<usize>>,
+    pub total_locked: u64,
+    pub total_unlocked: u64,
+    pub total_fees: u64,
+}
+
+impl Model {
+    /// State right after `initialize_bridge`, with the defaults it sets.
+    pub fn new(required_signatures: u8) -> Self {
+        Model {
+            is_active: true,
+            is_paused: false,
+            pause_flags: 0,
+            mint_paused: false,
+            pause_reason: PAUSE_REASON_NONE,
+            max_unlock_bps: 0,
+            max_unlocks_per_window: 0,
+            window_unlocks: 0,
+            min_lock_amount: 1_000_000,
+            max_lock_amount: 1_000_000_000_000,
+            bridge_fee: 100,
+            required_signatures,
+            next_lock_id: 1,
+            locks: Vec::new(),
+            unlocked: HashSet::new(),
+            balances: [USER_BALANCE; USERS],
+            validators_active: vec![true; INITIAL_VALIDATORS],
+            votes: HashMap::new(),
+            total_locked: 0,
+            total_unlocked: 0,
+            total_fees: 0,
+        }
+    }
+
+    pub fn open(&self) -> bool {
+        self.is_active && !self.is_paused
+    }
+
+    /// Whether neither maintenance mode, the `operation` bit nor the mint pause stops it.
+    pub fn allows(&self, operation: u8) -> bool {
+        self.pause_flags & (PAUSE_MAINTENANCE | operation) == 0 && !self.mint_paused
+    }
+
+    pub fn pause_flags_valid(flags: u8) -> bool {
+        flags != 0 && flags & !PAUSE_FLAGS_ALL == 0
+    }
+
+    /// `(actual_amount, fee)` as the program must compute it.
+    pub fn fee(&self, amount: u64) -> (u64, u64) {
+        let fee = (amount as u128 * self.bridge_fee as u128 / 10_000) as u64;
+        (amount - fee, fee)
+    }
+
+    pub fn lock_succeeds(&self, user: usize, amount: u64) -> bool {
+        self.open()
+            && self.allows(PAUSE_LOCK)
+            && amount >= self.min_lock_amount
+            && amount <= self.max_lock_amount
+            && self.balances[user] >= amount
+    }
+
+    /// Approvals the unlock recount finds once every active validator that hasn't voted yet
+    /// does: votes by validators deactivated since never count.
+    pub fn active_approvals_after_voting(&self) -> usize {
+        self.validators_active.iter().filter(|active| **active).count()
+    }
+
+    pub fn has_voted(&self, lock_id: u64, validator: usize) -> bool {
+        self.votes.get(&lock_id).is_some_and(|voters| voters.contains(&validator))
+    }
+
+    /// Inbound unlocks are tracked apart from the outbound locks, and the vault never pays out
+    /// more than was locked into it. Only the recounted votes approve one; the bridge's
+    /// `required_signatures` plays no part.
+    pub fn unlock_succeeds(&self, lock: &ModelLock) -> bool {
+        self.open()
+            && self.allows(PAUSE_UNLOCK)
+            && !self.unlocked.contains(&lock.id)
+            && self.total_unlocked + lock.actual_amount <= self.total_locked
+            && self.active_approvals_after_voting() >= APPROVAL_THRESHOLD
+    }
+
+    /// The tripwire a successful unlock of `amount` sets off instead of releasing funds. The
+    /// vault never falls short of the ledger here, so only the configurable ones can fire.
+    pub fn unlock_tripwire(&self, amount: u64) -> Option<u8> {
+        if self.max_unlock_bps > 0
+            && amount as u128 * 10_000 > self.expected_vault() as u128 * self.max_unlock_bps as u128
+        {
+            return Some(PAUSE_REASON_LARGE_UNLOCK);
+        }
+        if self.max_unlocks_per_window > 0 && self.window_unlocks >= self.max_unlocks_per_window {
+            return Some(PAUSE_REASON_UNLOCK_RATE);
+        }
+        None
+    }
+
+    /// Only the emergency pause stops refunds. Locks are never delivered to Qubic here, and
+    /// the driver waits out the refund timeout. Inbound unlocks may already have paid the
+    /// lock's tokens out of the vault.
+    pub fn refund_succeeds(&self, lock: &ModelLock) -> bool {
+        !self.is_paused && !lock.processed && self.total_unlocked + lock.actual_amount <= self.total_locked
+    }
+
+    pub fn config_valid(min: u64, max: u64, fee: u64, required_signatures: u8) -> bool {
+        min > 0 && max >= min && fee <= 1000 && required_signatures > 0
+    }
+
+    /// Tokens the vault must hold: everything locked and not unlocked, plus fees.
+    pub fn expected_vault(&self) -> u64 {
+        self.total_locked - self.total_unlocked + self.total_fees
+    }
+}
@@ -0,0 +1,111 @@
+// Operations against the HM25 simulator, and the accounting invariants it must keep after each.
+use std::collections::HashSet;
+
+use arbitrary::Arbitrary;
+use qubic_sim::io::*;
+use qubic_sim::{Hm25, Id};
+
+pub const USERS: usize = 3;
+pub const VALIDATORS: usize = 6;
+pub const USER_FUNDS: u64 = 10_000_000_000;
+
+const ADMIN: Id = [0xad; 32];
+
+#[derive(Debug, Clone, Arbitrary)]
+pub enum QubicOp {
+    Lock { user: u8, amount: u64 },
+    /// Release `amount` for Solana lock `lock_id`, signed by the validators set in `signers`.
+    Unlock { lock_id: u8, recipient: u8, amount: u64, signers: u8 },
+    UpdateConfig {
+        min_lock_amount: u64,
+        max_lock_amount: u64,
+        bridge_fee: u64,
+        required_signatures: u8,
+        is_active: bool,
+    },
+    AddValidator { validator: u8 },
+    RemoveValidator { validator: u8 },
+    EndTick,
+}
+
+fn user(index: u8) -> Id {
+    [index % USERS as u8 + 1; 32]
+}
+
+fn validator(index: u8) -> Id {
+    [0x80 + index % VALIDATORS as u8; 32]
+}
+
+/// Replay `ops` against a fresh contract with two validators, checking after every step that
+/// QU is conserved, the contract holds exactly what is locked and not unlocked plus fees,
+/// fees follow the basis-point rule, lock ids count up from 1 and no lock unlocks twice.
+pub fn check_qubic_sequence(ops: &[QubicOp]) {
+    let mut contract = Hm25::new(ADMIN, 2);
+    for index in 0..2 {
+        contract.add_validator(ADMIN, ValidatorInput { validator: validator(index) }).expect("add validator");
+    }
+    for index in 0..USERS as u8 {
+        contract.fund(user(index), USER_FUNDS);
+    }
+    let funded = USER_FUNDS * USERS as u64;
+    let mut unlocked = HashSet::new();
+    let mut released = 0u64;
+
+    for (step, op) in ops.iter().enumerate() {
+        let before = contract.get_bridge_info();
+        match *op {
+            QubicOp::Lock { user: index, amount } => {
+                let input = LockAssetsInput { solana_destination: [7; 32], amount, memo: [0; 64] };
+                if let Ok(output) = contract.lock_assets(user(index), input) {
+                    assert_eq!(output.lock_id, before.next_lock_id, "step {}: lock id", step);
+                    let fee = (amount as u128 * before.bridge_fee as u128 / 10_000) as u64;
+                    assert_eq!((output.actual_amount, output.fee), (amount - fee, fee), "step {}: fee math", step);
+
+                    let lock = contract.get_lock_info(GetLockInfoInput { lock_id: output.lock_id });
+                    assert!(lock.found, "step {}: lock recorded", step);
+                    assert_eq!(lock.lock.amount, lock.lock.actual_amount + lock.lock.fee, "step {}: fee split", step);
+                }
+            }
+            QubicOp::Unlock { lock_id, recipient, amount, signers } => {
+                let lock_id = lock_id as u64;
+                let validator_signatures =
+                    (0..8).filter(|bit| signers & (1 << bit) != 0).map(validator).collect();
+                let input = UnlockAssetsInput { lock_id, recipient: user(recipient), amount, validator_signatures };
+                if contract.unlock_assets(validator(0), input.clone()).is_ok() {
+                    assert!(unlocked.insert(lock_id), "step {}: lock {} unlocked twice", step, lock_id);
+                    released += amount;
+
+                    // The same release again must be refused and move nothing
+                    let balance = contract.contract_balance();
+                    assert!(contract.unlock_assets(validator(0), input).is_err(), "step {}: double unlock", step);
+                    assert_eq!(contract.contract_balance(), balance, "step {}: double unlock moved funds", step);
+                }
+            }
+            QubicOp::UpdateConfig { min_lock_amount, max_lock_amount, bridge_fee, required_signatures, is_active } => {
+                let input = UpdateConfigInput { min_lock_amount, max_lock_amount, bridge_fee, required_signatures, is_active };
+                let _ = contract.update_config(ADMIN, input);
+            }
+            QubicOp::AddValidator { validator: index } => {
+                let _ = contract.add_validator(ADMIN, ValidatorInput { validator: validator(index) });
+            }
+            QubicOp::RemoveValidator { validator: index } => {
+                let _ = contract.remove_validator(ADMIN, ValidatorInput { validator: validator(index) });
+            }
+            QubicOp::EndTick => contract.end_tick(),
+        }
+
+        let info = contract.get_bridge_info();
+        assert!(info.next_lock_id >= before.next_lock_id, "step {}: lock ids went backwards", step);
+        assert!(info.next_lock_id <= before.next_lock_id + 1, "step {}: lock ids skipped", step);
+        assert_eq!(info.total_unlocked, released, "step {}: unlocked total", step);
+        assert_eq!(
+            contract.contract_balance(),
+            info.total_locked - info.total_unlocked + contract.total_fees(),
+            "step {}: contract balance",
+            step
+        );
+
+        let held: u64 = (0..USERS as u8).map(|index| contract.balance(&user(index))).sum();
+        assert_eq!(held + contract.contract_balance(), funded, "step {}: QU conservation", step);
+    }
+}
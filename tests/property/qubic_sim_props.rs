@@ -0,0 +1,38 @@
+// Random operation sequences against the HM25 simulator.
+mod qubic_model;
+
+use proptest::prelude::*;
+
+use qubic_model::QubicOp;
+
+fn op() -> impl Strategy<Value = QubicOp> {
+    let amount = prop_oneof![0u64..2_000_000, 1_000_000u64..20_000_000_000, any::<u64>()];
+    let unlock_amount = prop_oneof![1u64..1_000_000_000, any::<u64>()];
+    prop_oneof![
+        6 => (any::<u8>(), amount).prop_map(|(user, amount)| QubicOp::Lock { user, amount }),
+        6 => (0u8..16, any::<u8>(), unlock_amount, any::<u8>()).prop_map(|(lock_id, recipient, amount, signers)| {
+            QubicOp::Unlock { lock_id, recipient, amount, signers }
+        }),
+        2 => (0u64..5_000_000, 0u64..20_000_000_000, 0u64..1_200, 0u8..5, prop::bool::weighted(0.8)).prop_map(
+            |(min_lock_amount, max_lock_amount, bridge_fee, required_signatures, is_active)| QubicOp::UpdateConfig {
+                min_lock_amount,
+                max_lock_amount,
+                bridge_fee,
+                required_signatures,
+                is_active,
+            }
+        ),
+        1 => any::<u8>().prop_map(|validator| QubicOp::AddValidator { validator }),
+        1 => any::<u8>().prop_map(|validator| QubicOp::RemoveValidator { validator }),
+        1 => Just(QubicOp::EndTick),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn hm25_accounting_holds(ops in prop::collection::vec(op(), 1..80)) {
+        qubic_model::check_qubic_sequence(&ops);
+    }
+}
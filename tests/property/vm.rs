@@ -0,0 +1,716 @@
+// The bridge and validator programs under solana-program-test, with one test mint, and a
+// driver that replays an operation sequence while checking the accounting invariants.
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::clock::Clock;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_sdk::{system_instruction, system_program};
+
+use bridge::state::{
+    LOCK_REFUND_TIMEOUT, PAUSE_REASON_MANUAL, PAUSE_REASON_NONE, PAUSE_REASON_PHANTOM_LOCK, PAUSE_REASON_UNLOCK_RATE,
+};
+use validator::state::{Attestation, AttestationDomain, DIRECTION_QUBIC_TO_SOLANA, DIRECTION_SOLANA_TO_QUBIC};
+
+use super::model::*;
+
+const ACCOUNT_LAMPORTS: u64 = 100_000_000_000;
+const QUBIC_NETWORK_ID: u64 = 1;
+const BRIDGE_ID: u64 = 1;
+
+pub struct BridgeVm {
+    context: ProgramTestContext,
+    domain: AttestationDomain,
+    mint: Pubkey,
+    vault: Pubkey,
+    /// A second token account of the mint owned by the bridge state, which is not its vault.
+    decoy_vault: Keypair,
+    users: Vec<Keypair>,
+    validators: Vec<Keypair>,
+    /// Registered validator that submits unlocks; never deactivated.
+    submitter: Keypair,
+    /// May pause but not unpause.
+    guardian: Keypair,
+    /// Makes otherwise identical transactions distinct, so a retried failure isn't deduplicated.
+    nonce: u64,
+}
+
+impl BridgeVm {
+    /// Programs load from `anchor build` output unless `SBF_OUT_DIR` points elsewhere.
+    pub async fn start(required_signatures: u8) -> BridgeVm {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var("SBF_OUT_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy"));
+        }
+
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        program_test.add_program("bridge", bridge::ID, None);
+        program_test.add_program("validator", validator::ID, None);
+        let context = program_test.start_with_context().await;
+
+        let mint = Keypair::new();
+        let domain = AttestationDomain {
+            genesis_hash: context.genesis_config().hash().to_bytes(),
+            bridge_program: bridge::ID,
+            qubic_network_id: QUBIC_NETWORK_ID,
+            bridge_id: BRIDGE_ID,
+        };
+        let mut vm = BridgeVm {
+            context,
+            domain,
+            mint: mint.pubkey(),
+            vault: bridge::client::vault_address(&mint.pubkey()),
+            decoy_vault: Keypair::new(),
+            users: (0..USERS).map(|_| Keypair::new()).collect(),
+            validators: Vec::new(),
+            submitter: Keypair::new(),
+            guardian: Keypair::new(),
+            nonce: 0,
+        };
+
+        vm.initialize(required_signatures, &mint).await;
+        let submitter = vm.submitter.insecure_clone();
+        assert!(vm.register_validator(&submitter).await, "register submitter");
+        for _ in 0..INITIAL_VALIDATORS {
+            assert!(vm.add_validator().await, "register validator");
+        }
+        vm
+    }
+
+    async fn initialize(&mut self, required_signatures: u8, mint: &Keypair) {
+        let admin = self.admin();
+        let rent = self.context.banks_client.get_rent().await.expect("rent");
+        let mut instructions = vec![
+            Instruction {
+                program_id: bridge::ID,
+                accounts: bridge::accounts::InitializeBridge {
+                    bridge_state: bridge::client::bridge_state_address(),
+                    admin,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: bridge::instruction::InitializeBridge {
+                    admin,
+                    validator_threshold: required_signatures,
+                    genesis_hash: self.domain.genesis_hash,
+                    qubic_chain_id: QUBIC_NETWORK_ID,
+                    bridge_id: BRIDGE_ID,
+                }
+                .data(),
+            },
+            Instruction {
+                program_id: validator::ID,
+                accounts: validator::accounts::InitializeConfig {
+                    admin,
+                    validator_config: validator::client::validator_config_address(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: validator::instruction::InitializeConfig {
+                    threshold: APPROVAL_THRESHOLD as u8,
+                    domain: self.domain,
+                }
+                .data(),
+            },
+            system_instruction::create_account(
+                &admin,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &admin, None, 9)
+                .expect("initialize mint"),
+            create_token_account(&admin, &bridge::client::bridge_state_address(), &mint.pubkey()),
+            bridge::client::set_guardian(&admin, self.guardian.pubkey()),
+            system_instruction::transfer(&admin, &self.guardian.pubkey(), ACCOUNT_LAMPORTS),
+        ];
+        for user in &self.users {
+            instructions.push(system_instruction::transfer(&admin, &user.pubkey(), ACCOUNT_LAMPORTS));
+            instructions.push(create_token_account(&admin, &user.pubkey(), &mint.pubkey()));
+            instructions.push(
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &get_associated_token_address(&user.pubkey(), &mint.pubkey()),
+                    &admin,
+                    &[],
+                    USER_BALANCE,
+                )
+                .expect("mint to user"),
+            );
+        }
+        assert!(self.send_as_admin(instructions, &[mint]).await, "initialize bridge");
+
+        let decoy_vault = self.decoy_vault.insecure_clone();
+        let decoy = vec![
+            system_instruction::create_account(
+                &admin,
+                &decoy_vault.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(
+                &spl_token::ID,
+                &decoy_vault.pubkey(),
+                &mint.pubkey(),
+                &bridge::client::bridge_state_address(),
+            )
+            .expect("initialize decoy vault"),
+        ];
+        assert!(self.send_as_admin(decoy, &[&decoy_vault]).await, "create decoy vault");
+    }
+
+    fn admin(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub async fn lock(&mut self, user: usize, amount: u64) -> bool {
+        let vault = self.vault;
+        self.lock_into(user, amount, vault).await
+    }
+
+    /// Lock into a token account the bridge state owns that is not its vault; must be refused,
+    /// or the real vault would fall short of the ledger and trip the breaker on every unlock.
+    pub async fn lock_into_decoy(&mut self, user: usize, amount: u64) -> bool {
+        let decoy_vault = self.decoy_vault.pubkey();
+        self.lock_into(user, amount, decoy_vault).await
+    }
+
+    async fn lock_into(&mut self, user: usize, amount: u64, vault: Pubkey) -> bool {
+        let state = self.bridge_state().await;
+        let user = self.users[user].insecure_clone();
+        let lock = bridge::client::lock_assets(
+            &user.pubkey(),
+            state.next_lock_id,
+            &self.mint,
+            &get_associated_token_address(&user.pubkey(), &self.mint),
+            &vault,
+            amount,
+            [1; 32],
+            [0; 64],
+        );
+        self.send(vec![lock], &user).await
+    }
+
+    /// Vote for the attestation with every listed validator; returns how many votes landed.
+    pub async fn vote(&mut self, attestation: &Attestation, voters: &[usize]) -> usize {
+        let mut landed = 0;
+        for &index in voters {
+            let validator = self.validators[index].insecure_clone();
+            let vote = validator::client::validate_transaction(&validator.pubkey(), *attestation, true);
+            if self.send(vec![vote], &validator).await {
+                landed += 1;
+            }
+        }
+        landed
+    }
+
+    /// Unlock with the votes of `approvers` submitted for the recount.
+    pub async fn unlock(&mut self, attestation: &Attestation, approvers: &[usize]) -> bool {
+        let submitter = self.submitter.insecure_clone();
+        let unlock = self.unlock_instruction(attestation, approvers);
+        self.send(vec![unlock], &submitter).await
+    }
+
+    /// Whether the unlock transaction fits in a packet at all; every recounted vote grows it.
+    pub fn unlock_fits(&self, attestation: &Attestation, approvers: &[usize]) -> bool {
+        let transaction = Transaction::new_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_price(self.nonce),
+                self.unlock_instruction(attestation, approvers),
+            ],
+            Some(&self.submitter.pubkey()),
+        );
+        bincode::serialized_size(&transaction).expect("transaction size") <= PACKET_DATA_SIZE as u64
+    }
+
+    fn unlock_instruction(&self, attestation: &Attestation, approvers: &[usize]) -> Instruction {
+        let approvers: Vec<Pubkey> = approvers.iter().map(|&index| self.validators[index].pubkey()).collect();
+        bridge::client::unlock_assets(
+            &self.submitter.pubkey(),
+            attestation,
+            &get_associated_token_address(&attestation.recipient, &attestation.mint),
+            &self.vault,
+            &approvers,
+        )
+    }
+
+    /// The guardian's emergency pause.
+    pub async fn pause(&mut self) -> bool {
+        let guardian = self.guardian.insecure_clone();
+        self.send(vec![bridge::client::emergency_pause(&guardian.pubkey())], &guardian).await
+    }
+
+    pub async fn unpause(&mut self) -> bool {
+        let accounts = bridge::accounts::EmergencyUnpause {
+            bridge_state: bridge::client::bridge_state_address(),
+            admin: self.admin(),
+        };
+        self.admin_instruction(accounts.to_account_metas(None), bridge::instruction::EmergencyUnpause {}.data())
+            .await
+    }
+
+    pub async fn pause_operations(&mut self, flags: u8) -> bool {
+        let guardian = self.guardian.insecure_clone();
+        self.send(vec![bridge::client::pause_operations(&guardian.pubkey(), flags)], &guardian).await
+    }
+
+    pub async fn unpause_operations(&mut self, flags: u8, as_guardian: bool) -> bool {
+        let signer = self.signer(as_guardian);
+        self.send(vec![bridge::client::unpause_operations(&signer.pubkey(), flags)], &signer).await
+    }
+
+    pub async fn set_mint_paused(&mut self, is_paused: bool, as_guardian: bool) -> bool {
+        let signer = self.signer(as_guardian);
+        let instruction = bridge::client::set_mint_paused(&signer.pubkey(), self.mint, is_paused);
+        self.send(vec![instruction], &signer).await
+    }
+
+    /// One validator recording a Qubic delivery for `lock_id` on its own. Nothing in these
+    /// sequences approves a real lock's outbound attestation, so this must always be refused.
+    pub async fn record_delivery(&mut self, lock_id: u64) -> bool {
+        let Some(lock) = self.lock_account(lock_id).await else {
+            return false;
+        };
+        let attestation = Attestation {
+            domain: self.domain,
+            direction: DIRECTION_SOLANA_TO_QUBIC,
+            lock_id,
+            mint: lock.token_mint,
+            recipient: Pubkey::new_from_array(lock.qubic_destination),
+            amount: lock.actual_amount,
+        };
+        let submitter = self.submitter.insecure_clone();
+        let instruction = bridge::client::validate_bridge(&submitter.pubkey(), &attestation, [9; 32]);
+        self.send(vec![instruction], &submitter).await
+    }
+
+    /// Admin refund once the lock's refund timeout has passed. Until then it must be refused,
+    /// so a refund due later is tried first and the clock moved on after.
+    pub async fn refund(&mut self, lock_id: u64, user: usize) -> bool {
+        let refundable_at = match self.lock_account(lock_id).await {
+            Some(lock) => lock.created_at + LOCK_REFUND_TIMEOUT,
+            None => 0,
+        };
+        let clock: Clock = self.context.banks_client.get_sysvar().await.expect("clock");
+        if clock.unix_timestamp < refundable_at {
+            assert!(!self.send_refund(lock_id, user).await, "refund before the timeout");
+            self.context.set_sysvar(&Clock { unix_timestamp: refundable_at, ..clock });
+        }
+        self.send_refund(lock_id, user).await
+    }
+
+    async fn send_refund(&mut self, lock_id: u64, user: usize) -> bool {
+        let accounts = bridge::accounts::RefundLock {
+            bridge_state: bridge::client::bridge_state_address(),
+            bridge_transaction: bridge::client::bridge_transaction_address(lock_id),
+            user_token_account: get_associated_token_address(&self.users[user].pubkey(), &self.mint),
+            bridge_token_account: self.vault,
+            mint_ledger: bridge::client::mint_ledger_address(&self.mint),
+            admin: self.admin(),
+            token_program: spl_token::ID,
+        };
+        self.admin_instruction(accounts.to_account_metas(None), bridge::instruction::RefundLock { lock_id }.data())
+            .await
+    }
+
+    pub async fn configure_circuit_breaker(&mut self, max_unlock_bps: u16, max_unlocks_per_window: u16) -> bool {
+        let window = if max_unlocks_per_window > 0 { UNLOCK_WINDOW_SLOTS } else { 0 };
+        let admin = self.admin();
+        let instruction = bridge::client::configure_circuit_breaker(&admin, max_unlock_bps, max_unlocks_per_window, window);
+        self.send_as_admin(vec![instruction], &[]).await
+    }
+
+    /// Report `attestation` as approved for a lock that was never made; anyone may.
+    pub async fn report_phantom_lock(&mut self, attestation: Attestation) -> bool {
+        let reporter = self.users[0].insecure_clone();
+        self.send(vec![bridge::client::report_phantom_lock(&reporter.pubkey(), attestation)], &reporter).await
+    }
+
+    fn signer(&self, as_guardian: bool) -> Keypair {
+        if as_guardian {
+            self.guardian.insecure_clone()
+        } else {
+            self.context.payer.insecure_clone()
+        }
+    }
+
+    pub async fn update_config(&mut self, min: u64, max: u64, fee: u64, required_signatures: u8, is_active: bool) -> bool {
+        let accounts = bridge::accounts::UpdateConfig {
+            bridge_state: bridge::client::bridge_state_address(),
+            admin: self.admin(),
+            system_program: system_program::ID,
+        };
+        let data = bridge::instruction::UpdateConfig {
+            new_min_lock_amount: min,
+            new_max_lock_amount: max,
+            new_bridge_fee: fee,
+            new_required_signatures: required_signatures,
+            new_is_active: is_active,
+        };
+        self.admin_instruction(accounts.to_account_metas(None), data.data()).await
+    }
+
+    pub async fn add_validator(&mut self) -> bool {
+        let validator = Keypair::new();
+        let registered = self.register_validator(&validator).await;
+        if registered {
+            self.validators.push(validator);
+        }
+        registered
+    }
+
+    async fn register_validator(&mut self, validator: &Keypair) -> bool {
+        let admin = self.admin();
+        let register = Instruction {
+            program_id: validator::ID,
+            accounts: validator::accounts::RegisterValidator {
+                admin,
+                validator_config: validator::client::validator_config_address(),
+                validator_info: validator::client::validator_info_address(&validator.pubkey()),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: validator::instruction::RegisterValidator {
+                validator_pubkey: validator.pubkey(),
+                qubic_validator_id: validator.pubkey().to_bytes(),
+            }
+            .data(),
+        };
+        let fund = system_instruction::transfer(&admin, &validator.pubkey(), ACCOUNT_LAMPORTS);
+        self.send_as_admin(vec![fund, register], &[]).await
+    }
+
+    pub async fn set_validator_active(&mut self, validator: usize, is_active: bool) -> bool {
+        let validator_pubkey = self.validators[validator].pubkey();
+        let instruction = Instruction {
+            program_id: validator::ID,
+            accounts: validator::accounts::UpdateValidatorStatus {
+                admin: self.admin(),
+                validator_config: validator::client::validator_config_address(),
+                validator_info: validator::client::validator_info_address(&validator_pubkey),
+            }
+            .to_account_metas(None),
+            data: validator::instruction::UpdateValidatorStatus { validator_pubkey, is_active }.data(),
+        };
+        self.send_as_admin(vec![instruction], &[]).await
+    }
+
+    pub fn user(&self, index: usize) -> Pubkey {
+        self.users[index].pubkey()
+    }
+
+    pub fn domain(&self) -> AttestationDomain {
+        self.domain
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    pub async fn bridge_state(&mut self) -> bridge::state::BridgeState {
+        self.anchor_account(bridge::client::bridge_state_address()).await.expect("bridge state")
+    }
+
+    pub async fn lock_account(&mut self, lock_id: u64) -> Option<bridge::state::BridgeTransaction> {
+        self.anchor_account(bridge::client::bridge_transaction_address(lock_id)).await
+    }
+
+    pub async fn mint_ledger(&mut self) -> Option<bridge::state::MintLedger> {
+        self.anchor_account(bridge::client::mint_ledger_address(&self.mint)).await
+    }
+
+    pub async fn vault_balance(&mut self) -> u64 {
+        let vault = self.vault;
+        self.token_balance(vault).await
+    }
+
+    pub async fn user_balance(&mut self, user: usize) -> u64 {
+        let account = get_associated_token_address(&self.users[user].pubkey(), &self.mint);
+        self.token_balance(account).await
+    }
+
+    async fn token_balance(&mut self, account: Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(account).await.expect("banks").expect("token account");
+        spl_token::state::Account::unpack(&account.data).expect("token account").amount
+    }
+
+    async fn anchor_account<T: AccountDeserialize>(&mut self, address: Pubkey) -> Option<T> {
+        let account = self.context.banks_client.get_account(address).await.expect("banks")?;
+        Some(T::try_deserialize(&mut &account.data[..]).expect("account data"))
+    }
+
+    async fn admin_instruction(&mut self, accounts: Vec<solana_sdk::instruction::AccountMeta>, data: Vec<u8>) -> bool {
+        let instruction = Instruction { program_id: bridge::ID, accounts, data };
+        self.send_as_admin(vec![instruction], &[]).await
+    }
+
+    async fn send_as_admin(&mut self, instructions: Vec<Instruction>, signers: &[&Keypair]) -> bool {
+        let admin = self.context.payer.insecure_clone();
+        let mut all = vec![&admin];
+        all.extend_from_slice(signers);
+        self.send_signed(instructions, &all).await
+    }
+
+    async fn send(&mut self, instructions: Vec<Instruction>, signer: &Keypair) -> bool {
+        self.send_signed(instructions, &[signer]).await
+    }
+
+    /// Whether the transaction landed. The first signer pays.
+    async fn send_signed(&mut self, mut instructions: Vec<Instruction>, signers: &[&Keypair]) -> bool {
+        self.nonce += 1;
+        instructions.insert(0, ComputeBudgetInstruction::set_compute_unit_price(self.nonce));
+        let blockhash = self.context.banks_client.get_latest_blockhash().await.expect("blockhash");
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await.is_ok()
+    }
+}
+
+fn create_token_account(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        owner,
+        mint,
+        &spl_token::ID,
+    )
+}
+
+/// Replay `ops` against a fresh bridge and check, after every step, that the program agreed
+/// with the model and that conservation, fee math, lock id order and single unlock hold.
+pub async fn check_sequence(required_signatures: u8, ops: &[Op]) {
+    let mut vm = BridgeVm::start(required_signatures).await;
+    let mut model = Model::new(required_signatures);
+
+    for (step, op) in ops.iter().enumerate() {
+        match *op {
+            Op::Lock { user, amount } => {
+                let user = user as usize % USERS;
+                let expected = model.lock_succeeds(user, amount);
+                let lock_id = vm.bridge_state().await.next_lock_id;
+                assert_eq!(lock_id, model.next_lock_id, "step {}: next lock id", step);
+                assert!(!vm.lock_into_decoy(user, amount).await, "step {}: lock into a decoy vault", step);
+
+                assert_eq!(vm.lock(user, amount).await, expected, "step {}: {:?}", step, op);
+                if expected {
+                    let (actual_amount, fee) = model.fee(amount);
+                    let lock = vm.lock_account(lock_id).await.expect("lock account");
+                    assert_eq!(lock.id, lock_id, "step {}: lock id", step);
+                    assert_eq!((lock.actual_amount, lock.fee), (actual_amount, fee), "step {}: fee math", step);
+                    assert_eq!(lock.amount, lock.actual_amount + lock.fee, "step {}: fee split", step);
+
+                    model.balances[user] -= amount;
+                    model.total_locked += actual_amount;
+                    model.total_fees += fee;
+                    model.next_lock_id += 1;
+                    model.locks.push(ModelLock { id: lock_id, user, actual_amount, fee, processed: false });
+                }
+                assert_eq!(vm.bridge_state().await.next_lock_id, model.next_lock_id, "step {}: lock ids", step);
+            }
+            Op::Unlock { lock } => {
+                if model.locks.is_empty() {
+                    continue;
+                }
+                let index = lock as usize % model.locks.len();
+                let target = model.locks[index].clone();
+                let attestation = Attestation {
+                    domain: vm.domain(),
+                    direction: DIRECTION_QUBIC_TO_SOLANA,
+                    lock_id: target.id,
+                    mint: vm.mint(),
+                    recipient: vm.user(target.user),
+                    amount: target.actual_amount,
+                };
+
+                let voters: Vec<usize> = (0..model.validators_active.len())
+                    .filter(|&v| model.validators_active[v] && !model.has_voted(target.id, v))
+                    .collect();
+                assert_eq!(vm.vote(&attestation, &voters).await, voters.len(), "step {}: votes", step);
+                model.votes.entry(target.id).or_default().extend(voters);
+
+                // Threshold-many active voters as the relayer sends them, plus one deactivated since
+                // if there is one: the recount must skip its vote
+                let voted = &model.votes[&target.id];
+                let mut approvers: Vec<usize> =
+                    voted.iter().copied().filter(|&v| model.validators_active[v]).take(APPROVAL_THRESHOLD).collect();
+                approvers.extend(voted.iter().copied().filter(|&v| !model.validators_active[v]).take(1));
+                let expected = model.unlock_succeeds(&target) && vm.unlock_fits(&attestation, &approvers);
+                assert_eq!(vm.unlock(&attestation, &approvers).await, expected, "step {}: {:?}", step, op);
+                if expected {
+                    if let Some(reason) = model.unlock_tripwire(target.actual_amount) {
+                        // The breaker pauses the bridge instead of releasing anything
+                        model.is_paused = true;
+                        model.pause_reason = reason;
+                        if reason == PAUSE_REASON_UNLOCK_RATE {
+                            model.window_unlocks += 1;
+                        }
+                    } else {
+                        if model.max_unlocks_per_window > 0 {
+                            model.window_unlocks += 1;
+                        }
+                        model.unlocked.insert(target.id);
+                        model.total_unlocked += target.actual_amount;
+                        model.balances[target.user] += target.actual_amount;
+
+                        // No double unlock: the same release again must fail and move nothing
+                        let vault = vm.vault_balance().await;
+                        assert!(!vm.unlock(&attestation, &approvers).await, "step {}: double unlock", step);
+                        assert_eq!(vm.vault_balance().await, vault, "step {}: double unlock moved funds", step);
+                    }
+                }
+            }
+            Op::Pause => {
+                assert!(vm.pause().await, "step {}: pause", step);
+                model.is_paused = true;
+                model.pause_reason = PAUSE_REASON_MANUAL;
+            }
+            Op::Unpause => {
+                assert!(vm.unpause().await, "step {}: unpause", step);
+                model.is_paused = false;
+                model.pause_reason = PAUSE_REASON_NONE;
+            }
+            Op::PauseOperations { flags } => {
+                let expected = Model::pause_flags_valid(flags);
+                assert_eq!(vm.pause_operations(flags).await, expected, "step {}: {:?}", step, op);
+                if expected {
+                    model.pause_flags |= flags;
+                }
+            }
+            Op::UnpauseOperations { flags } => {
+                assert!(!vm.unpause_operations(flags, true).await, "step {}: guardian unpaused", step);
+                let expected = Model::pause_flags_valid(flags);
+                assert_eq!(vm.unpause_operations(flags, false).await, expected, "step {}: {:?}", step, op);
+                if expected {
+                    model.pause_flags &= !flags;
+                }
+            }
+            Op::SetMintPaused { is_paused } => {
+                if !is_paused {
+                    assert!(!vm.set_mint_paused(false, true).await, "step {}: guardian resumed the mint", step);
+                }
+                assert!(vm.set_mint_paused(is_paused, is_paused).await, "step {}: {:?}", step, op);
+                model.mint_paused = is_paused;
+            }
+            Op::Refund { lock } => {
+                if model.locks.is_empty() {
+                    continue;
+                }
+                let index = lock as usize % model.locks.len();
+                let target = model.locks[index].clone();
+                let expected = model.refund_succeeds(&target);
+                assert!(!vm.record_delivery(target.id).await, "step {}: unapproved delivery of {}", step, target.id);
+                assert_eq!(vm.refund(target.id, target.user).await, expected, "step {}: {:?}", step, op);
+                if expected {
+                    model.locks[index].processed = true;
+                    model.total_unlocked += target.actual_amount;
+                    model.total_fees -= target.fee;
+                    model.balances[target.user] += target.actual_amount + target.fee;
+                }
+            }
+            Op::ConfigureCircuitBreaker { max_unlock_bps, max_unlocks_per_window } => {
+                let expected = max_unlock_bps <= 10_000;
+                let configured = vm.configure_circuit_breaker(max_unlock_bps, max_unlocks_per_window).await;
+                assert_eq!(configured, expected, "step {}: {:?}", step, op);
+                if expected {
+                    model.max_unlock_bps = max_unlock_bps;
+                    model.max_unlocks_per_window = max_unlocks_per_window;
+                    model.window_unlocks = 0;
+                }
+            }
+            Op::ReportLock { lock, phantom } => {
+                let lock_id = match (phantom, model.locks.len()) {
+                    (true, _) => model.next_lock_id + lock as u64 % 3,
+                    (false, 0) => continue,
+                    (false, locks) => model.locks[lock as usize % locks].id,
+                };
+                // An amount no lock uses, so every report votes on a fresh attestation
+                let attestation = Attestation {
+                    domain: vm.domain(),
+                    direction: DIRECTION_SOLANA_TO_QUBIC,
+                    lock_id,
+                    mint: vm.mint(),
+                    recipient: vm.user(0),
+                    amount: u64::MAX - step as u64,
+                };
+                let voters: Vec<usize> = (0..model.validators_active.len())
+                    .filter(|&v| model.validators_active[v])
+                    .collect();
+                assert_eq!(vm.vote(&attestation, &voters).await, voters.len(), "step {}: votes", step);
+
+                // The same id on the inbound leg is HM25's, which this bridge has no counter for
+                let inbound = Attestation { direction: DIRECTION_QUBIC_TO_SOLANA, ..attestation };
+                assert_eq!(vm.vote(&inbound, &voters).await, voters.len(), "step {}: inbound votes", step);
+                assert!(!vm.report_phantom_lock(inbound).await, "step {}: inbound report {:?}", step, op);
+
+                let expected = phantom && !voters.is_empty();
+                assert_eq!(vm.report_phantom_lock(attestation).await, expected, "step {}: {:?}", step, op);
+                if expected {
+                    model.is_paused = true;
+                    model.pause_reason = PAUSE_REASON_PHANTOM_LOCK;
+                }
+            }
+            Op::UpdateConfig { min_lock_amount, max_lock_amount, bridge_fee, required_signatures, is_active } => {
+                let expected = Model::config_valid(min_lock_amount, max_lock_amount, bridge_fee, required_signatures);
+                let updated = vm
+                    .update_config(min_lock_amount, max_lock_amount, bridge_fee, required_signatures, is_active)
+                    .await;
+                assert_eq!(updated, expected, "step {}: {:?}", step, op);
+                if expected {
+                    model.min_lock_amount = min_lock_amount;
+                    model.max_lock_amount = max_lock_amount;
+                    model.bridge_fee = bridge_fee;
+                    model.required_signatures = required_signatures;
+                    model.is_active = is_active;
+                }
+            }
+            Op::AddValidator => {
+                if model.validators_active.len() >= MAX_VALIDATORS {
+                    continue;
+                }
+                assert!(vm.add_validator().await, "step {}: add validator", step);
+                model.validators_active.push(true);
+            }
+            Op::SetValidatorActive { validator, is_active } => {
+                let validator = validator as usize % model.validators_active.len();
+                assert!(vm.set_validator_active(validator, is_active).await, "step {}: {:?}", step, op);
+                model.validators_active[validator] = is_active;
+            }
+        }
+
+        let state = vm.bridge_state().await;
+        assert_eq!(
+            (state.is_paused, state.pause_reason),
+            (model.is_paused, model.pause_reason),
+            "step {}: pause state",
+            step
+        );
+
+        // Conservation: the vault holds exactly what is still owed plus fees
+        let vault = vm.vault_balance().await;
+        assert_eq!(vault, model.expected_vault(), "step {}: vault against model", step);
+        if let Some(ledger) = vm.mint_ledger().await {
+            assert_eq!(
+                vault,
+                ledger.total_locked - ledger.total_unlocked + ledger.total_fees,
+                "step {}: vault against mint ledger",
+                step
+            );
+        }
+        for user in 0..USERS {
+            assert_eq!(vm.user_balance(user).await, model.balances[user], "step {}: user {} balance", step, user);
+        }
+    }
+}
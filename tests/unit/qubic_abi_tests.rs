@@ -0,0 +1,201 @@
+// Unit tests for the HM25 inputs and outputs in their C++ byte layout
+use shared::qubic::{
+    abi::{Bit4096, Reader, Writer},
+    hm25::*,
+    QpiStruct, QubicError, NULL_ID,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+        u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn round_trip<T: QpiStruct + PartialEq + std::fmt::Debug>(value: &T) -> Vec<u8> {
+        let bytes = value.encode();
+        assert_eq!(bytes.len(), T::SIZE);
+        assert_eq!(&T::decode(&bytes).unwrap(), value);
+        bytes
+    }
+
+    #[test]
+    fn test_lock_assets_input_layout() {
+        let input = LockAssetsInput { solana_destination: [3u8; 32], amount: 0x0102_0304_0506_0708, memo: [5u8; 64] };
+        let bytes = round_trip(&input);
+
+        assert_eq!(bytes.len(), 104);
+        assert_eq!(bytes[0..32], [3u8; 32]);
+        assert_eq!(bytes[32..40], [8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(bytes[40..104], [5u8; 64]);
+    }
+
+    #[test]
+    fn test_lock_assets_output_layout() {
+        let output = LockAssetsOutput { success: true, lock_id: 4, actual_amount: 990, fee: 10 };
+        let bytes = round_trip(&output);
+
+        assert_eq!(bytes[0], 1);
+        assert_eq!(bytes[1..8], [0u8; 7]);
+        assert_eq!(u64_at(&bytes, 8), 4);
+        assert_eq!(u64_at(&bytes, 16), 990);
+        assert_eq!(u64_at(&bytes, 24), 10);
+    }
+
+    #[test]
+    fn test_unlock_assets_input_layout() {
+        let signers = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        let input = UnlockAssetsInput::new(42, [9u8; 32], 1_000_000, &signers).unwrap();
+        let bytes = round_trip(&input);
+
+        assert_eq!(bytes.len(), 608);
+        assert_eq!(u64_at(&bytes, 0), 42);
+        // `recipient` is an id and starts on the next 32-byte boundary
+        assert_eq!(bytes[8..32], [0u8; 24]);
+        assert_eq!(bytes[32..64], [9u8; 32]);
+        assert_eq!(u64_at(&bytes, 64), 1_000_000);
+        assert_eq!(bytes[72..96], [0u8; 24]);
+        assert_eq!(bytes[96..128], [1u8; 32]);
+        assert_eq!(bytes[128..160], [2u8; 32]);
+        assert_eq!(bytes[160..192], [3u8; 32]);
+        assert!(bytes[192..608].iter().all(|&b| b == 0));
+
+        assert_eq!(input.signers().copied().collect::<Vec<_>>(), signers);
+        assert_eq!(input.validator_signatures[3], NULL_ID);
+    }
+
+    #[test]
+    fn test_unlock_assets_input_limits_signers() {
+        let signers = vec![[1u8; 32]; MAX_VALIDATORS + 1];
+        assert_eq!(
+            UnlockAssetsInput::new(1, [9u8; 32], 1, &signers).unwrap_err(),
+            QubicError::TooManySigners(MAX_VALIDATORS + 1)
+        );
+        let full = UnlockAssetsInput::new(1, [9u8; 32], 1, &signers[..MAX_VALIDATORS]).unwrap();
+        assert_eq!(full.encode()[576..608], [1u8; 32]);
+    }
+
+    #[test]
+    fn test_small_structs_layout() {
+        assert_eq!(round_trip(&UnlockAssetsOutput { success: true, valid_signatures: 3 }), [1, 3]);
+        assert_eq!(round_trip(&SuccessOutput { success: false }), [0]);
+        assert_eq!(round_trip(&ValidatorInput { validator: [6u8; 32] }), [6u8; 32]);
+        assert_eq!(round_trip(&GetLockInfoInput { lock_id: 258 }), [2, 1, 0, 0, 0, 0, 0, 0]);
+        assert!(round_trip(&GetBridgeInfoInput).is_empty());
+    }
+
+    #[test]
+    fn test_update_config_input_layout() {
+        let input = UpdateConfigInput {
+            min_lock_amount: 1_000_000,
+            max_lock_amount: 1_000_000_000_000,
+            bridge_fee: 100,
+            required_signatures: 3,
+            is_active: true,
+        };
+        let bytes = round_trip(&input);
+
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(u64_at(&bytes, 0), 1_000_000);
+        assert_eq!(u64_at(&bytes, 8), 1_000_000_000_000);
+        assert_eq!(u64_at(&bytes, 16), 100);
+        assert_eq!(bytes[24..], [3, 1, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_get_bridge_info_output_layout() {
+        let output = GetBridgeInfoOutput {
+            admin: [4u8; 32],
+            min_lock_amount: 1,
+            max_lock_amount: 2,
+            bridge_fee: 3,
+            is_active: true,
+            validator_count: 5,
+            required_signatures: 6,
+            total_locked: 7,
+            total_unlocked: 8,
+            total_transfers: 9,
+            total_validator_actions: 10,
+            next_lock_id: 11,
+        };
+        let bytes = round_trip(&output);
+
+        assert_eq!(bytes.len(), 128);
+        assert_eq!(bytes[0..32], [4u8; 32]);
+        assert_eq!([u64_at(&bytes, 32), u64_at(&bytes, 40), u64_at(&bytes, 48)], [1, 2, 3]);
+        assert_eq!(bytes[56..64], [1, 0, 0, 0, 0, 0, 0, 0]);
+        let counters: Vec<u64> = (64..120).step_by(8).map(|offset| u64_at(&bytes, offset)).collect();
+        assert_eq!(counters, [5, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(bytes[120..128], [0u8; 8]);
+    }
+
+    #[test]
+    fn test_get_lock_info_output_parsing() {
+        // A `GetLockInfo` response as the node returns it, assembled byte by byte
+        let mut bytes = vec![0u8; 224];
+        bytes[0] = 1;
+        bytes[32..64].copy_from_slice(&[7u8; 32]);
+        bytes[64..72].copy_from_slice(&10_000_000u64.to_le_bytes());
+        bytes[72..80].copy_from_slice(&9_900_000u64.to_le_bytes());
+        bytes[80..88].copy_from_slice(&100_000u64.to_le_bytes());
+        bytes[88..96].copy_from_slice(&15_000_042u64.to_le_bytes());
+        bytes[96..128].copy_from_slice(&[8u8; 32]);
+        bytes[128..192].copy_from_slice(&[b'm'; 64]);
+        bytes[192] = 2;
+
+        let output = GetLockInfoOutput::decode(&bytes).unwrap();
+        assert!(output.found);
+        assert_eq!(output.locker, [7u8; 32]);
+        assert_eq!(output.amount, 10_000_000);
+        assert_eq!(output.actual_amount, 9_900_000);
+        assert_eq!(output.fee, 100_000);
+        assert_eq!(output.timestamp, 15_000_042);
+        assert_eq!(output.solana_destination, [8u8; 32]);
+        assert_eq!(output.memo, [b'm'; 64]);
+        assert_eq!(output.status, 2);
+        assert_eq!(output.encode(), bytes);
+    }
+
+    #[test]
+    fn test_unknown_lock_reads_as_not_found() {
+        let output = GetLockInfoOutput::decode(&[0u8; 224]).unwrap();
+        assert!(!output.found);
+        assert_eq!(output.locker, NULL_ID);
+        assert_eq!(output.amount, 0);
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_size() {
+        assert_eq!(
+            GetLockInfoOutput::decode(&[0u8; 223]).unwrap_err(),
+            QubicError::StructSize { expected: 224, actual: 223 }
+        );
+        assert!(UnlockAssetsInput::decode(&[0u8; 609]).is_err());
+        assert!(GetBridgeInfoInput::decode(&[0]).is_err());
+    }
+
+    #[test]
+    fn test_bit_4096_layout() {
+        let mut bits = Bit4096::new();
+        bits.set(0, true);
+        bits.set(65, true);
+        bits.set(4095, true);
+        assert!(bits.get(65) && !bits.get(64));
+
+        // After a byte, a bit array starts on an 8-byte boundary
+        let bytes = Writer::new().u8(0xAA).bit_array(&bits).finish(8 + 512);
+        assert_eq!(bytes[0], 0xAA);
+        assert_eq!(bytes[1..8], [0u8; 7]);
+        assert_eq!(bytes[8], 1);
+        assert_eq!(bytes[16], 2);
+        assert_eq!(bytes[519], 0x80);
+
+        let mut reader = Reader::new(&bytes, 8 + 512).unwrap();
+        assert_eq!(reader.u8(), 0xAA);
+        assert_eq!(reader.bit_array::<64>(), bits);
+
+        bits.set(65, false);
+        assert!(!bits.get(65));
+    }
+}
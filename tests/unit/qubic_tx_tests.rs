@@ -0,0 +1,211 @@
+// Unit tests for the native Qubic primitives: K12, FourQ signatures, identities and the
+// transaction wire format
+use shared::qubic::{
+    contract_id, fourq,
+    identity::{digest_from_lowercase_identity, lowercase_identity},
+    identity, k12, public_key_from_identity,
+    transaction::{HEADER_SIZE, MAX_INPUT_SIZE, SIGNATURE_SIZE},
+    QubicError, QubicKeypair, QubicTransaction, HM25_CONTRACT_INDEX,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    fn keypair() -> QubicKeypair {
+        QubicKeypair::from_seed(SEED).unwrap()
+    }
+
+    fn procedure_call(keypair: &QubicKeypair) -> QubicTransaction {
+        QubicTransaction::procedure_call(
+            keypair.public_key(),
+            HM25_CONTRACT_INDEX,
+            1_000,
+            15_000_042,
+            1,
+            vec![7u8; 48],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_k12_vectors() {
+        // From the KangarooTwelve specification, with an empty customization string
+        assert_eq!(
+            hex::encode_upper(k12::k12_digest(b"")),
+            "1AC2D450FC3B4205D19DA7BFCA1B37513C0803577AC7167F06FE2CE1F0EF39E5"
+        );
+        let pattern: Vec<u8> = (0..17u8).collect();
+        assert_eq!(
+            hex::encode_upper(k12::k12_digest(&pattern)),
+            "6BF75FA2239198DB4772E36478F8E19B0F371205F6A9A93A273F51DF37122888"
+        );
+    }
+
+    #[test]
+    fn test_k12_output_is_extendable() {
+        let mut short = [0u8; 32];
+        let mut long = [0u8; 200];
+        k12::kangaroo_twelve(b"qubic", &mut short);
+        k12::kangaroo_twelve(b"qubic", &mut long);
+        assert_eq!(short, long[..32]);
+    }
+
+    #[test]
+    fn test_k12_tree_mode_differs_per_chunk() {
+        // Inputs over one 8 KiB chunk are hashed as a tree; a change in any chunk must show
+        let mut input = vec![0u8; 20_000];
+        let before = k12::k12_digest(&input);
+        input[15_000] = 1;
+        assert_ne!(before, k12::k12_digest(&input));
+    }
+
+    #[test]
+    fn test_seed_validation() {
+        assert_eq!(QubicKeypair::from_seed("abc").unwrap_err(), QubicError::InvalidSeed);
+        let uppercase = SEED.to_uppercase();
+        assert_eq!(QubicKeypair::from_seed(&uppercase).unwrap_err(), QubicError::InvalidSeed);
+        assert!(QubicKeypair::from_seed(&format!("{}\n", SEED)).is_ok());
+    }
+
+    #[test]
+    fn test_identity_round_trip() {
+        let keypair = keypair();
+        let id = keypair.identity();
+        // The identity qubic-cli and the wallet derive for this seed
+        assert_eq!(id, "BZBQFLLBNCXEMGLOBHUVFTLUPLVCPQUASSILFABOFFBCADQSSUPNWLZBQEXK");
+        assert_eq!(public_key_from_identity(&id).unwrap(), keypair.public_key());
+        assert_eq!(identity(&keypair.public_key()), id);
+    }
+
+    #[test]
+    fn test_identity_checksum_is_checked() {
+        let id = keypair().identity();
+        let mut tampered = id.clone().into_bytes();
+        tampered[59] = if tampered[59] == b'A' { b'B' } else { b'A' };
+        let tampered = String::from_utf8(tampered).unwrap();
+        assert!(matches!(public_key_from_identity(&tampered), Err(QubicError::InvalidIdentity(_))));
+        assert!(public_key_from_identity(&id.to_lowercase()).is_err());
+        assert!(public_key_from_identity(&id[..59]).is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let keypair = keypair();
+        let digest = k12::k12_digest(b"lock 42");
+        let signature = keypair.sign(&digest);
+        assert!(fourq::verify(&keypair.public_key(), &digest, &signature));
+
+        // Signing is deterministic
+        assert_eq!(signature, keypair.sign(&digest));
+
+        let other = k12::k12_digest(b"lock 43");
+        assert!(!fourq::verify(&keypair.public_key(), &other, &signature));
+
+        let mut tampered = signature;
+        tampered[40] ^= 1;
+        assert!(!fourq::verify(&keypair.public_key(), &digest, &tampered));
+
+        let stranger = QubicKeypair::from_seed(&"b".repeat(55)).unwrap();
+        assert!(!fourq::verify(&stranger.public_key(), &digest, &signature));
+    }
+
+    #[test]
+    fn test_signature_vector() {
+        // Pinned from this implementation, not yet from qubic-cli or Qubic core: it holds the
+        // signing path still, while conformance rests on the identity above, which runs the same
+        // FourQ arithmetic. Replace with a reference vector for this seed and digest once one
+        // is taken from a node or qubic-cli.
+        let keypair = keypair();
+        let digest = k12::k12_digest(b"lock 42");
+        assert_eq!(
+            hex::encode(keypair.public_key()),
+            "1f590d03e613bdded38b4c0820ac44615f91af12435980b3ede3c08c315a2544"
+        );
+        assert_eq!(hex::encode(digest), "df2d5c4f8e5967c0efd5e026b9ec87461030516590b0ed0891081e10d848f231");
+
+        let signature = hex::decode(
+            "12667bf16c581048f170e664ec648707c4380f3a7fc50ddccc2561338b9fb51b\
+             8a05ff55f052b3def2dfe66174e5e86c382bde7f919f56abc58eff05bab00400",
+        )
+        .unwrap();
+        assert_eq!(keypair.sign(&digest).to_vec(), signature);
+        assert!(fourq::verify(&keypair.public_key(), &digest, &signature.try_into().unwrap()));
+    }
+
+    #[test]
+    fn test_contract_id() {
+        let id = contract_id(HM25_CONTRACT_INDEX);
+        assert_eq!(id[0], 12);
+        assert!(id[1..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_transaction_layout() {
+        let keypair = keypair();
+        let mut transaction = procedure_call(&keypair);
+        transaction.sign(&keypair).unwrap();
+        let bytes = transaction.to_bytes();
+
+        assert_eq!(HEADER_SIZE, 80);
+        assert_eq!(bytes.len(), HEADER_SIZE + 48 + SIGNATURE_SIZE);
+        assert_eq!(bytes[0..32], keypair.public_key());
+        assert_eq!(bytes[32..64], contract_id(HM25_CONTRACT_INDEX));
+        assert_eq!(bytes[64..72], 1_000i64.to_le_bytes());
+        assert_eq!(bytes[72..76], 15_000_042u32.to_le_bytes());
+        assert_eq!(bytes[76..78], 1u16.to_le_bytes());
+        assert_eq!(bytes[78..80], 48u16.to_le_bytes());
+        assert_eq!(bytes[80..128], [7u8; 48]);
+        assert_eq!(bytes[128..], transaction.signature);
+        assert_eq!(transaction.digest(), k12::k12_digest(&bytes[..128]));
+    }
+
+    #[test]
+    fn test_transaction_sign_verify_and_parse() {
+        let keypair = keypair();
+        let mut transaction = procedure_call(&keypair);
+        assert!(!transaction.verify());
+        transaction.sign(&keypair).unwrap();
+        assert!(transaction.verify());
+
+        let parsed = QubicTransaction::from_bytes(&transaction.to_bytes()).unwrap();
+        assert_eq!(parsed, transaction);
+        assert!(parsed.verify());
+
+        let mut retargeted = transaction.clone();
+        retargeted.tick += 1;
+        assert!(!retargeted.verify());
+    }
+
+    #[test]
+    fn test_transaction_id() {
+        let keypair = keypair();
+        let mut transaction = procedure_call(&keypair);
+        transaction.sign(&keypair).unwrap();
+        let id = transaction.id();
+        assert_eq!(id.len(), 60);
+        assert!(id.bytes().all(|c| c.is_ascii_lowercase()));
+        assert_eq!(digest_from_lowercase_identity(&id).unwrap(), k12::k12_digest(&transaction.to_bytes()));
+        assert_eq!(lowercase_identity(&k12::k12_digest(&transaction.to_bytes())), id);
+    }
+
+    #[test]
+    fn test_transaction_rejects_bad_input() {
+        let keypair = keypair();
+        let source = keypair.public_key();
+        let oversized = QubicTransaction::new(source, [0; 32], 0, 1, 1, vec![0; MAX_INPUT_SIZE + 1]);
+        assert_eq!(oversized.unwrap_err(), QubicError::InputTooLarge(MAX_INPUT_SIZE + 1));
+        assert_eq!(QubicTransaction::new(source, [0; 32], -1, 1, 1, vec![]).unwrap_err(), QubicError::InvalidAmount(-1));
+
+        let stranger = QubicKeypair::from_seed(&"b".repeat(55)).unwrap();
+        let mut transaction = procedure_call(&keypair);
+        assert_eq!(transaction.sign(&stranger).unwrap_err(), QubicError::WrongSigner);
+
+        transaction.sign(&keypair).unwrap();
+        let bytes = transaction.to_bytes();
+        assert!(matches!(QubicTransaction::from_bytes(&bytes[..100]), Err(QubicError::Truncated(100))));
+        assert!(QubicTransaction::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
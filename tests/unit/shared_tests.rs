@@ -1,54 +1,30 @@
 // Unit tests for shared module functionality
+use anchor_lang::prelude::Pubkey;
 use shared::{
-    types::{BridgeRequest, BridgeResponse, BridgeDirection, ValidatorSignature},
     crypto::verify_signature,
+    types::{BridgeDirection, BridgeError, BridgeStatus, BridgeTransaction, ValidatorSignature},
 };
-use std::time::SystemTime;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
-    #[test]
-    fn test_bridge_request_creation() {
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
 
-        assert_eq!(request.id, [1u8; 32]);
-        assert_eq!(request.source_chain, "solana");
-        assert_eq!(request.target_chain, "qubic");
-        assert_eq!(request.amount, 1000);
-        assert_eq!(request.direction, BridgeDirection::SolanaToQubic);
+    fn transaction(amount: u64, direction: BridgeDirection) -> BridgeTransaction {
+        BridgeTransaction::new(1, Pubkey::new_unique(), Pubkey::new_unique(), amount, direction)
     }
 
     #[test]
-    fn test_bridge_response_creation() {
-        let response = BridgeResponse {
-            request_id: [1u8; 32],
-            success: true,
-            error_message: None,
-            transaction_hash: Some([4u8; 32]),
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        };
-
-        assert_eq!(response.request_id, [1u8; 32]);
-        assert!(response.success);
-        assert!(response.error_message.is_none());
-        assert!(response.transaction_hash.is_some());
+    fn test_bridge_transaction_creation() {
+        let tx = transaction(1000, BridgeDirection::SolanaToQubic);
+
+        assert_eq!(tx.id, 1);
+        assert_eq!(tx.amount, 1000);
+        assert_eq!(tx.status, BridgeStatus::Pending);
+        assert_eq!(tx.confirmations, 0);
+        assert!(tx.completed_at.is_none());
+        assert!(tx.qubic_tx_hash.is_none());
+        assert!(tx.validator_signatures.is_empty());
+        assert_eq!(tx.direction, BridgeDirection::SolanaToQubic);
     }
 
     #[test]
@@ -57,7 +33,7 @@ mod tests {
         let qubic_to_solana = BridgeDirection::QubicToSolana;
 
         assert_ne!(solana_to_qubic, qubic_to_solana);
-        
+
         // Test serialization consistency
         let serialized_stq = serde_json::to_string(&solana_to_qubic).unwrap();
         let serialized_qts = serde_json::to_string(&qubic_to_solana).unwrap();
@@ -66,121 +42,76 @@ mod tests {
 
     #[test]
     fn test_validator_signature_creation() {
+        let validator = Pubkey::new_unique();
         let validator_signature = ValidatorSignature {
-            validator_id: [5u8; 32],
+            validator_pubkey: validator,
             signature: [6u8; 64],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: 1_700_000_000,
         };
 
-        assert_eq!(validator_signature.validator_id, [5u8; 32]);
+        assert_eq!(validator_signature.validator_pubkey, validator);
         assert_eq!(validator_signature.signature, [6u8; 64]);
         assert!(validator_signature.timestamp > 0);
     }
 
     #[test]
     fn test_serialization_deserialization() {
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
+        let mut tx = transaction(1000, BridgeDirection::SolanaToQubic);
+        tx.qubic_tx_hash = Some([4u8; 32]);
 
         // Test JSON serialization
-        let json_str = serde_json::to_string(&request).unwrap();
-        let deserialized: BridgeRequest = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(deserialized.id, request.id);
-        assert_eq!(deserialized.amount, request.amount);
+        let json_str = serde_json::to_string(&tx).unwrap();
+        let deserialized: BridgeTransaction = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(deserialized.id, tx.id);
+        assert_eq!(deserialized.amount, tx.amount);
+        assert_eq!(deserialized.qubic_tx_hash, tx.qubic_tx_hash);
 
         // Test binary serialization
-        let binary_data = bincode::serialize(&request).unwrap();
-        let deserialized_bin: BridgeRequest = bincode::deserialize(&binary_data).unwrap();
-        assert_eq!(deserialized_bin.id, request.id);
-        assert_eq!(deserialized_bin.amount, request.amount);
+        let binary_data = bincode::serialize(&tx).unwrap();
+        let deserialized_bin: BridgeTransaction = bincode::deserialize(&binary_data).unwrap();
+        assert_eq!(deserialized_bin.id, tx.id);
+        assert_eq!(deserialized_bin.amount, tx.amount);
+        assert_eq!(deserialized_bin.user, tx.user);
     }
 
     #[test]
-    fn test_error_response_handling() {
-        let error_response = BridgeResponse {
-            request_id: [1u8; 32],
-            success: false,
-            error_message: Some("Insufficient funds".to_string()),
-            transaction_hash: None,
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        };
-
-        assert!(!error_response.success);
-        assert!(error_response.error_message.is_some());
-        assert_eq!(error_response.error_message.unwrap(), "Insufficient funds");
-        assert!(error_response.transaction_hash.is_none());
+    fn test_error_display() {
+        assert_eq!(BridgeError::InsufficientBalance.to_string(), "Insufficient balance");
+        assert_eq!(
+            BridgeError::UnknownError("Insufficient funds".to_string()).to_string(),
+            "Unknown error: Insufficient funds"
+        );
     }
 
     #[test]
-    fn test_timestamp_validation() {
-        let current_time = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [3u8; 32],
-            timestamp: current_time,
-            direction: BridgeDirection::SolanaToQubic,
-        };
-
-        // Timestamp should be recent (within the last minute)
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        assert!(request.timestamp <= now);
-        assert!(request.timestamp > now - 60); // Less than 1 minute ago
+    fn test_signature_threshold() {
+        let mut tx = transaction(1000, BridgeDirection::QubicToSolana);
+        assert!(!tx.has_enough_signatures(2));
+
+        for i in 0..2u8 {
+            tx.add_validator_signature(ValidatorSignature {
+                validator_pubkey: Pubkey::new_unique(),
+                signature: [i; 64],
+                timestamp: 1_700_000_000,
+            });
+        }
+        assert!(tx.has_enough_signatures(2));
+        assert!(!tx.has_enough_signatures(3));
     }
 
     #[test]
-    fn test_amount_validation() {
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 0,
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
+    fn test_expiry() {
+        let mut tx = transaction(1000, BridgeDirection::SolanaToQubic);
+        assert!(!tx.is_expired(60));
 
-        // Zero amount should be valid (for testing purposes)
-        assert_eq!(request.amount, 0);
+        tx.created_at -= 120;
+        assert!(tx.is_expired(60));
+    }
 
-        // Test with maximum amount
-        let max_request = BridgeRequest {
-            amount: u64::MAX,
-            ..request
-        };
-        assert_eq!(max_request.amount, u64::MAX);
+    #[test]
+    fn test_amount_boundaries() {
+        assert_eq!(transaction(0, BridgeDirection::SolanaToQubic).amount, 0);
+        assert_eq!(transaction(u64::MAX, BridgeDirection::SolanaToQubic).amount, u64::MAX);
     }
 
     #[test]
@@ -190,10 +121,7 @@ mod tests {
         let test_signature = [1u8; 64];
         let test_pubkey = [2u8; 32];
 
-        // This should not panic (even if it returns false)
-        let result = verify_signature(test_data, &test_signature, &test_pubkey);
-        // We don't assert the result since it's a mock implementation
-        // but we verify the function is callable
-        assert!(result == true || result == false);
+        // verify_signature is a placeholder; only its availability is checked here.
+        let _ = verify_signature(test_data, &test_signature, &test_pubkey);
     }
 }
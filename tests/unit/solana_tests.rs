@@ -1,222 +1,45 @@
 // Unit tests for Solana program functionality
-use shared::types::{BridgeRequest, BridgeDirection};
-use std::time::SystemTime;
+use anchor_lang::prelude::Pubkey;
+use shared::types::{BridgeDirection, BridgeTransaction};
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
-    #[test]
-    fn test_bridge_request_validation() {
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
 
-        // Test basic validation
-        assert!(request.amount > 0);
-        assert_eq!(request.source_chain, "solana");
-        assert_eq!(request.target_chain, "qubic");
-        assert_eq!(request.direction, BridgeDirection::SolanaToQubic);
+    fn transaction(amount: u64, direction: BridgeDirection) -> BridgeTransaction {
+        BridgeTransaction::new(1, Pubkey::new_unique(), Pubkey::new_unique(), amount, direction)
     }
 
     #[test]
     fn test_solana_to_qubic_direction() {
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
+        let mut tx = transaction(1000, BridgeDirection::SolanaToQubic);
+        tx.qubic_destination = [3u8; 32];
 
-        assert_eq!(request.direction, BridgeDirection::SolanaToQubic);
-        assert_eq!(request.source_chain, "solana");
-        assert_eq!(request.target_chain, "qubic");
+        assert_eq!(tx.direction, BridgeDirection::SolanaToQubic);
+        assert_eq!(tx.qubic_destination, [3u8; 32]);
     }
 
     #[test]
     fn test_qubic_to_solana_direction() {
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "qubic".to_string(),
-            target_chain: "solana".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::QubicToSolana,
-        };
+        let recipient = Pubkey::new_unique();
+        let mut tx = transaction(1000, BridgeDirection::QubicToSolana);
+        tx.solana_destination = recipient;
 
-        assert_eq!(request.direction, BridgeDirection::QubicToSolana);
-        assert_eq!(request.source_chain, "qubic");
-        assert_eq!(request.target_chain, "solana");
+        assert_eq!(tx.direction, BridgeDirection::QubicToSolana);
+        assert_eq!(tx.solana_destination, recipient);
     }
 
     #[test]
-    fn test_asset_amount_boundaries() {
-        // Test minimum amount (1 unit)
-        let min_request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1,
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
-
-        assert_eq!(min_request.amount, 1);
-
-        // Test large amount
-        let large_request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1_000_000_000, // 1 billion
-            recipient: [3u8; 32],
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
-
-        assert_eq!(large_request.amount, 1_000_000_000);
+    fn test_bridge_state_pda_is_stable() {
+        let (expected, _) = Pubkey::find_program_address(&[b"bridge_state"], &bridge::ID);
+        assert_eq!(bridge::client::bridge_state_address(), expected);
     }
 
     #[test]
-    fn test_recipient_validation() {
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [255u8; 32], // All bytes set to 255
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            direction: BridgeDirection::SolanaToQubic,
-        };
-
-        // Recipient should be a valid 32-byte array
-        assert_eq!(request.recipient.len(), 32);
-        assert_eq!(request.recipient[0], 255);
-        assert_eq!(request.recipient[31], 255);
-    }
-
-    #[test]
-    fn test_timestamp_generation() {
-        let current_time = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        let request = BridgeRequest {
-            id: [1u8; 32],
-            source_chain: "solana".to_string(),
-            target_chain: "qubic".to_string(),
-            asset_id: [2u8; 32],
-            amount: 1000,
-            recipient: [3u8; 32],
-            timestamp: current_time,
-            direction: BridgeDirection::SolanaToQubic,
-        };
-
-        // Timestamp should be close to current time (within 5 seconds)
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        assert!(request.timestamp <= now);
-        assert!(request.timestamp > now - 5);
-    }
-}
-            &[&payer],
-            recent_blockhash,
+    fn test_lock_pdas_are_distinct() {
+        assert_ne!(
+            bridge::client::bridge_transaction_address(1),
+            bridge::client::bridge_transaction_address(2)
         );
-
-        let result = banks_client.process_transaction(transaction).await;
-        assert!(result.is_ok());
-
-        // Additional assertions to verify the state after locking assets
     }
-
-    #[tokio::test]
-    async fn test_unlock_assets() {
-        let mut program_test = ProgramTest::new(
-            "bridge",
-            id(),
-            processor!(process_instruction),
-        );
-
-        let user = Keypair::new();
-        let asset_amount = 100;
-
-        // Assume assets are already locked
-        // Setup state accordingly
-
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-
-        let transaction = Transaction::new_signed_with_payer(
-            &[unlock_assets_instruction(user.pubkey(), asset_amount)],
-            Some(&payer.pubkey()),
-            &[&payer],
-            recent_blockhash,
-        );
-
-        let result = banks_client.process_transaction(transaction).await;
-        assert!(result.is_ok());
-
-        // Additional assertions to verify the state after unlocking assets
-    }
-
-    #[tokio::test]
-    async fn test_validate_bridge() {
-        let mut program_test = ProgramTest::new(
-            "bridge",
-            id(),
-            processor!(process_instruction),
-        );
-
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-
-        let transaction = Transaction::new_signed_with_payer(
-            &[validate_bridge_instruction()],
-            Some(&payer.pubkey()),
-            &[&payer],
-            recent_blockhash,
-        );
-
-        let result = banks_client.process_transaction(transaction).await;
-        assert!(result.is_ok());
-
-        // Additional assertions to verify the validation logic
-    }
-}
\ No newline at end of file
+}